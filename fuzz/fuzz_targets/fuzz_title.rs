@@ -0,0 +1,17 @@
+#![no_main]
+
+use orgize::elements::Title;
+use orgize::export::{DefaultOrgHandler, OrgHandler};
+use orgize::Element;
+
+libfuzzer_sys::fuzz_target!(|title: Title<'static>| {
+    assert_eq!(
+        title.is_archived(),
+        title.tags.iter().any(|tag| tag == "ARCHIVE")
+    );
+
+    let element = Element::Title(title);
+    let mut writer = Vec::new();
+    DefaultOrgHandler.start(&mut writer, &element).unwrap();
+    DefaultOrgHandler.end(&mut writer, &element).unwrap();
+});
@@ -0,0 +1,915 @@
+//! Agenda utilities, built on `chrono`.
+
+use std::cmp::Ordering;
+
+use chrono::{Datelike, Duration, Local, NaiveDate};
+
+use crate::config::ParseConfig;
+use crate::elements::{Clock, Datetime, Element, Timestamp};
+use crate::{Headline, Org};
+
+/// An inclusive range of dates
+///
+/// The range is empty if `from` is after `to`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateRange {
+    /// First date of this range
+    pub from: NaiveDate,
+    /// Last date of this range
+    pub to: NaiveDate,
+}
+
+impl DateRange {
+    /// Creates a range from `from` to `to`, both inclusive.
+    pub fn from_to(from: NaiveDate, to: NaiveDate) -> DateRange {
+        DateRange { from, to }
+    }
+
+    /// Returns the current week, from Monday to Sunday.
+    pub fn this_week() -> DateRange {
+        DateRange::week_of(today())
+    }
+
+    /// Returns the previous week, from Monday to Sunday.
+    pub fn last_week() -> DateRange {
+        DateRange::week_of(today() - Duration::days(7))
+    }
+
+    /// Returns the current month.
+    pub fn this_month() -> DateRange {
+        DateRange::month_of(today())
+    }
+
+    /// Returns `n` days starting from today, see [`DateRange::n_days_from`].
+    pub fn next_n_days(n: u32) -> DateRange {
+        DateRange::n_days_from(today(), n)
+    }
+
+    /// Returns `n` days starting from `from`.
+    ///
+    /// The range is empty if `n` is 0, and ends at the latest representable
+    /// date if it would go beyond.
+    pub fn n_days_from(from: NaiveDate, n: u32) -> DateRange {
+        let to = match n {
+            0 => from.pred_opt().unwrap_or(NaiveDate::MIN),
+            n => from
+                .checked_add_signed(Duration::days(i64::from(n) - 1))
+                .unwrap_or(NaiveDate::MAX),
+        };
+        DateRange { from, to }
+    }
+
+    /// Returns the week containing `date`, from Monday to Sunday.
+    ///
+    /// The range is clamped to the earliest and latest representable dates.
+    pub fn week_of(date: NaiveDate) -> DateRange {
+        let from = date
+            .checked_sub_signed(Duration::days(i64::from(
+                date.weekday().num_days_from_monday(),
+            )))
+            .unwrap_or(NaiveDate::MIN);
+        let to = date
+            .checked_add_signed(Duration::days(i64::from(
+                6 - date.weekday().num_days_from_monday(),
+            )))
+            .unwrap_or(NaiveDate::MAX);
+        DateRange { from, to }
+    }
+
+    /// Returns the month containing `date`.
+    pub fn month_of(date: NaiveDate) -> DateRange {
+        let from = date.with_day(1).unwrap_or(date);
+        let next_month = if date.month() == 12 {
+            NaiveDate::from_ymd_opt(date.year() + 1, 1, 1)
+        } else {
+            NaiveDate::from_ymd_opt(date.year(), date.month() + 1, 1)
+        };
+        DateRange {
+            from,
+            to: next_month.and_then(|d| d.pred_opt()).unwrap_or(date),
+        }
+    }
+
+    /// Returns `true` if `date` is inside this range.
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.from <= date && date <= self.to
+    }
+
+    /// Returns the number of days in this range.
+    pub fn len(&self) -> usize {
+        if self.is_empty() {
+            0
+        } else {
+            (self.to - self.from).num_days() as usize + 1
+        }
+    }
+
+    /// Returns `true` if this range contains no days.
+    pub fn is_empty(&self) -> bool {
+        self.from > self.to
+    }
+}
+
+impl IntoIterator for DateRange {
+    type Item = NaiveDate;
+    type IntoIter = DateRangeIter;
+
+    fn into_iter(self) -> DateRangeIter {
+        DateRangeIter {
+            next: Some(self.from).filter(|_| !self.is_empty()),
+            to: self.to,
+        }
+    }
+}
+
+impl IntoIterator for &DateRange {
+    type Item = NaiveDate;
+    type IntoIter = DateRangeIter;
+
+    fn into_iter(self) -> DateRangeIter {
+        (*self).into_iter()
+    }
+}
+
+/// An iterator over the days of a `DateRange`
+#[derive(Debug, Clone)]
+pub struct DateRangeIter {
+    next: Option<NaiveDate>,
+    to: NaiveDate,
+}
+
+impl Iterator for DateRangeIter {
+    type Item = NaiveDate;
+
+    fn next(&mut self) -> Option<NaiveDate> {
+        let date = self.next?;
+        self.next = date.succ_opt().filter(|next| *next <= self.to);
+        Some(date)
+    }
+}
+
+impl Headline {
+    /// Returns `true` if this headline is scheduled on `date`.
+    ///
+    /// Repeaters of the scheduled timestamp are expanded, and only the date
+    /// portion of timestamps is compared.
+    ///
+    /// ```rust
+    /// use chrono::NaiveDate;
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse("* TODO Review\nSCHEDULED: <2019-04-08 Mon 10:00 +1w>");
+    /// let headline = org.headlines().next().unwrap();
+    ///
+    /// assert!(headline.is_scheduled_for(NaiveDate::from_ymd_opt(2019, 4, 15).unwrap(), &org));
+    /// assert!(!headline.is_scheduled_for(NaiveDate::from_ymd_opt(2019, 4, 16).unwrap(), &org));
+    /// ```
+    pub fn is_scheduled_for(self, date: NaiveDate, org: &Org) -> bool {
+        matches!(self.title(org).scheduled(), Some(scheduled) if occurs_on(scheduled, date))
+    }
+
+    /// Returns the number of days from `from` until the deadline of this headline.
+    ///
+    /// Negative values mean the deadline is in the past. Repeaters are not
+    /// expanded. Returns `None` if there's no deadline.
+    ///
+    /// ```rust
+    /// use chrono::NaiveDate;
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse("* TODO Report\nDEADLINE: <2019-04-10 Wed>");
+    /// let headline = org.headlines().next().unwrap();
+    ///
+    /// assert_eq!(headline.days_until_deadline(NaiveDate::from_ymd_opt(2019, 4, 8).unwrap(), &org), Some(2));
+    /// assert_eq!(headline.days_until_deadline(NaiveDate::from_ymd_opt(2019, 4, 12).unwrap(), &org), Some(-2));
+    /// ```
+    pub fn days_until_deadline(self, from: NaiveDate, org: &Org) -> Option<i64> {
+        let deadline = timestamp_start(self.title(org).deadline()?)?;
+        Some((naive_date(deadline)? - from).num_days())
+    }
+}
+
+/// An item of agenda view
+///
+/// It holds owned data of a headline, so it can be sorted and filtered
+/// without borrowing the `Org` struct.
+#[derive(Debug, Clone)]
+pub struct AgendaEntry {
+    /// Headline of this entry
+    pub headline: Headline,
+    /// Value of `CATEGORY` property, inherited from ancestors or `#+CATEGORY` keyword
+    pub category: Option<String>,
+    /// Headline todo keyword
+    pub keyword: Option<String>,
+    /// Headline priority cookie
+    pub priority: Option<char>,
+    /// Raw headline's text
+    pub title: String,
+    /// Headline tags
+    pub tags: Vec<String>,
+    /// Date of scheduled timestamp
+    pub scheduled: Option<NaiveDate>,
+    /// Date of deadline timestamp
+    pub deadline: Option<NaiveDate>,
+    /// Total time of closed clocks in the headline's section
+    pub clock_sum: Duration,
+    /// Value of `EFFORT` property
+    pub effort: Option<Duration>,
+}
+
+impl AgendaEntry {
+    /// Constructs an agenda entry from `headline`.
+    pub fn new(headline: Headline, org: &Org) -> AgendaEntry {
+        let title = headline.title(org);
+
+        let category = std::iter::successors(Some(headline), |headline| headline.parent(org))
+            .find_map(|headline| property(headline, "CATEGORY", org))
+            .or_else(|| {
+                org.keywords()
+                    .filter(|keyword| keyword.key.eq_ignore_ascii_case("CATEGORY"))
+                    .last()
+                    .map(|keyword| keyword.value.trim().to_string())
+            });
+
+        let clock_sum = headline
+            .section_node()
+            .into_iter()
+            .flat_map(|section| section.descendants(&org.arena))
+            .filter_map(|node| match &org[node] {
+                Element::Clock(Clock::Closed { duration, .. }) => parse_duration(duration),
+                _ => None,
+            })
+            .fold(Duration::zero(), |sum, duration| {
+                sum.checked_add(&duration).unwrap_or(Duration::MAX)
+            });
+
+        AgendaEntry {
+            headline,
+            category,
+            keyword: title.keyword.as_ref().map(|keyword| keyword.to_string()),
+            priority: title.priority,
+            title: title.raw.to_string(),
+            tags: title.tags.iter().map(|tag| tag.to_string()).collect(),
+            scheduled: title
+                .scheduled()
+                .and_then(timestamp_start)
+                .and_then(naive_date),
+            deadline: title
+                .deadline()
+                .and_then(timestamp_start)
+                .and_then(naive_date),
+            clock_sum,
+            effort: property(headline, "EFFORT", org).and_then(|effort| parse_duration(&effort)),
+        }
+    }
+}
+
+impl AgendaEntry {
+    /// Returns the urgency score of this entry, with default weights.
+    ///
+    /// ```rust
+    /// use orgize::agenda::AgendaEntry;
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse("* TODO [#A] Fix\n* TODO [#C] Refactor\n* Notes\n");
+    /// let scores: Vec<_> = org
+    ///     .headlines()
+    ///     .map(|headline| AgendaEntry::new(headline, &org).urgency_score())
+    ///     .collect();
+    ///
+    /// assert!(scores[0] > scores[1]);
+    /// assert!(scores[1] > scores[2]);
+    /// ```
+    pub fn urgency_score(&self) -> f64 {
+        self.urgency_score_custom(&UrgencyWeights::default(), today())
+    }
+
+    /// Returns the urgency score of this entry, with custom weights and date of today.
+    ///
+    /// The score is the sum of:
+    ///
+    /// 1. the weight of priority cookie;
+    /// 2. the weight of deadline, divided by one plus the days until deadline,
+    ///    past deadlines have the full weight;
+    /// 3. the weight of todo keyword;
+    /// 4. the weight of effort, divided by one plus the effort in hours.
+    pub fn urgency_score_custom(&self, weights: &UrgencyWeights, today: NaiveDate) -> f64 {
+        let priority = self
+            .priority
+            .and_then(|priority| weights.priorities.iter().find(|(p, _)| *p == priority))
+            .map_or(0.0, |(_, weight)| *weight);
+
+        let deadline = self.deadline.map_or(0.0, |deadline| {
+            let days = (deadline - today).num_days().max(0);
+            weights.deadline / (1.0 + days as f64)
+        });
+
+        let keyword = self
+            .keyword
+            .as_ref()
+            .and_then(|keyword| weights.keywords.iter().find(|(k, _)| k == keyword))
+            .map_or(0.0, |(_, weight)| *weight);
+
+        let effort = self.effort.map_or(0.0, |effort| {
+            let hours = effort.num_minutes().max(0) as f64 / 60.0;
+            weights.effort / (1.0 + hours)
+        });
+
+        priority + deadline + keyword + effort
+    }
+}
+
+/// Weights of agenda entry urgency score
+#[derive(Debug, Clone)]
+pub struct UrgencyWeights {
+    /// Weights of priority cookies, default is `A` = 1000, `B` = 100 and `C` = 10
+    pub priorities: Vec<(char, f64)>,
+    /// Weight of a deadline due today, default is 500
+    pub deadline: f64,
+    /// Weights of todo keywords, default is `TODO` = 50 and `WAIT` = 10
+    pub keywords: Vec<(String, f64)>,
+    /// Weight of a zero effort, default is 20
+    pub effort: f64,
+}
+
+impl Default for UrgencyWeights {
+    fn default() -> Self {
+        UrgencyWeights {
+            priorities: vec![('A', 1000.0), ('B', 100.0), ('C', 10.0)],
+            deadline: 500.0,
+            keywords: vec![(String::from("TODO"), 50.0), (String::from("WAIT"), 10.0)],
+            effort: 20.0,
+        }
+    }
+}
+
+/// Agenda sorting order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortOrder {
+    /// Smaller values come first
+    Ascending,
+    /// Larger values come first
+    Descending,
+}
+
+/// Agenda sorting criterion
+///
+/// In ascending order, entries with a missing value are placed last.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AgendaSortCriterion {
+    /// Sorts by category alphabetically
+    Category,
+    /// Sorts by priority, `A` comes first
+    Priority,
+    /// Sorts by deadline date, earlier comes first
+    Deadline,
+    /// Sorts by scheduled date, earlier comes first
+    Scheduled,
+    /// Sorts by tags alphabetically
+    Tag,
+    /// Sorts by title alphabetically
+    Title,
+    /// Sorts by clocked time, shorter comes first
+    ClockSum,
+    /// Sorts by effort, shorter comes first
+    Effort,
+    /// Sorts by urgency score with default weights, more urgent comes first
+    Urgency,
+    /// Sorts by multiple criteria, later ones are used if earlier ones are equal
+    Composite(Vec<(AgendaSortCriterion, SortOrder)>),
+}
+
+impl AgendaSortCriterion {
+    /// Compares two entries, each paired with its precomputed urgency score.
+    fn compare(
+        &self,
+        (a, a_urgency): (&AgendaEntry, f64),
+        (b, b_urgency): (&AgendaEntry, f64),
+    ) -> Ordering {
+        match self {
+            AgendaSortCriterion::Category => none_last(&a.category, &b.category),
+            AgendaSortCriterion::Priority => none_last(&a.priority, &b.priority),
+            AgendaSortCriterion::Deadline => none_last(&a.deadline, &b.deadline),
+            AgendaSortCriterion::Scheduled => none_last(&a.scheduled, &b.scheduled),
+            AgendaSortCriterion::Tag => match (a.tags.is_empty(), b.tags.is_empty()) {
+                (false, true) => Ordering::Less,
+                (true, false) => Ordering::Greater,
+                _ => a.tags.cmp(&b.tags),
+            },
+            AgendaSortCriterion::Title => a.title.cmp(&b.title),
+            AgendaSortCriterion::ClockSum => a.clock_sum.cmp(&b.clock_sum),
+            AgendaSortCriterion::Effort => none_last(&a.effort, &b.effort),
+            AgendaSortCriterion::Urgency => {
+                b_urgency.partial_cmp(&a_urgency).unwrap_or(Ordering::Equal)
+            }
+            AgendaSortCriterion::Composite(criteria) => criteria
+                .iter()
+                .map(|(criterion, order)| match order {
+                    SortOrder::Ascending => criterion.compare((a, a_urgency), (b, b_urgency)),
+                    SortOrder::Descending => criterion.compare((b, b_urgency), (a, a_urgency)),
+                })
+                .find(|ordering| *ordering != Ordering::Equal)
+                .unwrap_or(Ordering::Equal),
+        }
+    }
+}
+
+impl Org<'_> {
+    /// Sorts agenda entries by `criterion`, in ascending order.
+    ///
+    /// The sort is stable, and use [`AgendaSortCriterion::Composite`] for
+    /// descending order or multi-level sorting.
+    ///
+    /// ```rust
+    /// use orgize::agenda::{AgendaEntry, AgendaSortCriterion, SortOrder};
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse("* TODO [#B] Write\n* TODO Read\n* TODO [#A] Run\n");
+    /// let entries = org
+    ///     .headlines()
+    ///     .map(|headline| AgendaEntry::new(headline, &org))
+    ///     .collect();
+    ///
+    /// let entries = Org::sort_agenda_items(
+    ///     entries,
+    ///     AgendaSortCriterion::Composite(vec![
+    ///         (AgendaSortCriterion::Priority, SortOrder::Ascending),
+    ///         (AgendaSortCriterion::Title, SortOrder::Descending),
+    ///     ]),
+    /// );
+    ///
+    /// let titles: Vec<_> = entries.iter().map(|entry| entry.title.as_str()).collect();
+    /// assert_eq!(titles, ["Run", "Write", "Read"]);
+    /// ```
+    ///
+    /// [`AgendaSortCriterion::Composite`]: agenda/enum.AgendaSortCriterion.html#variant.Composite
+    pub fn sort_agenda_items(
+        items: Vec<AgendaEntry>,
+        criterion: AgendaSortCriterion,
+    ) -> Vec<AgendaEntry> {
+        // urgency depends on the current date, so read it only once
+        let (weights, today) = (UrgencyWeights::default(), today());
+        let mut items: Vec<_> = items
+            .into_iter()
+            .map(|item| (item.urgency_score_custom(&weights, today), item))
+            .collect();
+        items.sort_by(|(a_urgency, a), (b_urgency, b)| {
+            criterion.compare((a, *a_urgency), (b, *b_urgency))
+        });
+        items.into_iter().map(|(_, item)| item).collect()
+    }
+
+    /// Removes done headlines which have no section and no sub-headlines,
+    /// and were closed more than `threshold_days` days before `today`.
+    ///
+    /// Done keywords are taken from `config`. Headlines without a `CLOSED`
+    /// timestamp are kept. Returns the number of removed headlines.
+    ///
+    /// ```rust
+    /// use chrono::NaiveDate;
+    /// use orgize::{Org, ParseConfig};
+    ///
+    /// let mut org = Org::parse(
+    ///     "* DONE Old\nCLOSED: [2019-01-01 Tue]\n\
+    ///      * DONE Recent\nCLOSED: [2019-04-01 Mon]\n\
+    ///      * DONE Notes\nCLOSED: [2019-01-01 Tue]\nkept for its body\n\
+    ///      * TODO Open\n\
+    ///      * CANCELED Dropped\nCLOSED: [2019-01-01 Tue]\n",
+    /// );
+    ///
+    /// let today = NaiveDate::from_ymd_opt(2019, 4, 8).unwrap();
+    /// assert_eq!(org.prune_empty_headlines(30, today, &ParseConfig::default()), 1);
+    ///
+    /// let titles: Vec<_> = org.headlines().map(|h| h.title(&org).raw.to_string()).collect();
+    /// assert_eq!(titles, ["Recent", "Notes", "Open", "CANCELED Dropped"]);
+    ///
+    /// let config = ParseConfig {
+    ///     todo_keywords: (vec!["TODO".into()], vec!["DONE".into(), "CANCELED".into()]),
+    ///     ..Default::default()
+    /// };
+    /// let mut org = Org::parse_custom("* CANCELED Dropped\nCLOSED: [2019-01-01 Tue]\n", &config);
+    /// assert_eq!(org.prune_empty_headlines(30, today, &config), 1);
+    /// ```
+    pub fn prune_empty_headlines(
+        &mut self,
+        threshold_days: u32,
+        today: NaiveDate,
+        config: &ParseConfig,
+    ) -> usize {
+        let pruned: Vec<_> = self
+            .headlines()
+            .filter(|headline| {
+                let title = headline.title(self);
+                let done = matches!(&title.keyword, Some(keyword)
+                    if config.todo_keywords.1.iter().any(|k| k == keyword));
+                let closed = title
+                    .closed()
+                    .and_then(timestamp_start)
+                    .and_then(naive_date);
+                done && headline.section_node().is_none()
+                    && headline.is_leaf(self)
+                    && matches!(closed, Some(closed)
+                        if (today - closed).num_days() > i64::from(threshold_days))
+            })
+            .collect();
+
+        for headline in &pruned {
+            headline.detach(self);
+        }
+
+        pruned.len()
+    }
+}
+
+fn none_last<T: Ord>(a: &Option<T>, b: &Option<T>) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => a.cmp(b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+fn property(headline: Headline, key: &str, org: &Org) -> Option<String> {
+    headline
+        .title(org)
+        .properties
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case(key))
+        .map(|(_, value)| value.trim().to_string())
+}
+
+/// Parses duration like `1:30` or `45`(minutes), returning `None` if it's out of range.
+pub(crate) fn parse_duration(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    match value.find(':') {
+        Some(colon) => {
+            let hours: i64 = value[..colon].parse().ok()?;
+            let minutes: i64 = value[colon + 1..].parse().ok()?;
+            Duration::try_hours(hours)?.checked_add(&Duration::try_minutes(minutes)?)
+        }
+        None => Duration::try_minutes(value.parse().ok()?),
+    }
+}
+
+/// Returns `true` if `timestamp`, or one of its repetitions, falls on `date`.
+pub(crate) fn occurs_on(timestamp: &Timestamp, date: NaiveDate) -> bool {
+    let (start, end, repeater) = match timestamp {
+        Timestamp::Active {
+            start, repeater, ..
+        }
+        | Timestamp::Inactive {
+            start, repeater, ..
+        } => (start, start, repeater),
+        Timestamp::ActiveRange {
+            start,
+            end,
+            repeater,
+            ..
+        }
+        | Timestamp::InactiveRange {
+            start,
+            end,
+            repeater,
+            ..
+        } => (start, end, repeater),
+        Timestamp::Diary { .. } => return false,
+    };
+
+    let span = match (naive_date(start), naive_date(end)) {
+        (Some(start), Some(end)) => (end - start).num_days().max(0),
+        _ => return false,
+    };
+
+    // a range occurs on every day between its start and end
+    (0..=span).any(|offset| repeats_on(start, repeater.as_deref(), date - Duration::days(offset)))
+}
+
+/// Returns the start of `timestamp`, or `None` if it's a diary timestamp.
+pub(crate) fn timestamp_start<'a>(timestamp: &'a Timestamp) -> Option<&'a Datetime<'a>> {
+    match timestamp {
+        Timestamp::Active { start, .. }
+        | Timestamp::Inactive { start, .. }
+        | Timestamp::ActiveRange { start, .. }
+        | Timestamp::InactiveRange { start, .. } => Some(start),
+        Timestamp::Diary { .. } => None,
+    }
+}
+
+/// Returns `true` if a timestamp starting at `start` with `repeater` starts on `date`.
+fn repeats_on(start: &Datetime, repeater: Option<&str>, date: NaiveDate) -> bool {
+    let start_date = match naive_date(start) {
+        Some(start_date) => start_date,
+        None => return false,
+    };
+
+    if date <= start_date {
+        return date == start_date;
+    }
+
+    let days = (date - start_date).num_days();
+    let months = i64::from(date.year() - start_date.year()) * 12 + i64::from(date.month())
+        - i64::from(start_date.month());
+
+    // intervals too large to multiply never repeat within representable dates
+    match repeater.and_then(parse_repeater) {
+        Some((n, 'd')) => days % n == 0,
+        Some((n, 'w')) => matches!(n.checked_mul(7), Some(step) if days % step == 0),
+        Some((n, 'm')) => date.day() == start_date.day() && months % n == 0,
+        Some((n, 'y')) => {
+            date.day() == start_date.day() && months % 12 == 0 && (months / 12) % n == 0
+        }
+        Some((n, 'h')) => {
+            // minutes from the start to the beginning of `date`
+            let elapsed = days * 24 * 60
+                - i64::from(start.hour.unwrap_or(0)) * 60
+                - i64::from(start.minute.unwrap_or(0));
+            let first = n
+                .checked_mul(60)
+                .and_then(|step| Some(elapsed.checked_add(step - 1)? / step * step));
+            matches!(first, Some(first) if first < elapsed + 24 * 60)
+        }
+        _ => false,
+    }
+}
+
+/// Parses repeater like `+1w`, `++2d` or `.+1m` into its interval and unit.
+fn parse_repeater(repeater: &str) -> Option<(i64, char)> {
+    let repeater = repeater.trim_start_matches(&['+', '.'][..]);
+    let unit = repeater.chars().last()?;
+    let n = repeater[..repeater.len() - unit.len_utf8()].parse().ok()?;
+    if n > 0 {
+        Some((n, unit))
+    } else {
+        None
+    }
+}
+
+pub(crate) fn naive_date(datetime: &Datetime) -> Option<NaiveDate> {
+    NaiveDate::from_ymd_opt(
+        datetime.year.into(),
+        datetime.month.into(),
+        datetime.day.into(),
+    )
+}
+
+fn today() -> NaiveDate {
+    Local::now().naive_local().date()
+}
+
+#[test]
+fn date_range() {
+    let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+    let week = DateRange::week_of(date(2019, 4, 10));
+    assert_eq!(
+        week,
+        DateRange::from_to(date(2019, 4, 8), date(2019, 4, 14))
+    );
+    assert_eq!(week.len(), 7);
+    assert!(week.contains(date(2019, 4, 14)));
+    assert!(!week.contains(date(2019, 4, 15)));
+
+    assert_eq!(
+        DateRange::month_of(date(2019, 12, 25)),
+        DateRange::from_to(date(2019, 12, 1), date(2019, 12, 31))
+    );
+    assert_eq!(DateRange::month_of(date(2020, 2, 3)).len(), 29);
+
+    assert_eq!(
+        DateRange::from_to(date(2019, 2, 27), date(2019, 3, 1))
+            .into_iter()
+            .collect::<Vec<_>>(),
+        vec![date(2019, 2, 27), date(2019, 2, 28), date(2019, 3, 1)]
+    );
+
+    let empty = DateRange::from_to(date(2019, 4, 8), date(2019, 4, 7));
+    assert!(empty.is_empty());
+    assert_eq!(empty.into_iter().count(), 0);
+
+    assert_eq!(
+        DateRange::n_days_from(date(2019, 2, 27), 3),
+        DateRange::from_to(date(2019, 2, 27), date(2019, 3, 1))
+    );
+    assert!(DateRange::n_days_from(date(2019, 4, 8), 0).is_empty());
+    assert_eq!(
+        DateRange::n_days_from(date(2019, 4, 8), u32::MAX).to,
+        NaiveDate::MAX
+    );
+}
+
+#[test]
+fn is_scheduled_for() {
+    let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+    let org = Org::parse(
+        "* Weekly\nSCHEDULED: <2019-04-08 Mon +2w>\n\
+         * Monthly\nSCHEDULED: <2019-01-31 Thu .+1m>\n\
+         * Hourly\nSCHEDULED: <2019-04-08 Mon 20:00 ++30h>\n\
+         * Range\nSCHEDULED: <2019-04-08 Mon>--<2019-04-10 Wed>\n\
+         * None\n\
+         * Huge weeks\nSCHEDULED: <2019-04-08 Mon +9223372036854775807w>\n\
+         * Huge hours\nSCHEDULED: <2019-04-08 Mon 20:00 +9223372036854775807h>\n",
+    );
+    let headlines: Vec<_> = org.headlines().collect();
+    let scheduled = |index: usize, y, m, d| headlines[index].is_scheduled_for(date(y, m, d), &org);
+
+    assert!(scheduled(0, 2019, 4, 8));
+    assert!(!scheduled(0, 2019, 4, 15));
+    assert!(scheduled(0, 2019, 4, 22));
+    assert!(!scheduled(0, 2019, 3, 25));
+
+    assert!(scheduled(1, 2019, 3, 31));
+    assert!(!scheduled(1, 2019, 2, 28));
+
+    assert!(scheduled(2, 2019, 4, 10));
+    assert!(!scheduled(2, 2019, 4, 9));
+
+    assert!(scheduled(3, 2019, 4, 9));
+    assert!(!scheduled(3, 2019, 4, 11));
+
+    assert!(!scheduled(4, 2019, 4, 8));
+
+    assert!(scheduled(5, 2019, 4, 8));
+    assert!(!scheduled(5, 2019, 4, 15));
+    assert!(scheduled(6, 2019, 4, 8));
+    assert!(!scheduled(6, 2019, 4, 9));
+}
+
+#[test]
+fn days_until_deadline() {
+    let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+    let org = Org::parse(
+        "* Report\nDEADLINE: <2019-04-10 Wed 18:00 +1w>\n\
+         * Range\nDEADLINE: <2019-05-01 Wed>--<2019-05-03 Fri>\n\
+         * None\n",
+    );
+    let headlines: Vec<_> = org.headlines().collect();
+
+    assert_eq!(
+        headlines[0].days_until_deadline(date(2019, 4, 10), &org),
+        Some(0)
+    );
+    assert_eq!(
+        headlines[0].days_until_deadline(date(2019, 4, 17), &org),
+        Some(-7)
+    );
+    assert_eq!(
+        headlines[1].days_until_deadline(date(2019, 4, 1), &org),
+        Some(30)
+    );
+    assert_eq!(
+        headlines[2].days_until_deadline(date(2019, 4, 1), &org),
+        None
+    );
+}
+
+#[test]
+fn durations() {
+    assert_eq!(parse_duration("1:30"), Some(Duration::minutes(90)));
+    assert_eq!(parse_duration(" 45 "), Some(Duration::minutes(45)));
+    assert_eq!(parse_duration("9223372036854775807:00"), None);
+    assert_eq!(parse_duration("9223372036854775807"), None);
+}
+
+#[test]
+fn sort_agenda_items() {
+    let org = Org::parse(
+        "#+CATEGORY: work\n\
+         * TODO Write :doc:\n\
+         DEADLINE: <2019-04-10 Wed>\n\
+         :PROPERTIES:\n\
+         :EFFORT: 1:30\n\
+         :END:\n\
+         * Home\n\
+         :PROPERTIES:\n\
+         :CATEGORY: home\n\
+         :END:\n\
+         ** TODO Clean\n\
+         SCHEDULED: <2019-04-08 Mon>\n\
+         :LOGBOOK:\n\
+         CLOCK: [2019-04-08 Mon 10:00]--[2019-04-08 Mon 11:00] =>  1:00\n\
+         CLOCK: [2019-04-09 Tue 10:00]--[2019-04-09 Tue 10:30] =>  0:30\n\
+         :END:\n",
+    );
+    let entries: Vec<_> = org
+        .headlines()
+        .map(|headline| AgendaEntry::new(headline, &org))
+        .collect();
+
+    assert_eq!(entries[0].category.as_deref(), Some("work"));
+    assert_eq!(entries[0].effort, Some(Duration::minutes(90)));
+    assert_eq!(entries[2].category.as_deref(), Some("home"));
+    assert_eq!(entries[2].clock_sum, Duration::minutes(90));
+
+    let titles = |criterion| {
+        Org::sort_agenda_items(entries.clone(), criterion)
+            .into_iter()
+            .map(|entry| entry.title)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        titles(AgendaSortCriterion::Deadline),
+        ["Write", "Home", "Clean"]
+    );
+    assert_eq!(
+        titles(AgendaSortCriterion::Scheduled),
+        ["Clean", "Write", "Home"]
+    );
+    assert_eq!(
+        titles(AgendaSortCriterion::Category),
+        ["Home", "Clean", "Write"]
+    );
+    assert_eq!(
+        titles(AgendaSortCriterion::ClockSum),
+        ["Write", "Home", "Clean"]
+    );
+    assert_eq!(titles(AgendaSortCriterion::Tag), ["Write", "Home", "Clean"]);
+    assert_eq!(
+        titles(AgendaSortCriterion::Composite(vec![
+            (AgendaSortCriterion::Category, SortOrder::Descending),
+            (AgendaSortCriterion::Title, SortOrder::Ascending),
+        ])),
+        ["Write", "Clean", "Home"]
+    );
+}
+
+#[test]
+fn urgency_score() {
+    let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+    let org = Org::parse_custom(
+        "* TODO [#B] Soon\nDEADLINE: <2019-04-09 Tue>\n\
+         * TODO [#B] Later\nDEADLINE: <2019-04-20 Sat>\n\
+         * WAIT [#B] Waiting\nDEADLINE: <2019-04-20 Sat>\n\
+         * TODO Quick\n:PROPERTIES:\n:EFFORT: 0:30\n:END:\n\
+         * TODO Slow\n:PROPERTIES:\n:EFFORT: 8:00\n:END:\n",
+        &crate::ParseConfig {
+            todo_keywords: (vec!["TODO".into(), "WAIT".into()], vec!["DONE".into()]),
+            ..Default::default()
+        },
+    );
+    let weights = UrgencyWeights::default();
+    let scores: Vec<_> = org
+        .headlines()
+        .map(|headline| {
+            AgendaEntry::new(headline, &org).urgency_score_custom(&weights, date(2019, 4, 8))
+        })
+        .collect();
+
+    assert_eq!(scores[0], 100.0 + 250.0 + 50.0);
+    assert!(scores[0] > scores[1]);
+    assert!(scores[1] > scores[2]);
+    assert!(scores[3] > scores[4]);
+}
+
+#[test]
+fn prune_empty_headlines() {
+    let today = NaiveDate::from_ymd_opt(2019, 4, 8).unwrap();
+    let config = ParseConfig {
+        todo_keywords: (vec!["À_FAIRE".into()], vec!["FINI".into()]),
+        ..Default::default()
+    };
+
+    let mut org = Org::parse("");
+    assert_eq!(org.prune_empty_headlines(0, today, &config), 0);
+
+    let mut org = Org::parse_custom(
+        "* FINI Exactly\nCLOSED: [2019-03-09 Sat]\n\
+         * FINI Older\nCLOSED: [2019-03-08 Fri]\n\
+         * FINI Future\nCLOSED: [2019-05-01 Wed]\n\
+         * FINI Parent\nCLOSED: [2019-01-01 Tue]\n\
+         ** FINI Été\nCLOSED: [2019-01-01 Tue]\n\
+         * DONE Default\nCLOSED: [2019-01-01 Tue]\n",
+        &config,
+    );
+    // only leaves are removed, so parents are kept in the same pass
+    assert_eq!(org.prune_empty_headlines(30, today, &config), 2);
+    let titles: Vec<_> = org
+        .headlines()
+        .map(|h| h.title(&org).raw.to_string())
+        .collect();
+    assert_eq!(titles, ["Exactly", "Future", "Parent", "DONE Default"]);
+}
+
+#[test]
+fn week_of_bounds() {
+    let week = DateRange::week_of(NaiveDate::MIN);
+    assert_eq!(week.from, NaiveDate::MIN);
+    assert_eq!(week.to.weekday(), chrono::Weekday::Sun);
+
+    let week = DateRange::week_of(NaiveDate::MAX);
+    assert_eq!(week.from.weekday(), chrono::Weekday::Mon);
+    assert_eq!(week.to, NaiveDate::MAX);
+
+    let date = NaiveDate::from_ymd_opt(2019, 4, 10).unwrap();
+    assert_eq!(
+        DateRange::week_of(date),
+        DateRange::from_to(
+            NaiveDate::from_ymd_opt(2019, 4, 8).unwrap(),
+            NaiveDate::from_ymd_opt(2019, 4, 14).unwrap()
+        )
+    );
+}
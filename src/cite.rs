@@ -0,0 +1,655 @@
+//! Citations and bibliographies.
+
+use std::fs;
+use std::io::Result as IOResult;
+use std::path::{Path, PathBuf};
+
+use crate::elements::{Element, FnRef, Link, Title};
+use crate::{Headline, Org};
+
+/// A citation link, like `[[cite:key]]` or `[[cite:key1,key2][description]]`
+///
+/// Citation links are parsed as regular [`Link`]s, use
+/// [`CiteLink::from_link`] to read them as citations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CiteLink<'a> {
+    /// Cited keys
+    pub keys: Vec<&'a str>,
+    /// Link description
+    pub desc: Option<&'a str>,
+}
+
+impl CiteLink<'_> {
+    /// Reads `link` as a citation, returns `None` if its path doesn't start
+    /// with `cite:`.
+    ///
+    /// Keys are separated by `,`, and can be prefixed by `&` or `@`, as in
+    /// org-ref and org-cite.
+    ///
+    /// ```rust
+    /// use orgize::cite::CiteLink;
+    /// use orgize::elements::Link;
+    ///
+    /// let link = Link {
+    ///     path: "cite:&knuth84, @lamport94".into(),
+    ///     desc: Some("TeX".into()),
+    /// };
+    /// assert_eq!(
+    ///     CiteLink::from_link(&link),
+    ///     Some(CiteLink {
+    ///         keys: vec!["knuth84", "lamport94"],
+    ///         desc: Some("TeX"),
+    ///     })
+    /// );
+    ///
+    /// let link = Link {
+    ///     path: "https://example.com".into(),
+    ///     desc: None,
+    /// };
+    /// assert_eq!(CiteLink::from_link(&link), None);
+    /// ```
+    pub fn from_link<'a>(link: &'a Link) -> Option<CiteLink<'a>> {
+        let keys = link.path.strip_prefix("cite:")?;
+        Some(CiteLink {
+            keys: keys
+                .split(',')
+                .map(|key| key.trim().trim_start_matches(['&', '@']))
+                .filter(|key| !key.is_empty())
+                .collect(),
+            desc: link.desc.as_deref(),
+        })
+    }
+}
+
+/// An entry of a BibTeX database
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BibEntry {
+    /// Entry type in lowercase, like `article` or `book`
+    pub kind: String,
+    /// Citation key
+    pub key: String,
+    /// Fields in order of appearance, with lowercase names and values
+    /// without their delimiters
+    pub fields: Vec<(String, String)>,
+    /// Source text of this entry
+    pub raw: String,
+}
+
+impl BibEntry {
+    /// Returns the value of field `name`, compared case-insensitively.
+    pub fn field(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// A BibTeX database
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Bibliography {
+    /// Entries in order of appearance
+    pub entries: Vec<BibEntry>,
+}
+
+impl Bibliography {
+    /// Parses a BibTeX database.
+    ///
+    /// `@comment`, `@preamble` and `@string` entries, and malformed entries
+    /// are skipped. String macros are not expanded.
+    ///
+    /// ```rust
+    /// use orgize::cite::Bibliography;
+    ///
+    /// let bib = Bibliography::parse(
+    ///     r#"@comment{ignored}
+    /// @Book{knuth84,
+    ///   author = {Donald E. Knuth},
+    ///   title = {The {\TeX}book},
+    ///   year = 1984,
+    /// }"#,
+    /// );
+    ///
+    /// let entry = bib.get("knuth84").unwrap();
+    /// assert_eq!(entry.kind, "book");
+    /// assert_eq!(entry.field("Title"), Some("The {\\TeX}book"));
+    /// assert_eq!(entry.field("year"), Some("1984"));
+    /// assert_eq!(bib.entries.len(), 1);
+    /// ```
+    pub fn parse(text: &str) -> Bibliography {
+        let mut entries = Vec::new();
+        let mut rest = text;
+        while let Some(start) = rest.find('@') {
+            rest = &rest[start..];
+            match parse_entry(rest) {
+                Some((entry, len)) => {
+                    let kind = entry.kind.as_str();
+                    if kind != "comment" && kind != "preamble" && kind != "string" {
+                        entries.push(entry);
+                    }
+                    rest = &rest[len..];
+                }
+                None => rest = &rest[1..],
+            }
+        }
+        Bibliography { entries }
+    }
+
+    /// Returns the entry with citation key `key`.
+    pub fn get(&self, key: &str) -> Option<&BibEntry> {
+        self.entries.iter().find(|entry| entry.key == key)
+    }
+}
+
+// parses an entry starting with `@`, returns it and its length
+fn parse_entry(input: &str) -> Option<(BibEntry, usize)> {
+    let open = input.find(['{', '('])?;
+    let kind = input[1..open].trim();
+    if kind.is_empty() || !kind.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    let close = if &input[open..=open] == "{" { '}' } else { ')' };
+    let body_len = balanced_len(&input[open + 1..], close)?;
+    let body = &input[open + 1..open + 1 + body_len];
+    let len = open + body_len + 2;
+
+    let kind = kind.to_ascii_lowercase();
+    let (key, mut fields_text) = match body.find(',') {
+        Some(i) => (body[..i].trim(), &body[i + 1..]),
+        None => (body.trim(), ""),
+    };
+
+    let mut fields = Vec::new();
+    loop {
+        fields_text = fields_text.trim_start_matches(|c: char| c == ',' || c.is_whitespace());
+        let eq = match fields_text.find('=') {
+            Some(eq) => eq,
+            None => break,
+        };
+        let name = fields_text[..eq].trim().to_ascii_lowercase();
+        let value_text = fields_text[eq + 1..].trim_start();
+        let (value, value_len) = if let Some(braced) = value_text.strip_prefix('{') {
+            let end = balanced_len(braced, '}')?;
+            (&braced[..end], end + 2)
+        } else if let Some(quoted) = value_text.strip_prefix('"') {
+            let end = quoted.find('"')?;
+            (&quoted[..end], end + 2)
+        } else {
+            let end = value_text.find(',').unwrap_or(value_text.len());
+            (value_text[..end].trim(), end)
+        };
+        fields.push((name, value.to_string()));
+        fields_text = &value_text[value_len..];
+    }
+
+    Some((
+        BibEntry {
+            kind,
+            key: key.to_string(),
+            fields,
+            raw: input[..len].to_string(),
+        },
+        len,
+    ))
+}
+
+// length of `input` before the `close` delimiter matching an already opened one
+fn balanced_len(input: &str, close: char) -> Option<usize> {
+    let open = if close == '}' { '{' } else { '(' };
+    let mut depth = 0;
+    for (i, c) in input.char_indices() {
+        if c == open {
+            depth += 1;
+        } else if c == close {
+            if depth == 0 {
+                return Some(i);
+            }
+            depth -= 1;
+        }
+    }
+    None
+}
+
+/// Citation style of [`OrgCiteProcessor`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CitationStyle {
+    /// `(Knuth 1984)`, with a bibliography sorted by author
+    AuthorYear,
+    /// `[1]`, with a bibliography numbered in order of citation
+    Numeric,
+    /// Footnotes containing the full reference, with a bibliography sorted
+    /// by author
+    Note,
+    /// `(1)`, with a bibliography numbered in order of citation, in the
+    /// Vancouver format
+    Vancouver,
+}
+
+/// Replaces citation links with formatted citations
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OrgCiteProcessor {
+    /// Citation style
+    pub style: CitationStyle,
+}
+
+impl OrgCiteProcessor {
+    /// Returns a copy of `doc` where citation links are replaced by
+    /// citations of entries from `bib`, followed by a `Bibliography`
+    /// headline listing the cited entries.
+    ///
+    /// Keys which aren't found in `bib` are cited by their key, and aren't
+    /// listed in the bibliography.
+    ///
+    /// ```rust
+    /// use orgize::cite::{Bibliography, CitationStyle, OrgCiteProcessor};
+    /// use orgize::Org;
+    ///
+    /// let bib = Bibliography::parse(
+    ///     "@book{knuth84, author = {Knuth, Donald E.}, title = {The {\\TeX}book}, \
+    ///      publisher = {Addison-Wesley}, year = 1984}",
+    /// );
+    /// let org = Org::parse("* Intro\nTypesetting [[cite:knuth84]].\n");
+    ///
+    /// let write = |org: Org| {
+    ///     let mut writer = Vec::new();
+    ///     org.write_org(&mut writer).unwrap();
+    ///     String::from_utf8(writer).unwrap()
+    /// };
+    ///
+    /// let processor = OrgCiteProcessor { style: CitationStyle::AuthorYear };
+    /// assert_eq!(
+    ///     write(processor.process_citations(&org, &bib)),
+    ///     "* Intro\nTypesetting (Knuth 1984).\n\
+    ///      * Bibliography\n\
+    ///      Knuth, Donald E. (1984). The \\TeXbook. Addison-Wesley.\n"
+    /// );
+    ///
+    /// let processor = OrgCiteProcessor { style: CitationStyle::Vancouver };
+    /// assert_eq!(
+    ///     write(processor.process_citations(&org, &bib)),
+    ///     "* Intro\nTypesetting (1).\n\
+    ///      * Bibliography\n\
+    ///      1. Knuth DE. The \\TeXbook. Addison-Wesley; 1984.\n"
+    /// );
+    /// ```
+    pub fn process_citations(&self, doc: &Org, bib: &Bibliography) -> Org<'static> {
+        let keys = doc.cited_keys();
+        let mut org = doc.copy_without(|_| false);
+
+        let links: Vec<_> = org
+            .root
+            .descendants(&org.arena)
+            .filter(|&node| matches!(&org[node], Element::Link(link) if CiteLink::from_link(link).is_some()))
+            .collect();
+        for node in links {
+            let citations: Vec<_> = match &org[node] {
+                Element::Link(link) => CiteLink::from_link(link)
+                    .map(|link| link.keys)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|key| {
+                        let number = keys.iter().position(|k| *k == key).unwrap_or_default() + 1;
+                        self.citation(key, number, bib.get(key))
+                    })
+                    .collect(),
+                _ => continue,
+            };
+            org[node] = match self.style {
+                CitationStyle::AuthorYear | CitationStyle::Vancouver => Element::Text {
+                    value: format!("({})", citations.join("; ")).into(),
+                },
+                CitationStyle::Numeric => Element::Text {
+                    value: format!("[{}]", citations.join(", ")).into(),
+                },
+                CitationStyle::Note => Element::FnRef(FnRef {
+                    label: "".into(),
+                    definition: Some(citations.join("; ").into()),
+                }),
+            };
+        }
+
+        let mut entries: Vec<_> = keys
+            .iter()
+            .enumerate()
+            .filter_map(|(i, key)| Some((i + 1, bib.get(key)?)))
+            .collect();
+        if !entries.is_empty() {
+            if matches!(self.style, CitationStyle::AuthorYear | CitationStyle::Note) {
+                entries.sort_by_key(|(_, entry)| {
+                    (
+                        last_names(entry).join(" ").to_lowercase(),
+                        field(entry, "year"),
+                    )
+                });
+            }
+            let references: Vec<_> = entries
+                .iter()
+                .map(|(number, entry)| self.reference(*number, entry))
+                .collect();
+
+            let mut headline = Headline::new(
+                Title {
+                    raw: "Bibliography".into(),
+                    ..Default::default()
+                },
+                &mut org,
+            );
+            headline.set_section_content(references.join("\n\n") + "\n", &mut org);
+            org.document()
+                .append(headline, &mut org)
+                .expect("top level headlines can always be appended");
+        }
+
+        org
+    }
+
+    fn citation(&self, key: &str, number: usize, entry: Option<&BibEntry>) -> String {
+        let entry = match entry {
+            Some(entry) => entry,
+            None if self.style == CitationStyle::Numeric => return String::from("?"),
+            None => return key.to_string(),
+        };
+        match self.style {
+            CitationStyle::AuthorYear => {
+                let names = last_names(entry);
+                let authors = match names.len() {
+                    0 => field(entry, "title"),
+                    1 => names[0].clone(),
+                    2 => format!("{} and {}", names[0], names[1]),
+                    _ => format!("{} et al.", names[0]),
+                };
+                join(&[authors, field(entry, "year")], " ")
+            }
+            CitationStyle::Numeric | CitationStyle::Vancouver => number.to_string(),
+            CitationStyle::Note => {
+                let parts = [
+                    field(entry, "author").replace(" and ", ", "),
+                    field(entry, "title"),
+                    container(entry),
+                    field(entry, "year"),
+                ];
+                join(&parts, ", ") + "."
+            }
+        }
+    }
+
+    fn reference(&self, number: usize, entry: &BibEntry) -> String {
+        let authors = field(entry, "author").replace(" and ", ", ");
+        let title = field(entry, "title");
+        let year = field(entry, "year");
+        match self.style {
+            CitationStyle::AuthorYear => {
+                let authors = if year.is_empty() {
+                    authors
+                } else {
+                    format!("{} ({})", authors, year)
+                };
+                join(&[authors, title, container(entry)], ". ") + "."
+            }
+            CitationStyle::Numeric => format!(
+                "[{}] {}.",
+                number,
+                join(
+                    &[authors, title, join(&[container(entry), year], ", ")],
+                    ". "
+                )
+            ),
+            CitationStyle::Note => {
+                join(
+                    &[authors, title, join(&[container(entry), year], ", ")],
+                    ". ",
+                ) + "."
+            }
+            CitationStyle::Vancouver => {
+                let mut names: Vec<_> = split_names(entry)
+                    .iter()
+                    .map(|(last, first)| {
+                        let initials: String = first
+                            .split(|c: char| c.is_whitespace() || c == '.' || c == '-')
+                            .filter_map(|name| name.chars().next())
+                            .collect();
+                        join(&[last.clone(), initials], " ")
+                    })
+                    .collect();
+                if names.len() > 6 {
+                    names.truncate(6);
+                    names.push(String::from("et al"));
+                }
+                format!(
+                    "{}. {}.",
+                    number,
+                    join(
+                        &[
+                            names.join(", "),
+                            title,
+                            join(&[container(entry), year], "; ")
+                        ],
+                        ". "
+                    )
+                )
+            }
+        }
+    }
+}
+
+// value of field `name` without braces, and with whitespaces collapsed
+fn field(entry: &BibEntry, name: &str) -> String {
+    let value = entry
+        .field(name)
+        .unwrap_or_default()
+        .replace(['{', '}'], "");
+    value.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// `journal`, `booktitle` or `publisher` field
+fn container(entry: &BibEntry) -> String {
+    ["journal", "booktitle", "publisher"]
+        .iter()
+        .map(|name| field(entry, name))
+        .find(|value| !value.is_empty())
+        .unwrap_or_default()
+}
+
+// authors, or editors, as `(last name, first names)`
+fn split_names(entry: &BibEntry) -> Vec<(String, String)> {
+    let mut names = field(entry, "author");
+    if names.is_empty() {
+        names = field(entry, "editor");
+    }
+    names
+        .split(" and ")
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| match name.find(',') {
+            Some(i) => (
+                name[..i].trim().to_string(),
+                name[i + 1..].trim().to_string(),
+            ),
+            None => match name.rfind(' ') {
+                Some(i) => (name[i + 1..].to_string(), name[..i].to_string()),
+                None => (name.to_string(), String::new()),
+            },
+        })
+        .collect()
+}
+
+fn last_names(entry: &BibEntry) -> Vec<String> {
+    split_names(entry)
+        .into_iter()
+        .map(|(last, _)| last)
+        .collect()
+}
+
+// joins non-empty parts with `separator`
+fn join(parts: &[String], separator: &str) -> String {
+    parts
+        .iter()
+        .filter(|part| !part.is_empty())
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+impl Org<'_> {
+    /// Returns all citation links in this document.
+    pub fn cite_links(&self) -> Vec<CiteLink<'_>> {
+        self.root
+            .descendants(&self.arena)
+            .filter_map(|node| match &self[node] {
+                Element::Link(link) => CiteLink::from_link(link),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns the keys cited in this document, in order of first citation
+    /// and without duplicates.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse("See [[cite:b]], [[cite:a,b][both]] and [[https://example.com]].");
+    /// assert_eq!(org.cited_keys(), vec!["b", "a"]);
+    /// ```
+    pub fn cited_keys(&self) -> Vec<&str> {
+        let mut keys = Vec::new();
+        for link in self.cite_links() {
+            for key in link.keys {
+                if !keys.contains(&key) {
+                    keys.push(key);
+                }
+            }
+        }
+        keys
+    }
+
+    /// Returns the BibTeX entries cited in this document, in order of first
+    /// citation.
+    ///
+    /// Entries are looked up in the BibTeX files listed by `#+BIBLIOGRAPHY`
+    /// keywords, separated by whitespace. Relative paths are resolved from
+    /// the directory of [`Org::source_path`], or from the current directory
+    /// if it isn't set. Keys which aren't found are skipped.
+    ///
+    /// Returns an error if any of the files can't be read.
+    ///
+    /// [`Org::source_path`]: ../struct.Org.html#method.source_path
+    ///
+    /// ```rust,no_run
+    /// use orgize::Org;
+    ///
+    /// let mut org = Org::parse("#+BIBLIOGRAPHY: refs.bib\nAs shown by [[cite:knuth84]].");
+    /// org.set_source_path("/home/user/notes/paper.org");
+    ///
+    /// // reads /home/user/notes/refs.bib
+    /// std::fs::write("cited.bib", org.to_bibtex().unwrap()).unwrap();
+    /// ```
+    pub fn to_bibtex(&self) -> IOResult<String> {
+        let base_dir = self.source_path().and_then(Path::parent);
+
+        let mut bibliography = Bibliography::default();
+        for keyword in self.keywords() {
+            if keyword.key.eq_ignore_ascii_case("BIBLIOGRAPHY") {
+                for path in keyword.value.split_whitespace() {
+                    let path = match base_dir {
+                        Some(base_dir) => base_dir.join(path),
+                        None => PathBuf::from(path),
+                    };
+                    let text = fs::read_to_string(path)?;
+                    bibliography
+                        .entries
+                        .extend(Bibliography::parse(&text).entries);
+                }
+            }
+        }
+
+        let mut output = String::new();
+        for key in self.cited_keys() {
+            if let Some(entry) = bibliography.get(key) {
+                output.push_str(&entry.raw);
+                output.push_str("\n\n");
+            }
+        }
+        Ok(output)
+    }
+}
+
+#[test]
+fn process_citations() {
+    let bib = Bibliography::parse(
+        "@book{a, author = {Zed, Ann}, title = {A}, year = 2001}\n\
+         @book{b, author = {Alpha, Bob}, title = {B}, year = 1999}",
+    );
+    let config = crate::ParseConfig {
+        todo_keywords: (vec!["À_FAIRE".into()], vec![]),
+        ..Default::default()
+    };
+    let org = Org::parse_custom(
+        "* À_FAIRE Read [[cite:a]]\nAlso [[cite:b,missing]].\n",
+        &config,
+    );
+    let write = |org: Org| {
+        let mut writer = Vec::new();
+        org.write_org(&mut writer).unwrap();
+        String::from_utf8(writer).unwrap()
+    };
+
+    let processor = OrgCiteProcessor {
+        style: CitationStyle::Note,
+    };
+    let processed = processor.process_citations(&org, &bib);
+    // the keyword is kept, instead of being re-parsed as part of the title
+    let title = processed.headlines().next().unwrap().title(&processed);
+    assert_eq!(title.keyword.as_deref(), Some("À_FAIRE"));
+    assert_eq!(
+        write(processed),
+        "* À_FAIRE Read [fn::Zed, Ann, A, 2001.]\n\
+         Also [fn::Alpha, Bob, B, 1999.; missing].\n\
+         * Bibliography\n\
+         Alpha, Bob. B. 1999.\n\n\
+         Zed, Ann. A. 2001.\n"
+    );
+
+    // no bibliography without cited entries
+    let processor = OrgCiteProcessor {
+        style: CitationStyle::Numeric,
+    };
+    assert_eq!(
+        write(processor.process_citations(&Org::parse("[[cite:x]]"), &bib)),
+        "[?]\n"
+    );
+}
+
+#[test]
+fn to_bibtex() {
+    let dir = crate::test_dir("to-bibtex");
+    fs::create_dir_all(dir.join("refs")).unwrap();
+    fs::write(
+        dir.join("refs/a.bib"),
+        "@article{a, title = \"A\"}\n@book(b, title = {B {b}})\n@misc{c, title = {C}}\n",
+    )
+    .unwrap();
+
+    // relative paths are resolved from the directory of the document
+    let mut org =
+        Org::parse("#+BIBLIOGRAPHY: refs/a.bib\n[[cite:b]] [[cite:missing,a]] [[cite:b]]\n");
+    org.set_source_path(dir.join("paper.org"));
+    assert_eq!(
+        org.to_bibtex().unwrap(),
+        "@book(b, title = {B {b}})\n\n@article{a, title = \"A\"}\n\n"
+    );
+
+    let org = Org::parse_string(format!(
+        "#+BIBLIOGRAPHY: {}\n[[cite:c]]\n",
+        dir.join("refs/a.bib").display()
+    ));
+    assert_eq!(org.to_bibtex().unwrap(), "@misc{c, title = {C}}\n\n");
+
+    // missing files are errors
+    let mut org = Org::parse("#+BIBLIOGRAPHY: missing.bib\n");
+    org.set_source_path(dir.join("paper.org"));
+    assert!(org.to_bibtex().is_err());
+    assert_eq!(Org::parse("[[cite:a]]").to_bibtex().unwrap(), "");
+
+    fs::remove_dir_all(&dir).unwrap();
+}
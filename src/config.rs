@@ -3,16 +3,156 @@
 pub struct ParseConfig {
     /// Headline's todo keywords
     pub todo_keywords: (Vec<String>, Vec<String>),
+    /// Rejects planning lines with a malformed timestamp
+    ///
+    /// Only used by `Org::try_parse_custom`. Other parsing functions, like
+    /// `Org::parse_custom`, ignore it and silently parse such lines as part
+    /// of headline's section.
+    pub strict_planning: bool,
+    /// Parses the document as if `#+STARTUP: indent` is set
+    ///
+    /// Sections are virtually indented to the headline's title, so leading
+    /// spaces up to this width are removed from paragraphs, lists, the
+    /// contents of blocks, fixed-width areas, comments and `table.el`
+    /// tables. Keywords and org tables are never indented once parsed.
+    ///
+    /// Documents containing `#+STARTUP: indent` are parsed this way even if
+    /// this option is `false`, unless a later `#+STARTUP: noindent` unsets it.
+    ///
+    /// ```rust
+    /// use orgize::{Element, Org, ParseConfig};
+    ///
+    /// let config = ParseConfig {
+    ///     org_indent_mode: true,
+    ///     ..Default::default()
+    /// };
+    /// let org = Org::parse_custom("** Title\n   text\n   - item\n     - nested\n", &config);
+    ///
+    /// let mut writer = Vec::new();
+    /// org.write_org(&mut writer).unwrap();
+    /// assert_eq!(
+    ///     String::from_utf8(writer).unwrap(),
+    ///     "** Title\ntext\n- item\n  - nested\n"
+    /// );
+    /// ```
+    pub org_indent_mode: bool,
+    /// Parses a line of stars without title, like `*`, as an empty headline
+    ///
+    /// By default, headline stars must be followed by a space.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ParseConfig};
+    ///
+    /// let config = ParseConfig {
+    ///     allow_empty_headline_stars: true,
+    ///     ..Default::default()
+    /// };
+    /// let org = Org::parse_custom("*\ntext\n", &config);
+    ///
+    /// let headline = org.headlines().next().unwrap();
+    /// assert_eq!(headline.level(), 1);
+    /// assert_eq!(headline.title(&org).raw, "");
+    ///
+    /// assert!(Org::parse("*\ntext\n").headlines().next().is_none());
+    /// ```
+    pub allow_empty_headline_stars: bool,
 }
 
 impl Default for ParseConfig {
     fn default() -> Self {
         ParseConfig {
             todo_keywords: (vec![String::from("TODO")], vec![String::from("DONE")]),
+            strict_planning: false,
+            org_indent_mode: false,
+            allow_empty_headline_stars: false,
         }
     }
 }
 
+impl ParseConfig {
+    /// Creates a `ParseConfig` with todo keywords read from environment
+    /// variables.
+    ///
+    /// `ORG_TODO_KEYWORDS` and `ORG_DONE_KEYWORDS` are read as described in
+    /// [`from_vars`](ParseConfig::from_vars).
+    pub fn from_env() -> ParseConfig {
+        ParseConfig::from_vars(|name| std::env::var(name).ok())
+    }
+
+    /// Creates a `ParseConfig` with todo keywords read from variables looked
+    /// up by `var`.
+    ///
+    /// `ORG_TODO_KEYWORDS` and `ORG_DONE_KEYWORDS` contain comma or
+    /// whitespace separated keywords. Done keywords are removed from the
+    /// todo keywords, so both variables can list the full sequence. Unset or
+    /// empty variables keep the default `TODO` and `DONE`.
+    ///
+    /// ```rust
+    /// use orgize::ParseConfig;
+    ///
+    /// let config = ParseConfig::from_vars(|name| match name {
+    ///     "ORG_TODO_KEYWORDS" => Some("TODO,IN-PROGRESS,DONE".into()),
+    ///     "ORG_DONE_KEYWORDS" => Some("DONE,CANCELLED".into()),
+    ///     _ => None,
+    /// });
+    /// assert_eq!(config.todo_keywords.0, vec!["TODO", "IN-PROGRESS"]);
+    /// assert_eq!(config.todo_keywords.1, vec!["DONE", "CANCELLED"]);
+    /// ```
+    pub fn from_vars(var: impl Fn(&str) -> Option<String>) -> ParseConfig {
+        let keywords = |name| -> Vec<String> {
+            var(name)
+                .unwrap_or_default()
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|keyword| !keyword.is_empty())
+                .map(String::from)
+                .collect()
+        };
+
+        let mut config = ParseConfig::default();
+        let done = keywords("ORG_DONE_KEYWORDS");
+        if !done.is_empty() {
+            config.todo_keywords.1 = done;
+        }
+        let todo: Vec<_> = keywords("ORG_TODO_KEYWORDS")
+            .into_iter()
+            .filter(|keyword| !config.todo_keywords.1.contains(keyword))
+            .collect();
+        if !todo.is_empty() {
+            config.todo_keywords.0 = todo;
+        }
+        config
+    }
+}
+
 lazy_static::lazy_static! {
     pub static ref DEFAULT_CONFIG: ParseConfig = ParseConfig::default();
 }
+
+#[test]
+fn from_vars() {
+    let config = ParseConfig::from_vars(|_| None);
+    assert_eq!(config.todo_keywords, DEFAULT_CONFIG.todo_keywords);
+
+    let config = ParseConfig::from_vars(|_| Some(" ,, ".into()));
+    assert_eq!(config.todo_keywords, DEFAULT_CONFIG.todo_keywords);
+
+    let config = ParseConfig::from_vars(|name| match name {
+        "ORG_TODO_KEYWORDS" => Some("NEXT WAIT\tÉTÉ".into()),
+        _ => None,
+    });
+    assert_eq!(config.todo_keywords.0, vec!["NEXT", "WAIT", "ÉTÉ"]);
+    assert_eq!(config.todo_keywords.1, vec!["DONE"]);
+
+    // only done keywords: the default todo keyword is kept
+    let config = ParseConfig::from_vars(|name| match name {
+        "ORG_DONE_KEYWORDS" => Some("CANCELLED".into()),
+        _ => None,
+    });
+    assert_eq!(config.todo_keywords.0, vec!["TODO"]);
+    assert_eq!(config.todo_keywords.1, vec!["CANCELLED"]);
+
+    // every todo keyword is also a done keyword
+    let config = ParseConfig::from_vars(|_| Some("DONE".into()));
+    assert_eq!(config.todo_keywords.0, vec!["TODO"]);
+    assert_eq!(config.todo_keywords.1, vec!["DONE"]);
+}
@@ -0,0 +1,100 @@
+//! `Arbitrary` implementations for fuzzing and property-based tests.
+//!
+//! Implemented for the owned (`'static`) versions of elements, since
+//! `Arbitrary` can't borrow strings from the fuzzer input for any lifetime.
+
+use std::borrow::Cow;
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::elements::{Datetime, Planning, PropertiesMap, Timestamp, Title};
+use crate::MAX_HEADLINE_LEVEL;
+
+fn cow(u: &mut Unstructured) -> Result<Cow<'static, str>> {
+    Ok(Cow::Owned(String::arbitrary(u)?))
+}
+
+fn option_cow(u: &mut Unstructured) -> Result<Option<Cow<'static, str>>> {
+    Ok(Option::<String>::arbitrary(u)?.map(Cow::Owned))
+}
+
+impl<'a> Arbitrary<'a> for Datetime<'static> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Datetime {
+            year: u.arbitrary()?,
+            month: u.arbitrary()?,
+            day: u.arbitrary()?,
+            dayname: cow(u)?,
+            hour: u.arbitrary()?,
+            minute: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Timestamp<'static> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.int_in_range(0..=4)? {
+            0 => Timestamp::Active {
+                start: u.arbitrary()?,
+                repeater: option_cow(u)?,
+                delay: option_cow(u)?,
+            },
+            1 => Timestamp::Inactive {
+                start: u.arbitrary()?,
+                repeater: option_cow(u)?,
+                delay: option_cow(u)?,
+            },
+            2 => Timestamp::ActiveRange {
+                start: u.arbitrary()?,
+                end: u.arbitrary()?,
+                repeater: option_cow(u)?,
+                delay: option_cow(u)?,
+            },
+            3 => Timestamp::InactiveRange {
+                start: u.arbitrary()?,
+                end: u.arbitrary()?,
+                repeater: option_cow(u)?,
+                delay: option_cow(u)?,
+            },
+            _ => Timestamp::Diary { value: cow(u)? },
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Planning<'static> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Planning {
+            deadline: u.arbitrary()?,
+            scheduled: u.arbitrary()?,
+            closed: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for PropertiesMap<'static> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let pairs: Vec<(String, String)> = u.arbitrary()?;
+        Ok(PropertiesMap {
+            pairs: pairs
+                .into_iter()
+                .map(|(key, value)| (Cow::Owned(key), Cow::Owned(value)))
+                .collect(),
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Title<'static> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let tags: Vec<String> = u.arbitrary()?;
+        Ok(Title {
+            level: u.int_in_range(1..=MAX_HEADLINE_LEVEL)?,
+            priority: u.arbitrary()?,
+            tags: tags.into_iter().map(Cow::Owned).collect(),
+            keyword: option_cow(u)?,
+            raw: cow(u)?,
+            planning: u.arbitrary()?,
+            properties: u.arbitrary()?,
+            post_blank: u.int_in_range(0..=3)?,
+        })
+    }
+}
@@ -1,4 +1,6 @@
 use std::borrow::Cow;
+use std::convert::TryFrom;
+use std::time::Duration;
 
 use nom::{
     bytes::complete::tag,
@@ -107,6 +109,36 @@ impl Clock<'_> {
         }
     }
 
+    /// Returns the time elapsed between start and end, computed from the
+    /// timestamps rather than the written duration, or `None` if the clock
+    /// is running, ends before it starts or has an invalid date.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    /// use orgize::elements::Element;
+    /// use std::time::Duration;
+    ///
+    /// let org = Org::parse("CLOCK: [2019-04-08 Mon 23:30]--[2019-04-09 Tue 01:15] =>  1:45\n");
+    /// let clock = org
+    ///     .arena()
+    ///     .iter()
+    ///     .find_map(|node| match node.get() {
+    ///         Element::Clock(clock) => Some(clock),
+    ///         _ => None,
+    ///     })
+    ///     .unwrap();
+    /// assert_eq!(clock.elapsed(), Some(Duration::from_secs(105 * 60)));
+    /// ```
+    pub fn elapsed(&self) -> Option<Duration> {
+        match self {
+            Clock::Closed { start, end, .. } => {
+                let minutes = minutes_since_epoch(end)? - minutes_since_epoch(start)?;
+                Some(Duration::from_secs(u64::try_from(minutes).ok()? * 60))
+            }
+            Clock::Running { .. } => None,
+        }
+    }
+
     /// Constructs a timestamp from the clock.
     pub fn value(&self) -> Timestamp {
         match &*self {
@@ -136,6 +168,29 @@ impl Clock<'_> {
     }
 }
 
+// converts a datetime into minutes since 1970-01-01, the day count follows
+// http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+//
+// returns `None` for months and days out of range, which the parser accepts,
+// and negative values for dates before the epoch
+fn minutes_since_epoch(datetime: &Datetime) -> Option<i64> {
+    if !(1..=12).contains(&datetime.month) || !(1..=31).contains(&datetime.day) {
+        return None;
+    }
+    let (month, day) = (i64::from(datetime.month), i64::from(datetime.day));
+    let year = i64::from(datetime.year) - if month <= 2 { 1 } else { 0 };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146_097 + day_of_era - 719_468;
+
+    Some(
+        (days * 24 + i64::from(datetime.hour.unwrap_or_default())) * 60
+            + i64::from(datetime.minute.unwrap_or_default()),
+    )
+}
+
 fn parse_internal(input: &str) -> IResult<&str, Clock, ()> {
     let (input, _) = space0(input)?;
     let (input, _) = tag("CLOCK:")(input)?;
@@ -240,3 +295,38 @@ fn parse() {
         ))
     );
 }
+
+#[test]
+fn elapsed() {
+    let elapsed = |input| Clock::parse(input).and_then(|(_, clock)| clock.elapsed());
+
+    assert_eq!(
+        elapsed("CLOCK: [2020-02-28 Fri 12:00]--[2020-03-01 Sun 12:00] => 48:00"),
+        Some(Duration::from_secs(48 * 60 * 60))
+    );
+    assert_eq!(
+        elapsed("CLOCK: [2019-12-31 Tue 23:59]--[2020-01-01 Wed 00:01] =>  0:02"),
+        Some(Duration::from_secs(2 * 60))
+    );
+    assert_eq!(
+        elapsed("CLOCK: [2019-04-09 Tue 10:00]--[2019-04-08 Mon 10:00] => -24:00"),
+        None
+    );
+    assert_eq!(
+        elapsed("CLOCK: [1969-12-31 Wed 23:00]--[1970-01-01 Thu 01:00] =>  2:00"),
+        Some(Duration::from_secs(2 * 60 * 60))
+    );
+    assert_eq!(
+        elapsed("CLOCK: [0000-02-28 Mon 12:00]--[0000-03-01 Wed 12:00] => 48:00"),
+        Some(Duration::from_secs(48 * 60 * 60))
+    );
+    assert_eq!(elapsed("CLOCK: [2019-04-09 Tue 10:00]"), None);
+    assert_eq!(
+        elapsed("CLOCK: [2019-03-00 Fri 12:00]--[2019-03-01 Fri 12:00] => 24:00"),
+        None
+    );
+    assert_eq!(
+        elapsed("CLOCK: [2019-13-01 Fri 12:00]--[2019-03-01 Fri 12:00] => 24:00"),
+        None
+    );
+}
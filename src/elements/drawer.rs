@@ -8,6 +8,7 @@ use nom::{
 };
 
 use crate::parse::combinators::{blank_lines_count, eol, lines_till};
+use crate::validate::DrawerError;
 
 /// Drawer Element
 #[derive(Debug, Default, Clone)]
@@ -29,6 +30,74 @@ impl Drawer<'_> {
         parse_drawer(input).ok()
     }
 
+    /// Validates drawer name, which must be an uppercase letter followed by
+    /// uppercase letters, digits, `_` or `-`.
+    ///
+    /// Since drawer contents are stored as child elements, unclosed drawers
+    /// and invalid property keys are reported by
+    /// `Org::find_broken_property_drawers` instead.
+    ///
+    /// ```rust
+    /// use orgize::elements::Drawer;
+    /// use orgize::DrawerError;
+    ///
+    /// let drawer = Drawer {
+    ///     name: "LOGBOOK".into(),
+    ///     ..Default::default()
+    /// };
+    /// assert!(drawer.validate().is_ok());
+    ///
+    /// let drawer = Drawer {
+    ///     name: "logbook".into(),
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(
+    ///     drawer.validate(),
+    ///     Err(DrawerError::InvalidName { name: "logbook".into() })
+    /// );
+    /// ```
+    pub fn validate(&self) -> Result<(), DrawerError> {
+        let mut chars = self.name.chars();
+        let valid = matches!(chars.next(), Some(c) if c.is_ascii_uppercase())
+            && chars.all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_' || c == '-');
+        if valid {
+            Ok(())
+        } else {
+            Err(DrawerError::InvalidName {
+                name: self.name.to_string(),
+            })
+        }
+    }
+
+    /// Serialises this drawer with the given `contents`, which are stored
+    /// as child elements and can be written with `Org::node_to_org`.
+    ///
+    /// ```rust
+    /// use orgize::elements::Drawer;
+    ///
+    /// let drawer = Drawer {
+    ///     name: "LOGBOOK".into(),
+    ///     pre_blank: 0,
+    ///     post_blank: 1,
+    /// };
+    ///
+    /// assert_eq!(
+    ///     drawer.to_org("- Note taken on [2024-01-15 Mon 10:30]"),
+    ///     ":LOGBOOK:\n- Note taken on [2024-01-15 Mon 10:30]\n:END:\n\n"
+    /// );
+    /// ```
+    pub fn to_org(&self, contents: &str) -> String {
+        let mut output = format!(":{}:\n", self.name);
+        output.push_str(&"\n".repeat(self.pre_blank));
+        output.push_str(contents);
+        if !contents.is_empty() && !contents.ends_with('\n') {
+            output.push('\n');
+        }
+        output.push_str(":END:\n");
+        output.push_str(&"\n".repeat(self.post_blank));
+        output
+    }
+
     pub fn into_owned(self) -> Drawer<'static> {
         Drawer {
             name: self.name.into_owned().into(),
@@ -119,3 +188,30 @@ fn parse() {
     // https://github.com/PoiScript/orgize/issues/9
     assert!(parse_drawer(":SPAGHETTI:\n").is_err());
 }
+
+#[test]
+fn to_org() {
+    use crate::{elements::Element, Org};
+
+    let org = Org::parse("* a\n:LOGBOOK:\n\n- note\n:END:\n\ntext\n");
+    let node = org
+        .root
+        .descendants(&org.arena)
+        .find(|&node| matches!(org[node], Element::Drawer(_)))
+        .unwrap();
+    let drawer = match &org[node] {
+        Element::Drawer(drawer) => drawer,
+        _ => unreachable!(),
+    };
+    let contents: String = node
+        .children(&org.arena)
+        .map(|child| org.node_to_org(child))
+        .collect();
+    assert_eq!(drawer.to_org(&contents), ":LOGBOOK:\n\n- note\n:END:\n\n");
+
+    let drawer = Drawer {
+        name: "NOTES".into(),
+        ..Default::default()
+    };
+    assert_eq!(drawer.to_org(""), ":NOTES:\n:END:\n");
+}
@@ -1,5 +1,7 @@
 //! Org-mode elements
 
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
 pub(crate) mod block;
 pub(crate) mod clock;
 pub(crate) mod comment;
@@ -56,7 +58,7 @@ pub use self::{
 use std::borrow::Cow;
 
 /// Element Enum
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 #[cfg_attr(feature = "ser", derive(serde::Serialize))]
 #[cfg_attr(feature = "ser", serde(tag = "type", rename_all = "kebab-case"))]
 pub enum Element<'a> {
@@ -133,6 +135,171 @@ impl Element<'_> {
         }
     }
 
+    /// Returns `true` if other elements can be inserted into this element.
+    ///
+    /// Unlike `is_container`, elements which only contain objects, like
+    /// paragraphs and titles, are not included.
+    ///
+    /// ```rust
+    /// use orgize::Element;
+    ///
+    /// assert!(Element::Section.accepts_children());
+    /// assert!(!Element::Paragraph { post_blank: 0 }.accepts_children());
+    /// ```
+    pub fn accepts_children(&self) -> bool {
+        matches!(
+            self,
+            Element::SpecialBlock(_)
+                | Element::QuoteBlock(_)
+                | Element::CenterBlock(_)
+                | Element::VerseBlock(_)
+                | Element::Document { .. }
+                | Element::DynBlock(_)
+                | Element::Drawer(_)
+                | Element::FnDef(_)
+                | Element::Headline { .. }
+                | Element::List(_)
+                | Element::ListItem(_)
+                | Element::Section
+        )
+    }
+
+    /// Returns the name of this element type, in kebab case, like
+    /// `"source-block"`.
+    ///
+    /// It's the same as the `type` field of serialized elements.
+    ///
+    /// ```rust
+    /// use orgize::Element;
+    ///
+    /// assert_eq!(Element::Section.type_name(), "section");
+    /// assert_eq!(Element::Paragraph { post_blank: 0 }.type_name(), "paragraph");
+    /// ```
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Element::SpecialBlock(_) => "special-block",
+            Element::QuoteBlock(_) => "quote-block",
+            Element::CenterBlock(_) => "center-block",
+            Element::VerseBlock(_) => "verse-block",
+            Element::CommentBlock(_) => "comment-block",
+            Element::ExampleBlock(_) => "example-block",
+            Element::ExportBlock(_) => "export-block",
+            Element::SourceBlock(_) => "source-block",
+            Element::BabelCall(_) => "babel-call",
+            Element::Section => "section",
+            Element::Clock(_) => "clock",
+            Element::Cookie(_) => "cookie",
+            Element::RadioTarget => "radio-target",
+            Element::Drawer(_) => "drawer",
+            Element::Document { .. } => "document",
+            Element::DynBlock(_) => "dyn-block",
+            Element::FnDef(_) => "fn-def",
+            Element::FnRef(_) => "fn-ref",
+            Element::Headline { .. } => "headline",
+            Element::InlineCall(_) => "inline-call",
+            Element::InlineSrc(_) => "inline-src",
+            Element::Keyword(_) => "keyword",
+            Element::Link(_) => "link",
+            Element::List(_) => "list",
+            Element::ListItem(_) => "list-item",
+            Element::Macros(_) => "macros",
+            Element::Snippet(_) => "snippet",
+            Element::Text { .. } => "text",
+            Element::Paragraph { .. } => "paragraph",
+            Element::Rule(_) => "rule",
+            Element::Timestamp(_) => "timestamp",
+            Element::Target(_) => "target",
+            Element::Bold => "bold",
+            Element::Strike => "strike",
+            Element::Italic => "italic",
+            Element::Underline => "underline",
+            Element::Verbatim { .. } => "verbatim",
+            Element::Code { .. } => "code",
+            Element::Comment(_) => "comment",
+            Element::FixedWidth(_) => "fixed-width",
+            Element::Title(_) => "title",
+            Element::Table(_) => "table",
+            Element::TableRow(_) => "table-row",
+            Element::TableCell(_) => "table-cell",
+        }
+    }
+
+    /// Returns the number of characters, not bytes, in the text content of
+    /// this element.
+    ///
+    /// Only the element's own text is counted, like the value of a `Text` or
+    /// the contents of a `SourceBlock`. Text in child elements is not
+    /// included, sum over the descendants of a node to count it as well.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse("Grüße, *Welt* ~code~");
+    /// let count: usize = org
+    ///     .arena()
+    ///     .iter()
+    ///     .map(|node| node.get().char_count())
+    ///     .sum();
+    ///
+    /// assert_eq!(count, 16);
+    /// ```
+    pub fn char_count(&self) -> usize {
+        let text = match self {
+            Element::CommentBlock(CommentBlock { contents, .. })
+            | Element::ExampleBlock(ExampleBlock { contents, .. })
+            | Element::ExportBlock(ExportBlock { contents, .. })
+            | Element::SourceBlock(SourceBlock { contents, .. }) => contents,
+            Element::InlineSrc(InlineSrc { body, .. }) => body,
+            Element::Link(Link { path, desc }) => desc.as_ref().unwrap_or(path),
+            Element::FnRef(FnRef {
+                definition: Some(definition),
+                ..
+            }) => definition,
+            Element::Cookie(Cookie { value, .. })
+            | Element::Snippet(Snippet { value, .. })
+            | Element::Comment(Comment { value, .. })
+            | Element::FixedWidth(FixedWidth { value, .. })
+            | Element::Keyword(Keyword { value, .. })
+            | Element::BabelCall(BabelCall { value, .. })
+            | Element::Text { value }
+            | Element::Verbatim { value }
+            | Element::Code { value } => value,
+            Element::Target(Target { target }) => target,
+            _ => return 0,
+        };
+        text.chars().count()
+    }
+
+    /// Returns the number of blank lines after this element, if it has one.
+    pub(crate) fn post_blank_mut(&mut self) -> Option<&mut usize> {
+        match self {
+            Element::SpecialBlock(SpecialBlock { post_blank, .. })
+            | Element::QuoteBlock(QuoteBlock { post_blank, .. })
+            | Element::CenterBlock(CenterBlock { post_blank, .. })
+            | Element::VerseBlock(VerseBlock { post_blank, .. })
+            | Element::CommentBlock(CommentBlock { post_blank, .. })
+            | Element::ExampleBlock(ExampleBlock { post_blank, .. })
+            | Element::ExportBlock(ExportBlock { post_blank, .. })
+            | Element::SourceBlock(SourceBlock { post_blank, .. })
+            | Element::BabelCall(BabelCall { post_blank, .. })
+            | Element::Clock(Clock::Closed { post_blank, .. })
+            | Element::Clock(Clock::Running { post_blank, .. })
+            | Element::Drawer(Drawer { post_blank, .. })
+            | Element::DynBlock(DynBlock { post_blank, .. })
+            | Element::FnDef(FnDef { post_blank, .. })
+            | Element::Keyword(Keyword { post_blank, .. })
+            | Element::List(List { post_blank, .. })
+            | Element::Paragraph { post_blank }
+            | Element::Rule(Rule { post_blank })
+            | Element::Comment(Comment { post_blank, .. })
+            | Element::FixedWidth(FixedWidth { post_blank, .. })
+            | Element::Title(Title { post_blank, .. })
+            | Element::Table(Table::Org { post_blank, .. })
+            | Element::Table(Table::TableEl { post_blank, .. }) => Some(post_blank),
+            _ => None,
+        }
+    }
+
     pub fn into_owned(self) -> Element<'static> {
         use Element::*;
 
@@ -60,6 +60,57 @@ impl Planning<'_> {
         }
     }
 
+    /// Returns `true` if `line` starts with a planning keyword, but it isn't
+    /// a valid planning line.
+    pub(crate) fn is_malformed(line: &str) -> bool {
+        let is_keyword = |word: Option<&str>| {
+            matches!(
+                word,
+                Some("DEADLINE:") | Some("SCHEDULED:") | Some("CLOSED:")
+            )
+        };
+        let line = line.trim();
+        is_keyword(line.split_whitespace().next())
+            && (Planning::parse(line).is_none()
+                // a trailing keyword without timestamp is skipped by `parse`
+                || is_keyword(line.split_whitespace().last()))
+    }
+
+    /// Serialises this planning as an org planning line, with keywords in
+    /// `SCHEDULED`, `DEADLINE`, `CLOSED` order, or an empty string if it has
+    /// no timestamps.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse("* DONE a\nCLOSED: [2019-04-09 Tue] SCHEDULED: <2019-04-08 Mon 09:00 +1w>\n");
+    /// let title = org.headlines().next().unwrap().title(&org);
+    ///
+    /// assert_eq!(
+    ///     title.planning.as_ref().unwrap().to_org(),
+    ///     "SCHEDULED: <2019-04-08 Mon 09:00 +1w> CLOSED: [2019-04-09 Tue]\n"
+    /// );
+    /// ```
+    pub fn to_org(&self) -> String {
+        let entries = [
+            ("SCHEDULED", &self.scheduled),
+            ("DEADLINE", &self.deadline),
+            ("CLOSED", &self.closed),
+        ];
+        let line: Vec<_> = entries
+            .iter()
+            .filter_map(|(keyword, timestamp)| {
+                timestamp
+                    .as_ref()
+                    .map(|timestamp| format!("{}: {}", keyword, timestamp.to_org_string()))
+            })
+            .collect();
+        if line.is_empty() {
+            return String::new();
+        }
+        format!("{}\n", line.join(" "))
+    }
+
     pub fn into_owned(self) -> Planning<'static> {
         Planning {
             deadline: self.deadline.map(|x| x.into_owned()),
@@ -96,3 +147,27 @@ fn prase() {
         ))
     )
 }
+
+#[test]
+fn is_malformed() {
+    assert!(!Planning::is_malformed("SCHEDULED: <2019-04-08 Mon>"));
+    assert!(!Planning::is_malformed("Some text"));
+    assert!(Planning::is_malformed("SCHEDULED: tomorrow"));
+    assert!(Planning::is_malformed(
+        "SCHEDULED: <2019-04-08 Mon> DEADLINE:"
+    ));
+    assert!(Planning::is_malformed("CLOSED: [2019-04-08]x"));
+}
+
+#[test]
+fn to_org() {
+    let planning = Planning {
+        deadline: None,
+        scheduled: None,
+        closed: None,
+    };
+    assert_eq!(planning.to_org(), "");
+
+    let (_, planning) = Planning::parse("DEADLINE: <2019-04-08 Mon>").unwrap();
+    assert_eq!(planning.to_org(), "DEADLINE: <2019-04-08 Mon>\n");
+}
@@ -1,13 +1,16 @@
 use std::borrow::Cow;
 
 use nom::{
+    branch::alt,
     bytes::complete::{tag, take, take_till, take_while, take_while_m_n},
-    character::complete::{space0, space1},
-    combinator::{map, map_res, opt},
-    sequence::preceded,
+    character::complete::{digit1, one_of, space0, space1},
+    combinator::{map, map_res, opt, recognize},
+    sequence::{preceded, tuple},
     IResult,
 };
 
+use crate::export::write_timestamp;
+
 /// Datetime Struct
 #[cfg_attr(test, derive(PartialEq))]
 #[cfg_attr(feature = "ser", derive(serde::Serialize))]
@@ -149,6 +152,45 @@ impl Timestamp<'_> {
         parse_diary(input).ok()
     }
 
+    /// Formats this timestamp as a canonical org timestamp string.
+    ///
+    /// ```rust
+    /// use orgize::elements::{Datetime, Timestamp};
+    ///
+    /// let timestamp = Timestamp::Active {
+    ///     start: Datetime {
+    ///         year: 2024,
+    ///         month: 1,
+    ///         day: 15,
+    ///         dayname: "Mon".into(),
+    ///         hour: None,
+    ///         minute: None,
+    ///     },
+    ///     repeater: Some("+1w".into()),
+    ///     delay: Some("-2d".into()),
+    /// };
+    /// assert_eq!(timestamp.to_org_string(), "<2024-01-15 Mon +1w -2d>");
+    ///
+    /// let timestamp = Timestamp::Inactive {
+    ///     start: Datetime {
+    ///         year: 2024,
+    ///         month: 1,
+    ///         day: 15,
+    ///         dayname: "Mon".into(),
+    ///         hour: Some(10),
+    ///         minute: Some(30),
+    ///     },
+    ///     repeater: None,
+    ///     delay: None,
+    /// };
+    /// assert_eq!(timestamp.to_org_string(), "[2024-01-15 Mon 10:30]");
+    /// ```
+    pub fn to_org_string(&self) -> String {
+        let mut writer = Vec::new();
+        write_timestamp(&mut writer, self).expect("writing to vector never fails");
+        String::from_utf8_lossy(&writer).into_owned()
+    }
+
     pub fn into_owned(self) -> Timestamp<'static> {
         match self {
             Timestamp::Active {
@@ -204,8 +246,8 @@ pub fn parse_active(input: &str) -> IResult<&str, Timestamp, ()> {
 
     if input.starts_with('-') {
         let (input, (hour, minute)) = parse_time(&input[1..])?;
+        let (input, (repeater, delay)) = parse_repeater_or_delay(input)?;
         let (input, _) = space0(input)?;
-        // TODO: delay-or-repeater
         let (input, _) = tag(">")(input)?;
         let mut end = start.clone();
         end.hour = Some(hour);
@@ -215,28 +257,33 @@ pub fn parse_active(input: &str) -> IResult<&str, Timestamp, ()> {
             Timestamp::ActiveRange {
                 start,
                 end,
-                repeater: None,
-                delay: None,
+                repeater: repeater.map(Into::into),
+                delay: delay.map(Into::into),
             },
         ));
     }
 
+    let (input, (repeater, delay)) = parse_repeater_or_delay(input)?;
     let (input, _) = space0(input)?;
-    // TODO: delay-or-repeater
     let (input, _) = tag(">")(input)?;
 
     if input.starts_with("--<") {
         let (input, end) = parse_datetime(&input["--<".len()..])?;
+        // cookies of the end are only kept if the start has none
+        let (input, (end_repeater, end_delay)) = parse_repeater_or_delay(input)?;
         let (input, _) = space0(input)?;
-        // TODO: delay-or-repeater
         let (input, _) = tag(">")(input)?;
+        let (repeater, delay) = match (repeater, delay) {
+            (None, None) => (end_repeater, end_delay),
+            cookies => cookies,
+        };
         Ok((
             input,
             Timestamp::ActiveRange {
                 start,
                 end,
-                repeater: None,
-                delay: None,
+                repeater: repeater.map(Into::into),
+                delay: delay.map(Into::into),
             },
         ))
     } else {
@@ -244,8 +291,8 @@ pub fn parse_active(input: &str) -> IResult<&str, Timestamp, ()> {
             input,
             Timestamp::Active {
                 start,
-                repeater: None,
-                delay: None,
+                repeater: repeater.map(Into::into),
+                delay: delay.map(Into::into),
             },
         ))
     }
@@ -257,8 +304,8 @@ pub fn parse_inactive(input: &str) -> IResult<&str, Timestamp, ()> {
 
     if input.starts_with('-') {
         let (input, (hour, minute)) = parse_time(&input[1..])?;
+        let (input, (repeater, delay)) = parse_repeater_or_delay(input)?;
         let (input, _) = space0(input)?;
-        // TODO: delay-or-repeater
         let (input, _) = tag("]")(input)?;
         let mut end = start.clone();
         end.hour = Some(hour);
@@ -268,28 +315,33 @@ pub fn parse_inactive(input: &str) -> IResult<&str, Timestamp, ()> {
             Timestamp::InactiveRange {
                 start,
                 end,
-                repeater: None,
-                delay: None,
+                repeater: repeater.map(Into::into),
+                delay: delay.map(Into::into),
             },
         ));
     }
 
+    let (input, (repeater, delay)) = parse_repeater_or_delay(input)?;
     let (input, _) = space0(input)?;
-    // TODO: delay-or-repeater
     let (input, _) = tag("]")(input)?;
 
     if input.starts_with("--[") {
         let (input, end) = parse_datetime(&input["--[".len()..])?;
+        // cookies of the end are only kept if the start has none
+        let (input, (end_repeater, end_delay)) = parse_repeater_or_delay(input)?;
         let (input, _) = space0(input)?;
-        // TODO: delay-or-repeater
         let (input, _) = tag("]")(input)?;
+        let (repeater, delay) = match (repeater, delay) {
+            (None, None) => (end_repeater, end_delay),
+            cookies => cookies,
+        };
         Ok((
             input,
             Timestamp::InactiveRange {
                 start,
                 end,
-                repeater: None,
-                delay: None,
+                repeater: repeater.map(Into::into),
+                delay: delay.map(Into::into),
             },
         ))
     } else {
@@ -297,8 +349,8 @@ pub fn parse_inactive(input: &str) -> IResult<&str, Timestamp, ()> {
             input,
             Timestamp::Inactive {
                 start,
-                repeater: None,
-                delay: None,
+                repeater: repeater.map(Into::into),
+                delay: delay.map(Into::into),
             },
         ))
     }
@@ -317,6 +369,35 @@ pub fn parse_diary(input: &str) -> IResult<&str, Timestamp, ()> {
     ))
 }
 
+// parses repeater (e.g. `+1w`, `++2d`, `.+1m`) and delay (e.g. `-2d`, `--1y`) cookies,
+// each of them can appear at most once, in any order
+fn parse_repeater_or_delay(input: &str) -> IResult<&str, (Option<&str>, Option<&str>), ()> {
+    let (mut input, mut repeater, mut delay) = (input, None, None);
+
+    for _ in 0..2 {
+        let (input_, cookie) = opt(preceded(
+            space1,
+            recognize(tuple((
+                alt((tag("++"), tag(".+"), tag("+"), tag("--"), tag("-"))),
+                digit1,
+                one_of("hdwmy"),
+            ))),
+        ))(input)?;
+
+        match cookie {
+            Some(cookie) if cookie.starts_with('-') && delay.is_none() => delay = Some(cookie),
+            Some(cookie) if !cookie.starts_with('-') && repeater.is_none() => {
+                repeater = Some(cookie)
+            }
+            _ => break,
+        }
+
+        input = input_;
+    }
+
+    Ok((input, (repeater, delay)))
+}
+
 fn parse_time(input: &str) -> IResult<&str, (u8, u8), ()> {
     let (input, hour) = map_res(take_while_m_n(1, 2, |c: char| c.is_ascii_digit()), |num| {
         u8::from_str_radix(num, 10)
@@ -479,4 +560,80 @@ fn parse() {
             },
         ))
     );
+    assert_eq!(
+        parse_active("<2003-09-16 Tue 09:39 +1w -2d>"),
+        Ok((
+            "",
+            Timestamp::Active {
+                start: Datetime {
+                    year: 2003,
+                    month: 9,
+                    day: 16,
+                    dayname: "Tue".into(),
+                    hour: Some(9),
+                    minute: Some(39),
+                },
+                repeater: Some("+1w".into()),
+                delay: Some("-2d".into())
+            },
+        ))
+    );
+    assert_eq!(
+        parse_inactive("[2003-09-16 Tue .+1d]"),
+        Ok((
+            "",
+            Timestamp::Inactive {
+                start: Datetime {
+                    year: 2003,
+                    month: 9,
+                    day: 16,
+                    dayname: "Tue".into(),
+                    hour: None,
+                    minute: None,
+                },
+                repeater: Some(".+1d".into()),
+                delay: None
+            },
+        ))
+    );
+    assert!(parse_active("<2003-09-16 Tue +1w +2w>").is_err());
+}
+
+#[test]
+fn repeater_and_delay() {
+    let round_trip = |input: &str| {
+        let (_, timestamp) = parse_active(input)
+            .or_else(|_| parse_inactive(input))
+            .unwrap();
+        timestamp.to_org_string()
+    };
+
+    for input in [
+        "<2024-01-01 Mon +1w>",
+        "<2024-01-01 Mon 10:00 .+1d -2d>",
+        "[2024-01-01 Mon ++1m --1y]",
+        "<2024-01-01 Mon +1w>--<2024-01-02 Tue>",
+        "<2024-01-01 Mon -2d>--<2024-01-02 Tue>",
+        "[2024-01-01 Mon +1w -1d]--[2024-01-02 Tue]",
+    ] {
+        assert_eq!(round_trip(input), input);
+    }
+
+    // ranges keep a single repeater and delay
+    assert_eq!(
+        round_trip("<2024-01-01 Mon>--<2024-01-02 Tue +1w>"),
+        "<2024-01-01 Mon +1w>--<2024-01-02 Tue>"
+    );
+    assert_eq!(
+        round_trip("<2024-01-01 Mon +1w>--<2024-01-02 Tue +2w>"),
+        "<2024-01-01 Mon +1w>--<2024-01-02 Tue>"
+    );
+    assert_eq!(
+        round_trip("<2024-01-01 Mon 10:00-11:00 +1w>"),
+        "<2024-01-01 Mon 10:00 +1w>--<2024-01-01 Mon 11:00>"
+    );
+
+    assert!(parse_active("<2024-01-01 Mon +1w +2w>").is_err());
+    assert!(parse_active("<2024-01-01 Mon -1d -2d>").is_err());
+    assert!(parse_active("<2024-01-01 Mon +1x>").is_err());
 }
@@ -17,8 +17,9 @@ use nom::{
 
 use crate::{
     config::ParseConfig,
-    elements::{drawer::parse_drawer_without_blank, Planning, Timestamp},
+    elements::{drawer::parse_drawer_without_blank, Link, Planning, Timestamp},
     parse::combinators::{blank_lines_count, line, one_word},
+    validate::TitleError,
 };
 
 /// Title Element
@@ -79,6 +80,16 @@ impl Title<'_> {
         self.planning.as_ref().and_then(|p| p.deadline.as_ref())
     }
 
+    /// Returns `true` if this headline has a planning line
+    pub fn has_planning(&self) -> bool {
+        self.planning.is_some()
+    }
+
+    /// Returns `true` if this headline has any properties
+    pub fn has_properties(&self) -> bool {
+        !self.properties.is_empty()
+    }
+
     /// Returns `true` if this headline is archived
     pub fn is_archived(&self) -> bool {
         self.tags.iter().any(|tag| tag == "ARCHIVE")
@@ -90,6 +101,235 @@ impl Title<'_> {
             && (self.raw.len() == 7 || self.raw[7..].starts_with(char::is_whitespace))
     }
 
+    /// Returns the url this headline bookmarks, by convention.
+    ///
+    /// Looks at the `URL` property first, then the first url in the
+    /// `ROAM_REFS` property, then the first link to a url in the title
+    /// text. Use [`Headline::bookmark_url`] to also look in the section.
+    ///
+    /// [`Headline::bookmark_url`]: crate::Headline::bookmark_url
+    ///
+    /// ```rust
+    /// use orgize::elements::Title;
+    ///
+    /// let mut title = Title {
+    ///     raw: "Read [[https://example.com/post][this]]".into(),
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(title.as_bookmark_url().unwrap(), "https://example.com/post");
+    ///
+    /// title
+    ///     .properties
+    ///     .pairs
+    ///     .push(("ROAM_REFS".into(), "@key https://example.com".into()));
+    /// assert_eq!(title.as_bookmark_url().unwrap(), "https://example.com");
+    ///
+    /// title.properties.pairs.push(("URL".into(), "https://example.org".into()));
+    /// assert_eq!(title.as_bookmark_url().unwrap(), "https://example.org");
+    /// ```
+    pub fn as_bookmark_url(&self) -> Option<Cow<'_, str>> {
+        let property = |key: &str| {
+            self.properties
+                .iter()
+                .find(|(k, _)| k.eq_ignore_ascii_case(key))
+                .map(|(_, value)| value.trim())
+        };
+
+        if let Some(url) = property("URL").filter(|url| !url.is_empty()) {
+            return Some(Cow::Borrowed(url));
+        }
+
+        // refs are separated by spaces, urls may be wrapped in brackets
+        let roam_ref = property("ROAM_REFS").and_then(|refs| {
+            refs.split_whitespace()
+                .map(|r| r.trim_start_matches('[').trim_end_matches(']'))
+                .find(|r| r.contains("://"))
+        });
+        if let Some(url) = roam_ref {
+            return Some(Cow::Borrowed(url));
+        }
+
+        let mut text = &*self.raw;
+        while let Some(i) = text.find("[[") {
+            match Link::parse(&text[i..]) {
+                Some((_, link)) if link.path.contains("://") => return Some(link.path),
+                _ => text = &text[i + 2..],
+            }
+        }
+
+        None
+    }
+
+    /// Returns `true` if this title could have been produced by parsing
+    /// with `config`.
+    ///
+    /// Level must be at least 1, and [`validate`] must find no errors.
+    ///
+    /// [`validate`]: #method.validate
+    ///
+    /// ```rust
+    /// use orgize::{elements::Title, ParseConfig};
+    ///
+    /// let config = ParseConfig::default();
+    /// let mut title = Title {
+    ///     level: 1,
+    ///     keyword: Some("TODO".into()),
+    ///     priority: Some('A'),
+    ///     tags: vec!["work".into()],
+    ///     ..Default::default()
+    /// };
+    /// assert!(title.is_valid(&config));
+    ///
+    /// title.keyword = Some("NEXT".into());
+    /// assert!(!title.is_valid(&config));
+    ///
+    /// title.keyword = None;
+    /// title.level = 0;
+    /// assert!(!title.is_valid(&config));
+    /// ```
+    pub fn is_valid(&self, config: &ParseConfig) -> bool {
+        self.level >= 1 && self.validate(config).is_empty()
+    }
+
+    /// Validates this title against `config`, and returns all errors found.
+    ///
+    /// Todo keyword must be one of `config`'s keywords, priority must be an
+    /// uppercase letter, tags must be non-empty and contain only valid
+    /// characters, and property keys must be non-empty and unique. Property
+    /// keys are compared case-insensitively.
+    ///
+    /// ```rust
+    /// use orgize::{elements::Title, ParseConfig, TitleError};
+    ///
+    /// let title = Title {
+    ///     level: 1,
+    ///     keyword: Some("NEXT".into()),
+    ///     priority: Some('1'),
+    ///     tags: vec!["ok".into(), "no spaces".into()],
+    ///     properties: vec![("ID".into(), "1".into()), ("id".into(), "2".into())]
+    ///         .into_iter()
+    ///         .collect(),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// assert_eq!(
+    ///     title.validate(&ParseConfig::default()),
+    ///     vec![
+    ///         TitleError::InvalidKeyword("NEXT".into()),
+    ///         TitleError::InvalidPriority('1'),
+    ///         TitleError::InvalidTag("no spaces".into()),
+    ///         TitleError::DuplicatePropertyKey("id".into()),
+    ///     ]
+    /// );
+    /// ```
+    pub fn validate(&self, config: &ParseConfig) -> Vec<TitleError> {
+        let mut errors = Vec::new();
+
+        if let Some(keyword) = &self.keyword {
+            if !config.todo_keywords.0.iter().any(|k| k == keyword)
+                && !config.todo_keywords.1.iter().any(|k| k == keyword)
+            {
+                errors.push(TitleError::InvalidKeyword(keyword.to_string()));
+            }
+        }
+        if let Some(priority) = self.priority {
+            if !priority.is_ascii_uppercase() {
+                errors.push(TitleError::InvalidPriority(priority));
+            }
+        }
+        for tag in &self.tags {
+            if !is_valid_tag(tag) {
+                errors.push(TitleError::InvalidTag(tag.to_string()));
+            }
+        }
+        for (index, (key, _)) in self.properties.iter().enumerate() {
+            if key.is_empty() {
+                errors.push(TitleError::EmptyPropertyKey);
+            } else if self
+                .properties
+                .iter()
+                .take(index)
+                .any(|(k, _)| k.eq_ignore_ascii_case(key))
+            {
+                errors.push(TitleError::DuplicatePropertyKey(key.to_string()));
+            }
+        }
+
+        errors
+    }
+
+    /// Returns an Emacs Lisp expression, which updates the headline at point
+    /// to match this title when evaluated in an org-mode buffer.
+    ///
+    /// Keyword, priority, title text, tags, planning and properties are
+    /// updated, while level is left unchanged.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse(
+    ///     "* TODO [#A] Say \"hi\" :work:\nDEADLINE: <2019-04-08 Mon>\n:PROPERTIES:\n:ID: 1\n:END:\n",
+    /// );
+    /// let title = org.headlines().next().unwrap().title(&org);
+    ///
+    /// assert_eq!(
+    ///     title.to_emacs_lisp(),
+    ///     r#"(progn
+    ///   (org-todo "TODO")
+    ///   (org-priority ?A)
+    ///   (org-edit-headline "Say \"hi\"")
+    ///   (org-set-tags '("work"))
+    ///   (org-schedule '(4))
+    ///   (org-deadline nil "<2019-04-08 Mon>")
+    ///   (org-entry-put nil "ID" "1"))"#
+    /// );
+    /// ```
+    pub fn to_emacs_lisp(&self) -> String {
+        let mut forms = Vec::new();
+
+        forms.push(match &self.keyword {
+            Some(keyword) => format!("(org-todo {})", lisp_string(keyword)),
+            None => String::from("(org-todo 'none)"),
+        });
+        forms.push(match self.priority {
+            Some(priority) => format!("(org-priority ?{})", priority),
+            None => String::from("(org-priority 'remove)"),
+        });
+        forms.push(format!("(org-edit-headline {})", lisp_string(&self.raw)));
+        forms.push(if self.tags.is_empty() {
+            String::from("(org-set-tags nil)")
+        } else {
+            let tags: Vec<_> = self.tags.iter().map(|tag| lisp_string(tag)).collect();
+            format!("(org-set-tags '({}))", tags.join(" "))
+        });
+
+        // a prefix argument removes the timestamp
+        forms.push(match self.scheduled() {
+            Some(scheduled) => format!("(org-schedule nil {})", lisp_timestamp(scheduled)),
+            None => String::from("(org-schedule '(4))"),
+        });
+        forms.push(match self.deadline() {
+            Some(deadline) => format!("(org-deadline nil {})", lisp_timestamp(deadline)),
+            None => String::from("(org-deadline '(4))"),
+        });
+        if let Some(closed) = self.closed() {
+            forms.push(format!(
+                "(org-add-planning-info 'closed (org-time-string-to-time {}))",
+                lisp_timestamp(closed)
+            ));
+        }
+
+        for (key, value) in self.properties.iter() {
+            forms.push(format!(
+                "(org-entry-put nil {} {})",
+                lisp_string(key),
+                lisp_string(value)
+            ));
+        }
+
+        format!("(progn\n  {})", forms.join("\n  "))
+    }
+
     pub fn into_owned(self) -> Title<'static> {
         Title {
             level: self.level,
@@ -161,6 +401,49 @@ impl<'a> PropertiesMap<'a> {
         self.pairs.into_iter().collect()
     }
 
+    /// Serialises this map as a `:PROPERTIES:` drawer, with keys right-padded
+    /// so that values line up.
+    ///
+    /// Returns an empty string if there are no properties. `Org::write_org`
+    /// doesn't align keys, and writes properties as they were parsed.
+    ///
+    /// ```rust
+    /// use orgize::elements::PropertiesMap;
+    ///
+    /// let properties: PropertiesMap = vec![("ID".into(), "1".into()), ("CUSTOM_ID".into(), "intro".into())]
+    ///     .into_iter()
+    ///     .collect();
+    ///
+    /// assert_eq!(
+    ///     properties.to_org(),
+    ///     ":PROPERTIES:\n:ID:        1\n:CUSTOM_ID: intro\n:END:\n"
+    /// );
+    /// ```
+    pub fn to_org(&self) -> String {
+        if self.is_empty() {
+            return String::new();
+        }
+
+        let width = self
+            .pairs
+            .iter()
+            .map(|(key, _)| key.chars().count())
+            .max()
+            .unwrap_or(0);
+        let mut output = String::from(":PROPERTIES:\n");
+        for (key, value) in &self.pairs {
+            let key = format!(":{}:", key);
+            if value.is_empty() {
+                output.push_str(&key);
+            } else {
+                output.push_str(&format!("{:width$} {}", key, value, width = width + 2));
+            }
+            output.push('\n');
+        }
+        output.push_str(":END:\n");
+        output
+    }
+
     pub fn into_owned(self) -> PropertiesMap<'static> {
         self.pairs
             .into_iter()
@@ -177,6 +460,15 @@ impl<'a> FromIterator<(Cow<'a, str>, Cow<'a, str>)> for PropertiesMap<'a> {
     }
 }
 
+// escapes backslashes and double quotes in an Emacs Lisp string
+fn lisp_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn lisp_timestamp(timestamp: &Timestamp) -> String {
+    lisp_string(&timestamp.to_org_string())
+}
+
 fn white_spaces_or_eol(input: &str) -> IResult<&str, &str, ()> {
     alt((space1, line_ending))(input)
 }
@@ -247,7 +539,14 @@ fn parse_title<'a>(
     ))
 }
 
-fn is_tag_line(input: &str) -> bool {
+fn is_valid_tag(tag: &str) -> bool {
+    !tag.is_empty()
+        && tag
+            .chars()
+            .all(|ch| ch.is_alphanumeric() || ch == '_' || ch == '@' || ch == '#' || ch == '%')
+}
+
+pub(crate) fn is_tag_line(input: &str) -> bool {
     input.len() > 2
         && input.starts_with(':')
         && input.ends_with(':')
@@ -257,7 +556,7 @@ fn is_tag_line(input: &str) -> bool {
 }
 
 #[inline]
-fn parse_properties_drawer(input: &str) -> IResult<&str, PropertiesMap<'_>, ()> {
+pub(crate) fn parse_properties_drawer(input: &str) -> IResult<&str, PropertiesMap<'_>, ()> {
     let (input, (drawer, content)) = parse_drawer_without_blank(input.trim_start())?;
     if drawer.name != "PROPERTIES" {
         return Err(Err::Error(make_error(input, ErrorKind::Tag)));
@@ -496,6 +795,55 @@ fn parse_title_() {
     );
 }
 
+#[test]
+fn parse_title_crlf() {
+    use crate::config::DEFAULT_CONFIG;
+
+    assert_eq!(
+        parse_title(
+            "** TODO [#B] Title :tag:\r\nSCHEDULED: <2019-04-08 Mon>\r\n\
+             :PROPERTIES:\r\n:ID: 1\r\n:END:\r\n\r\ntext\r\n",
+            &DEFAULT_CONFIG
+        ),
+        Ok((
+            "text\r\n",
+            (
+                Title {
+                    level: 2,
+                    keyword: Some("TODO".into()),
+                    priority: Some('B'),
+                    raw: "Title".into(),
+                    tags: vec!["tag".into()],
+                    planning: Planning::parse("SCHEDULED: <2019-04-08 Mon>\n")
+                        .map(|(_, planning)| Box::new(planning)),
+                    properties: vec![("ID".into(), "1".into())].into_iter().collect(),
+                    post_blank: 1,
+                },
+                "Title"
+            )
+        ))
+    );
+    assert_eq!(
+        parse_title("* DONE\r\n** [#A]\r\n", &DEFAULT_CONFIG),
+        Ok((
+            "** [#A]\r\n",
+            (
+                Title {
+                    level: 1,
+                    keyword: Some("DONE".into()),
+                    priority: None,
+                    raw: "".into(),
+                    tags: vec![],
+                    planning: None,
+                    properties: PropertiesMap::new(),
+                    post_blank: 0,
+                },
+                ""
+            )
+        ))
+    );
+}
+
 #[test]
 fn parse_properties_drawer_() {
     assert_eq!(
@@ -549,3 +897,82 @@ fn preserve_properties_drawer_order() {
         assert_eq!(left, &right);
     }
 }
+
+#[test]
+fn properties_to_org() {
+    let properties: PropertiesMap = vec![
+        ("ÉTAT".into(), "ouvert".into()),
+        ("ID".into(), "1".into()),
+        ("EMPTY".into(), "".into()),
+    ]
+    .into_iter()
+    .collect();
+
+    assert_eq!(
+        properties.to_org(),
+        ":PROPERTIES:\n:ÉTAT:  ouvert\n:ID:    1\n:EMPTY:\n:END:\n"
+    );
+    assert_eq!(PropertiesMap::new().to_org(), "");
+}
+
+#[test]
+fn is_valid() {
+    use crate::Org;
+
+    let config = ParseConfig {
+        todo_keywords: (vec!["À_FAIRE".into()], vec!["FINI".into()]),
+        ..Default::default()
+    };
+    // titles produced by the parser are valid
+    let org = Org::parse_custom("* À_FAIRE [#B] été :日本:\n** FINI b\n** c\n", &config);
+    for headline in org.headlines() {
+        assert!(headline.title(&org).is_valid(&config));
+    }
+
+    let title = org.headlines().next().unwrap().title(&org);
+    assert!(!title.is_valid(&ParseConfig::default()));
+
+    let title = Title::default();
+    assert!(title.is_valid(&config));
+    assert!(!Title {
+        level: 0,
+        ..title.clone()
+    }
+    .is_valid(&config));
+    assert!(!Title {
+        tags: vec!["".into()],
+        ..title.clone()
+    }
+    .is_valid(&config));
+    assert!(!Title {
+        properties: vec![("".into(), "value".into())].into_iter().collect(),
+        ..title
+    }
+    .is_valid(&config));
+}
+
+#[test]
+fn as_bookmark_url() {
+    assert_eq!(Title::default().as_bookmark_url(), None);
+
+    let title = Title {
+        raw: "see [[file:a.org]] [[https://例え.jp/é][日本]]".into(),
+        properties: vec![
+            ("url".into(), "  ".into()),
+            ("ROAM_REFS".into(), "@key cite:x".into()),
+        ]
+        .into_iter()
+        .collect(),
+        ..Default::default()
+    };
+    // empty urls and refs without urls fall back to links in the title
+    assert_eq!(title.as_bookmark_url().unwrap(), "https://例え.jp/é");
+
+    let title = Title {
+        properties: vec![("roam_refs".into(), "[[https://example.com]]".into())]
+            .into_iter()
+            .collect(),
+        ..Default::default()
+    };
+    assert_eq!(title.as_bookmark_url().unwrap(), "https://example.com");
+}
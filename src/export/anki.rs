@@ -0,0 +1,152 @@
+use crate::export::{join_lines, write_plain_text};
+use crate::{Headline, Org};
+
+/// Structure of flashcards in [`Org::to_anki_cards`]
+///
+/// [`Org::to_anki_cards`]: ../struct.Org.html#method.to_anki_cards
+#[derive(Debug, Clone)]
+pub struct AnkiConfig {
+    /// Level of headlines naming decks, default is `1`
+    pub deck_level: usize,
+    /// Level of headlines which become cards, default is `2`
+    pub card_level: usize,
+    /// Deck of cards without a deck headline, default is `"Default"`
+    pub default_deck: String,
+    /// Note type used in AnkiConnect requests, default is `"Basic"`
+    pub model: String,
+}
+
+impl Default for AnkiConfig {
+    fn default() -> Self {
+        AnkiConfig {
+            deck_level: 1,
+            card_level: 2,
+            default_deck: String::from("Default"),
+            model: String::from("Basic"),
+        }
+    }
+}
+
+/// A flashcard
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "ser", derive(serde::Serialize))]
+pub struct AnkiCard {
+    /// Deck name
+    pub deck: String,
+    /// Front side, in plain text
+    pub front: String,
+    /// Back side, in html
+    pub back: String,
+    /// Tags of the card headline and its ancestors
+    pub tags: Vec<String>,
+}
+
+impl Org<'_> {
+    /// Returns flashcards of this document.
+    ///
+    /// Each headline at `config.card_level` becomes a card: its title is
+    /// the front side, and its section and child headlines, rendered in
+    /// html, are the back side. The card belongs to the deck named by its
+    /// ancestor headline at `config.deck_level`.
+    ///
+    /// ```rust
+    /// use orgize::export::{AnkiCard, AnkiConfig};
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse(
+    ///     "* Rust :lang:\n** What does /~&T~/ mean? :refs:\nA shared reference.\n",
+    /// );
+    ///
+    /// assert_eq!(
+    ///     org.to_anki_cards(&AnkiConfig::default()),
+    ///     vec![AnkiCard {
+    ///         deck: String::from("Rust"),
+    ///         front: String::from("What does &T mean?"),
+    ///         back: String::from("<section><p>A shared reference.</p></section>"),
+    ///         tags: vec![String::from("refs"), String::from("lang")],
+    ///     }]
+    /// );
+    /// ```
+    pub fn to_anki_cards(&self, config: &AnkiConfig) -> Vec<AnkiCard> {
+        self.headlines()
+            .filter(|headline| headline.level() == config.card_level)
+            .map(|headline| {
+                let mut deck = None;
+                let mut tags = Vec::new();
+                let mut current = Some(headline);
+                while let Some(headline) = current {
+                    if headline.level() == config.deck_level && headline.level() < config.card_level
+                    {
+                        deck = Some(plain_title(headline, self));
+                    }
+                    for tag in &headline.title(self).tags {
+                        if !tags.iter().any(|t| t == tag) {
+                            tags.push(tag.to_string());
+                        }
+                    }
+                    current = headline.parent(self);
+                }
+
+                let back = headline
+                    .headline_node()
+                    .children(&self.arena)
+                    .filter(|node| *node != headline.title_node())
+                    .map(|node| self.node_to_html(node))
+                    .collect();
+
+                AnkiCard {
+                    deck: deck.unwrap_or_else(|| config.default_deck.clone()),
+                    front: plain_title(headline, self),
+                    back,
+                    tags,
+                }
+            })
+            .collect()
+    }
+
+    /// Returns an AnkiConnect `addNotes` request, which adds the flashcards
+    /// of this document.
+    ///
+    /// See [`Org::to_anki_cards`] for details.
+    ///
+    /// [`Org::to_anki_cards`]: #method.to_anki_cards
+    ///
+    /// ```rust
+    /// use orgize::export::AnkiConfig;
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse("* Rust\n** Ownership\nEach value has an owner.\n");
+    /// let request = org.to_anki_connect_json(&AnkiConfig::default());
+    ///
+    /// assert_eq!(request["action"], "addNotes");
+    /// assert_eq!(request["params"]["notes"][0]["deckName"], "Rust");
+    /// assert_eq!(request["params"]["notes"][0]["fields"]["Front"], "Ownership");
+    /// ```
+    #[cfg(feature = "serde_json")]
+    pub fn to_anki_connect_json(&self, config: &AnkiConfig) -> serde_json::Value {
+        let notes: Vec<_> = self
+            .to_anki_cards(config)
+            .into_iter()
+            .map(|card| {
+                serde_json::json!({
+                    "deckName": card.deck,
+                    "modelName": config.model,
+                    "fields": { "Front": card.front, "Back": card.back },
+                    "tags": card.tags,
+                })
+            })
+            .collect();
+
+        serde_json::json!({
+            "action": "addNotes",
+            "version": 6,
+            "params": { "notes": notes },
+        })
+    }
+}
+
+fn plain_title(headline: Headline, org: &Org) -> String {
+    let mut text = String::new();
+    write_plain_text(org, headline.title_node(), &mut text);
+    join_lines(&text)
+}
@@ -0,0 +1,462 @@
+use indextree::NodeId;
+use unicode_width::UnicodeWidthChar;
+
+use crate::elements::{Element, Table, TableRow};
+use crate::export::{column_widths, fixed_width_contents, write_timestamp};
+use crate::Org;
+
+/// Colors supported by the terminal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorSupport {
+    /// 24-bit colors
+    Truecolor,
+    /// 256 colors
+    Ansi256,
+    /// 16 colors
+    Ansi16,
+    /// No escape codes at all, for example when output is piped
+    None,
+}
+
+/// Terminal display configuration
+#[derive(Clone, Debug)]
+pub struct TerminalConfig {
+    /// Colors supported by the terminal, default is `ColorSupport::Ansi16`
+    pub color_support: ColorSupport,
+    /// Headline's todo keywords, default is `TODO` and `DONE`
+    ///
+    /// The first todo keyword is displayed in red, other todo keywords in
+    /// yellow and done keywords in green.
+    pub todo_keywords: (Vec<String>, Vec<String>),
+}
+
+impl Default for TerminalConfig {
+    fn default() -> Self {
+        TerminalConfig {
+            color_support: ColorSupport::Ansi16,
+            todo_keywords: (vec![String::from("TODO")], vec![String::from("DONE")]),
+        }
+    }
+}
+
+// a color in each of the supported modes, as (rgb, 256 colors index, 16 colors code)
+type Color = ((u8, u8, u8), u8, u8);
+
+const RED: Color = ((224, 108, 117), 204, 31);
+const GREEN: Color = ((152, 195, 121), 114, 32);
+const YELLOW: Color = ((229, 192, 123), 180, 33);
+const BLUE: Color = ((97, 175, 239), 75, 34);
+const MAGENTA: Color = ((198, 120, 221), 176, 35);
+const CYAN: Color = ((86, 182, 194), 73, 36);
+const GREY: Color = ((48, 48, 48), 236, 90);
+
+const LEVEL_COLORS: [Color; 6] = [BLUE, MAGENTA, CYAN, GREEN, YELLOW, RED];
+
+impl Org<'_> {
+    /// Renders `Org` struct with ANSI escape codes for terminal display.
+    ///
+    /// Headlines are bold and colored by their level, todo keywords are
+    /// colored by their state, tables are drawn with box-drawing characters
+    /// and code blocks have a grey background.
+    ///
+    /// ```rust
+    /// use orgize::export::{ColorSupport, TerminalConfig};
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse("* TODO Title\n| a | b |\n");
+    ///
+    /// let config = TerminalConfig::default();
+    /// assert_eq!(
+    ///     org.to_ansi_terminal(&config),
+    ///     "\x1b[1;34m* \x1b[1;31mTODO\x1b[0m\x1b[1;34m Title\x1b[0m\n\
+    ///      ┌───┬───┐\n│ a │ b │\n└───┴───┘"
+    /// );
+    ///
+    /// let config = TerminalConfig {
+    ///     color_support: ColorSupport::None,
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(
+    ///     org.to_ansi_terminal(&config),
+    ///     "* TODO Title\n┌───┬───┐\n│ a │ b │\n└───┴───┘"
+    /// );
+    /// ```
+    pub fn to_ansi_terminal(&self, config: &TerminalConfig) -> String {
+        let mut terminal = Terminal {
+            org: self,
+            config,
+            output: String::new(),
+            styles: Vec::new(),
+        };
+        terminal.write_children(self.root);
+        let output = &mut terminal.output;
+        output.truncate(output.trim_end().len());
+        terminal.output
+    }
+}
+
+struct Terminal<'a, 'b> {
+    org: &'a Org<'b>,
+    config: &'a TerminalConfig,
+    output: String,
+    // escape codes of currently applied styles
+    styles: Vec<String>,
+}
+
+impl Terminal<'_, '_> {
+    // returns an escape code setting `attributes` and foreground `color`
+    fn style(&self, attributes: &str, color: Option<Color>, background: bool) -> String {
+        let color = match (color, self.config.color_support) {
+            (_, ColorSupport::None) => return String::new(),
+            (None, _) => String::new(),
+            (Some(((r, g, b), _, _)), ColorSupport::Truecolor) => {
+                format!("{};2;{};{};{}", if background { 48 } else { 38 }, r, g, b)
+            }
+            (Some((_, index, _)), ColorSupport::Ansi256) => {
+                format!("{};5;{}", if background { 48 } else { 38 }, index)
+            }
+            (Some((_, _, code)), ColorSupport::Ansi16) => {
+                // background codes are 10 greater than foreground codes
+                let code = if background { code + 10 } else { code };
+                code.to_string()
+            }
+        };
+        match (attributes.is_empty(), color.is_empty()) {
+            (true, true) => String::new(),
+            (false, true) => format!("\x1b[{}m", attributes),
+            (true, false) => format!("\x1b[{}m", color),
+            (false, false) => format!("\x1b[{};{}m", attributes, color),
+        }
+    }
+
+    fn push_style(&mut self, style: String) {
+        self.output.push_str(&style);
+        self.styles.push(style);
+    }
+
+    fn pop_style(&mut self) {
+        if let Some(style) = self.styles.pop() {
+            if !style.is_empty() {
+                // resets all styles, then re-applies the remaining ones
+                self.output.push_str("\x1b[0m");
+                for style in &self.styles {
+                    self.output.push_str(style);
+                }
+            }
+        }
+    }
+
+    fn write_styled(&mut self, style: String, text: &str) {
+        self.push_style(style);
+        self.output.push_str(text);
+        self.pop_style();
+    }
+
+    fn write_children(&mut self, node: NodeId) {
+        for child in node.children(&self.org.arena) {
+            self.write_node(child);
+        }
+    }
+
+    // writes the descendants of `node` into a separate string
+    fn render_children(&mut self, node: NodeId) -> String {
+        let output = std::mem::take(&mut self.output);
+        self.write_children(node);
+        std::mem::replace(&mut self.output, output)
+    }
+
+    fn write_node(&mut self, node: NodeId) {
+        let org = self.org;
+        match &org[node] {
+            Element::Document { .. }
+            | Element::Section
+            | Element::Headline { .. }
+            | Element::DynBlock(_)
+            | Element::CenterBlock(_)
+            | Element::SpecialBlock(_)
+            | Element::ListItem(_)
+            | Element::RadioTarget => self.write_children(node),
+            Element::Title(title) => {
+                let style = self.style("1", Some(LEVEL_COLORS[(title.level - 1) % 6]), false);
+                self.push_style(style);
+                self.output.push_str(&"*".repeat(title.level));
+                self.output.push(' ');
+                if let Some(keyword) = &title.keyword {
+                    let color = if self.config.todo_keywords.1.iter().any(|k| k == keyword) {
+                        GREEN
+                    } else if matches!(self.config.todo_keywords.0.first(), Some(k) if k == keyword)
+                    {
+                        RED
+                    } else {
+                        YELLOW
+                    };
+                    let style = self.style("1", Some(color), false);
+                    self.write_styled(style, keyword);
+                    self.output.push(' ');
+                }
+                if let Some(priority) = title.priority {
+                    self.output.push_str(&format!("[#{}] ", priority));
+                }
+                self.write_children(node);
+                self.pop_style();
+                if !title.tags.is_empty() {
+                    let tags = format!(" :{}:", title.tags.join(":"));
+                    let style = self.style("2", None, false);
+                    self.write_styled(style, &tags);
+                }
+                self.output.push('\n');
+            }
+            Element::Paragraph { .. } => {
+                self.write_children(node);
+                self.output.push_str("\n\n");
+            }
+            Element::QuoteBlock(_) | Element::VerseBlock(_) => {
+                let contents = self.render_children(node);
+                let style = self.style("", Some(GREY), false);
+                for line in contents.trim().lines() {
+                    self.write_styled(style.clone(), "│ ");
+                    self.output.push_str(line);
+                    self.output.push('\n');
+                }
+                self.output.push('\n');
+            }
+            Element::List(list) => {
+                let items: Vec<_> = node.children(&org.arena).collect();
+                for (index, item) in items.into_iter().enumerate() {
+                    let bullet = if list.ordered {
+                        format!("{}. ", index + 1)
+                    } else {
+                        String::from("• ")
+                    };
+                    let contents = self.render_children(item);
+                    self.output.push_str(&bullet);
+                    for (i, line) in contents.lines().filter(|l| !l.is_empty()).enumerate() {
+                        if i > 0 {
+                            for _ in 0..bullet.chars().count() {
+                                self.output.push(' ');
+                            }
+                        }
+                        self.output.push_str(line);
+                        self.output.push('\n');
+                    }
+                    if contents.trim().is_empty() {
+                        self.output.push('\n');
+                    }
+                }
+                self.output.push('\n');
+            }
+            Element::Bold => self.write_emphasis(node, "1"),
+            Element::Italic => self.write_emphasis(node, "3"),
+            Element::Underline => self.write_emphasis(node, "4"),
+            Element::Strike => self.write_emphasis(node, "9"),
+            Element::Code { value } | Element::Verbatim { value } => {
+                let style = self.style("", Some(GREY), true);
+                self.write_styled(style, value);
+            }
+            Element::InlineSrc(inline_src) => {
+                let style = self.style("", Some(GREY), true);
+                self.write_styled(style, &inline_src.body);
+            }
+            Element::Text { value } => self.output.push_str(value),
+            Element::Link(link) => {
+                let style = self.style("4", Some(BLUE), false);
+                self.write_styled(style, link.desc.as_ref().unwrap_or(&link.path));
+            }
+            Element::Timestamp(timestamp) => {
+                let mut writer = Vec::new();
+                if write_timestamp(&mut writer, timestamp).is_ok() {
+                    let style = self.style("", Some(MAGENTA), false);
+                    self.write_styled(style, &String::from_utf8_lossy(&writer));
+                }
+            }
+            Element::Cookie(cookie) => self.output.push_str(&cookie.value),
+            Element::FnRef(fn_ref) => {
+                let style = self.style("", Some(CYAN), false);
+                self.write_styled(style, &format!("[{}]", fn_ref.label));
+            }
+            Element::FnDef(fn_def) => {
+                let style = self.style("", Some(CYAN), false);
+                self.write_styled(style, &format!("[{}] ", fn_def.label));
+                self.write_children(node);
+            }
+            Element::SourceBlock(block) => self.write_code_block(&block.contents),
+            Element::ExampleBlock(block) => self.write_code_block(&block.contents),
+            Element::FixedWidth(fixed_width) => {
+                self.write_code_block(&fixed_width_contents(&fixed_width.value))
+            }
+            Element::Table(Table::Org { .. }) => self.write_table(node),
+            Element::Table(Table::TableEl { value, .. }) => self.write_code_block(value),
+            Element::Rule(_) => {
+                let style = self.style("", Some(GREY), false);
+                self.write_styled(style, &"─".repeat(40));
+                self.output.push_str("\n\n");
+            }
+            Element::BabelCall(_)
+            | Element::Clock(_)
+            | Element::Comment(_)
+            | Element::CommentBlock(_)
+            | Element::Drawer(_)
+            | Element::ExportBlock(_)
+            | Element::InlineCall(_)
+            | Element::Keyword(_)
+            | Element::Macros(_)
+            | Element::Snippet(_)
+            | Element::Target(_)
+            | Element::TableRow(_)
+            | Element::TableCell(_) => (),
+        }
+    }
+
+    fn write_emphasis(&mut self, node: NodeId, attributes: &str) {
+        let style = self.style(attributes, None, false);
+        self.push_style(style);
+        self.write_children(node);
+        self.pop_style();
+    }
+
+    // lines are padded to the same width, so the background is rectangular
+    fn write_code_block(&mut self, contents: &str) {
+        let width = contents
+            .lines()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or_default();
+        let style = self.style("", Some(GREY), true);
+        for line in contents.lines() {
+            let padding = width - line.chars().count();
+            self.write_styled(style.clone(), &format!(" {}{} ", line, " ".repeat(padding)));
+            self.output.push('\n');
+        }
+        self.output.push('\n');
+    }
+
+    fn write_table(&mut self, node: NodeId) {
+        let org = self.org;
+        // `None` stands for a rule
+        let rows: Vec<Option<Vec<String>>> = node
+            .children(&org.arena)
+            .map(|row| match org[row] {
+                Element::TableRow(TableRow::Header) | Element::TableRow(TableRow::Body) => Some(
+                    row.children(&org.arena)
+                        .map(|cell| self.render_children(cell).trim().to_string())
+                        .collect(),
+                ),
+                _ => None,
+            })
+            .collect();
+
+        let widths = column_widths(&rows, visible_width);
+
+        let border = |left: &str, middle: &str, right: &str| {
+            let lines: Vec<_> = widths.iter().map(|w| "─".repeat(w + 2)).collect();
+            format!("{}{}{}\n", left, lines.join(middle), right)
+        };
+
+        self.output.push_str(&border("┌", "┬", "┐"));
+        for (index, row) in rows.iter().enumerate() {
+            match row {
+                // rules at the beginning or the end are replaced by borders
+                None if index == 0 || index == rows.len() - 1 => (),
+                None => self.output.push_str(&border("├", "┼", "┤")),
+                Some(row) => {
+                    self.output.push('│');
+                    for (index, width) in widths.iter().enumerate() {
+                        let cell = row.get(index).map(String::as_str).unwrap_or_default();
+                        self.output.push(' ');
+                        self.output.push_str(cell);
+                        for _ in visible_width(cell)..*width {
+                            self.output.push(' ');
+                        }
+                        self.output.push_str(" │");
+                    }
+                    self.output.push('\n');
+                }
+            }
+        }
+        self.output.push_str(&border("└", "┴", "┘"));
+        self.output.push('\n');
+    }
+}
+
+// returns the display width of `text`, skipping escape codes
+fn visible_width(text: &str) -> usize {
+    let mut width = 0;
+    let mut in_escape = false;
+    for c in text.chars() {
+        match c {
+            '\x1b' => in_escape = true,
+            'm' if in_escape => in_escape = false,
+            _ if in_escape => (),
+            _ => width += c.width().unwrap_or_default(),
+        }
+    }
+    width
+}
+
+#[test]
+fn convert() {
+    let org = Org::parse(
+        r#"* DONE Title :tag:
+Some *bold /italic/* and ~code~.
+
+- one
+- two
+
+#+BEGIN_SRC rust
+fn main() {}
+#+END_SRC
+
+| a | bb |
+|---+----|
+| 1 | 2  |
+"#,
+    );
+
+    let config = TerminalConfig {
+        color_support: ColorSupport::None,
+        ..Default::default()
+    };
+    assert_eq!(
+        org.to_ansi_terminal(&config),
+        "* DONE Title :tag:\n\
+         Some bold italic and code.\n\n\
+         • one\n\
+         • two\n\n \
+         fn main() {} \n\n\
+         ┌───┬────┐\n\
+         │ a │ bb │\n\
+         ├───┼────┤\n\
+         │ 1 │ 2  │\n\
+         └───┴────┘"
+    );
+
+    let config = TerminalConfig {
+        color_support: ColorSupport::Truecolor,
+        ..Default::default()
+    };
+    let output = org.to_ansi_terminal(&config);
+    assert!(output.contains("\x1b[1;38;2;152;195;121mDONE"));
+    assert!(output.contains("\x1b[1mbold \x1b[3mitalic\x1b[0m\x1b[1m\x1b[0m"));
+    assert!(output.contains("\x1b[48;2;48;48;48mcode\x1b[0m"));
+
+    let org = Org::parse("~code~\n-----\n");
+    let output = org.to_ansi_terminal(&TerminalConfig::default());
+    assert!(output.contains("\x1b[100mcode\x1b[0m"));
+    assert!(output.contains("\x1b[90m─"));
+}
+
+#[test]
+fn wide_table_cells() {
+    let org = Org::parse("| 漢字 | a |\n| b | c |\n");
+    let config = TerminalConfig {
+        color_support: ColorSupport::None,
+        ..Default::default()
+    };
+    assert_eq!(
+        org.to_ansi_terminal(&config),
+        "┌──────┬───┐\n\
+         │ 漢字 │ a │\n\
+         │ b    │ c │\n\
+         └──────┴───┘"
+    );
+}
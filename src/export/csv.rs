@@ -0,0 +1,186 @@
+use crate::elements::Timestamp;
+use crate::export::write_timestamp;
+use crate::Org;
+
+/// A column of [`Org::to_csv_outline`]
+///
+/// [`Org::to_csv_outline`]: ../struct.Org.html#method.to_csv_outline
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OutlineField<'a> {
+    /// Headline level
+    Level,
+    /// Headline title, without todo keyword, priority or tags
+    Title,
+    /// Headline todo keyword
+    Keyword,
+    /// Headline priority
+    Priority,
+    /// Headline tags, like `:work:urgent:`
+    Tags,
+    /// Deadline timestamp
+    Deadline,
+    /// Scheduled timestamp
+    Scheduled,
+    /// One column per property, named after the property
+    Properties(Vec<&'a str>),
+}
+
+impl Org<'_> {
+    /// Exports headlines of this document as CSV, one row per headline, in
+    /// the order they appear.
+    ///
+    /// The first row contains column headers. Unless the `Level` field is
+    /// included, titles are indented with two spaces per level below the
+    /// first, so the outline structure is kept. Fields containing commas,
+    /// quotes or newlines are quoted.
+    ///
+    /// ```rust
+    /// use orgize::export::OutlineField;
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse(
+    ///     "* TODO [#A] Plan :work:\nDEADLINE: <2019-04-10 Wed>\n\
+    ///      :PROPERTIES:\n:OWNER: Alice, Bob\n:END:\n** Steps\n",
+    /// );
+    ///
+    /// assert_eq!(
+    ///     org.to_csv_outline(&[
+    ///         OutlineField::Title,
+    ///         OutlineField::Keyword,
+    ///         OutlineField::Priority,
+    ///         OutlineField::Tags,
+    ///         OutlineField::Deadline,
+    ///         OutlineField::Properties(vec!["OWNER"]),
+    ///     ]),
+    ///     "Title,Keyword,Priority,Tags,Deadline,OWNER\n\
+    ///      Plan,TODO,A,:work:,<2019-04-10 Wed>,\"Alice, Bob\"\n  \
+    ///      Steps,,,,,\n"
+    /// );
+    ///
+    /// assert_eq!(
+    ///     org.to_csv_outline(&[OutlineField::Level, OutlineField::Title]),
+    ///     "Level,Title\n1,Plan\n2,Steps\n"
+    /// );
+    /// ```
+    pub fn to_csv_outline(&self, fields: &[OutlineField]) -> String {
+        let mut output = String::new();
+        for row in outline_rows(self, fields) {
+            let row: Vec<_> = row.iter().map(|field| quote(field)).collect();
+            output.push_str(&row.join(","));
+            output.push('\n');
+        }
+        output
+    }
+}
+
+// headers, followed by one row per headline
+pub(crate) fn outline_rows(org: &Org, fields: &[OutlineField]) -> Vec<Vec<String>> {
+    let indent = !fields.contains(&OutlineField::Level);
+
+    let mut rows = Vec::new();
+
+    let mut headers = Vec::new();
+    for field in fields {
+        match field {
+            OutlineField::Level => headers.push(String::from("Level")),
+            OutlineField::Title => headers.push(String::from("Title")),
+            OutlineField::Keyword => headers.push(String::from("Keyword")),
+            OutlineField::Priority => headers.push(String::from("Priority")),
+            OutlineField::Tags => headers.push(String::from("Tags")),
+            OutlineField::Deadline => headers.push(String::from("Deadline")),
+            OutlineField::Scheduled => headers.push(String::from("Scheduled")),
+            OutlineField::Properties(keys) => {
+                headers.extend(keys.iter().map(|key| key.to_string()))
+            }
+        }
+    }
+    rows.push(headers);
+
+    for headline in org.headlines() {
+        let title = headline.title(org);
+        let mut row = Vec::new();
+        for field in fields {
+            match field {
+                OutlineField::Level => row.push(headline.level().to_string()),
+                OutlineField::Title => {
+                    let mut text = String::new();
+                    if indent {
+                        text.push_str(&"  ".repeat(headline.level().saturating_sub(1)));
+                    }
+                    text.push_str(&title.raw.split_whitespace().collect::<Vec<_>>().join(" "));
+                    row.push(text);
+                }
+                OutlineField::Keyword => {
+                    row.push(title.keyword.as_deref().unwrap_or_default().to_string())
+                }
+                OutlineField::Priority => {
+                    row.push(title.priority.map(String::from).unwrap_or_default())
+                }
+                OutlineField::Tags if title.tags.is_empty() => row.push(String::new()),
+                OutlineField::Tags => row.push(format!(":{}:", title.tags.join(":"))),
+                OutlineField::Deadline => row.push(timestamp(title.deadline())),
+                OutlineField::Scheduled => row.push(timestamp(title.scheduled())),
+                OutlineField::Properties(keys) => {
+                    for key in keys {
+                        let value = title
+                            .properties
+                            .iter()
+                            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+                            .map(|(_, value)| value.to_string());
+                        row.push(value.unwrap_or_default());
+                    }
+                }
+            }
+        }
+        rows.push(row);
+    }
+
+    rows
+}
+
+fn timestamp(timestamp: Option<&Timestamp>) -> String {
+    let mut writer = Vec::new();
+    match timestamp {
+        Some(timestamp) if write_timestamp(&mut writer, timestamp).is_ok() => {
+            String::from_utf8_lossy(&writer).into_owned()
+        }
+        _ => String::new(),
+    }
+}
+
+// quotes a field if needed, following RFC 4180
+fn quote(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[test]
+fn to_csv_outline() {
+    assert_eq!(
+        Org::parse("").to_csv_outline(&[OutlineField::Title]),
+        "Title\n"
+    );
+    assert_eq!(Org::parse("* a\n").to_csv_outline(&[]), "\n\n");
+
+    let config = crate::ParseConfig {
+        todo_keywords: (vec!["À_FAIRE".into()], vec![]),
+        ..Default::default()
+    };
+    let org = Org::parse_custom(
+        "* À_FAIRE Say \"été\"\n:PROPERTIES:\n:a,b: 日本\n:END:\n*** deep\n",
+        &config,
+    );
+    assert_eq!(
+        org.to_csv_outline(&[
+            OutlineField::Title,
+            OutlineField::Keyword,
+            OutlineField::Properties(vec!["A,B", "missing"]),
+        ]),
+        "Title,Keyword,\"A,B\",missing\n\
+         \"Say \"\"été\"\"\",À_FAIRE,日本,\n    \
+         deep,,,\n"
+    );
+}
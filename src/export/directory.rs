@@ -0,0 +1,229 @@
+use std::fs;
+use std::io::Result as IOResult;
+use std::path::{Component, Path, PathBuf};
+
+use crate::elements::Element;
+use crate::export::{is_image, latex::to_latex, markdown::to_markdown};
+use crate::Org;
+
+/// Export Format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// Html, written by `Org::write_html`
+    Html,
+    /// LaTeX document
+    Latex,
+    /// Markdown
+    Markdown,
+    /// Org-mode, written by `Org::write_org`
+    Org,
+}
+
+impl ExportFormat {
+    /// Returns the file extension of this format.
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Html => "html",
+            ExportFormat::Latex => "tex",
+            ExportFormat::Markdown => "md",
+            ExportFormat::Org => "org",
+        }
+    }
+}
+
+/// Directory export configuration
+#[derive(Clone, Debug)]
+pub struct ExportConfig {
+    /// Formats to export, default is html only
+    pub formats: Vec<ExportFormat>,
+    /// Name of exported files, without the extension, default is `"index"`
+    pub file_stem: String,
+    /// Directory which relative image links are resolved against, default is `None`
+    ///
+    /// Linked images are copied into the output directory only if it's set.
+    pub base_dir: Option<PathBuf>,
+}
+
+/// Files created by `Org::export_to_directory`
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ExportedFiles {
+    /// Paths of exported files and copied images
+    pub files: Vec<PathBuf>,
+    /// Linked images which don't exist in `base_dir`, relative to it
+    pub missing_images: Vec<PathBuf>,
+}
+
+impl Default for ExportConfig {
+    fn default() -> Self {
+        ExportConfig {
+            formats: vec![ExportFormat::Html],
+            file_stem: String::from("index"),
+            base_dir: None,
+        }
+    }
+}
+
+impl Org<'_> {
+    /// Exports `Org` struct into `dir` in multiple formats.
+    ///
+    /// Creates `dir` if it doesn't exist, and returns the paths of created
+    /// files, including copied images. Remote images, and images linked by
+    /// absolute paths or paths containing `..` are not copied. Images which
+    /// don't exist are reported in `missing_images` instead of failing the
+    /// export.
+    ///
+    /// ```rust,no_run
+    /// use orgize::export::{ExportConfig, ExportFormat};
+    /// use orgize::Org;
+    /// use std::path::Path;
+    ///
+    /// let org = Org::parse("* Title\n[[./images/cat.png]]");
+    ///
+    /// let exported = org
+    ///     .export_to_directory(
+    ///         Path::new("public"),
+    ///         &ExportConfig {
+    ///             formats: vec![ExportFormat::Html, ExportFormat::Markdown],
+    ///             base_dir: Some("notes".into()),
+    ///             ..Default::default()
+    ///         },
+    ///     )
+    ///     .unwrap();
+    ///
+    /// // public/index.html, public/index.md and public/images/cat.png
+    /// assert_eq!(exported.files.len(), 3);
+    /// assert!(exported.missing_images.is_empty());
+    /// ```
+    pub fn export_to_directory(
+        &self,
+        dir: &Path,
+        config: &ExportConfig,
+    ) -> IOResult<ExportedFiles> {
+        fs::create_dir_all(dir)?;
+
+        let mut files = Vec::new();
+        let mut missing_images = Vec::new();
+
+        for format in &config.formats {
+            let path = dir.join(format!("{}.{}", config.file_stem, format.extension()));
+            let contents = match format {
+                ExportFormat::Html => {
+                    let mut writer = Vec::new();
+                    self.write_html(&mut writer)?;
+                    writer
+                }
+                ExportFormat::Org => {
+                    let mut writer = Vec::new();
+                    self.write_org(&mut writer)?;
+                    writer
+                }
+                ExportFormat::Markdown => {
+                    let mut markdown = to_markdown(self, self.root);
+                    markdown.push('\n');
+                    markdown.into_bytes()
+                }
+                ExportFormat::Latex => to_latex(self).into_bytes(),
+            };
+            fs::write(&path, contents)?;
+            files.push(path);
+        }
+
+        if let Some(base_dir) = &config.base_dir {
+            let images = self.arena.iter().filter_map(|node| match node.get() {
+                Element::Link(link) if is_image(&link.path) => {
+                    Some(link.path.trim_start_matches("file:"))
+                }
+                _ => None,
+            });
+
+            for image in images {
+                // skips remote images like `https://...`
+                if image.split('/').next().unwrap_or(image).contains(':') {
+                    continue;
+                }
+
+                let image = Path::new(image);
+                if !image
+                    .components()
+                    .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+                {
+                    continue;
+                }
+
+                // so `./images/a.png` and `images/a.png` are copied once
+                let image: PathBuf = image
+                    .components()
+                    .filter(|c| matches!(c, Component::Normal(_)))
+                    .collect();
+
+                let target = dir.join(&image);
+                if files.contains(&target) || missing_images.contains(&image) {
+                    continue;
+                }
+                let source = base_dir.join(&image);
+                if !source.is_file() {
+                    missing_images.push(image);
+                    continue;
+                }
+                if let Some(parent) = target.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::copy(source, &target)?;
+                files.push(target);
+            }
+        }
+
+        Ok(ExportedFiles {
+            files,
+            missing_images,
+        })
+    }
+}
+
+#[test]
+fn export_to_directory() {
+    let base_dir = crate::test_dir("export-to-directory");
+    let dir = base_dir.join("public");
+    fs::create_dir_all(base_dir.join("images")).unwrap();
+    fs::write(base_dir.join("images/cat.png"), b"cat").unwrap();
+
+    let org = Org::parse(
+        "* Title\n[[./images/cat.png]] [[/etc/dog.png]]\n\
+         [[images/cat.png]] [[https://example.com/cow.png]] [[file:fox.png]]\n",
+    );
+    let exported = org
+        .export_to_directory(
+            &dir,
+            &ExportConfig {
+                formats: vec![ExportFormat::Org, ExportFormat::Markdown],
+                file_stem: String::from("notes"),
+                base_dir: Some(base_dir.clone()),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(
+        exported,
+        ExportedFiles {
+            files: vec![
+                dir.join("notes.org"),
+                dir.join("notes.md"),
+                dir.join("images/cat.png")
+            ],
+            missing_images: vec![PathBuf::from("fox.png")],
+        }
+    );
+    assert_eq!(
+        fs::read_to_string(dir.join("notes.org")).unwrap(),
+        "* Title\n[[./images/cat.png]] [[/etc/dog.png]]\n\
+         [[images/cat.png]] [[https://example.com/cow.png]] [[file:fox.png]]\n"
+    );
+    assert_eq!(
+        fs::read_to_string(dir.join("notes.md")).unwrap(),
+        "# Title\n\n![](./images/cat.png) ![](/etc/dog.png)\n\
+         ![](images/cat.png) ![](https://example.com/cow.png) ![](fox.png)\n"
+    );
+    assert_eq!(fs::read(dir.join("images/cat.png")).unwrap(), b"cat");
+
+    fs::remove_dir_all(&base_dir).unwrap();
+}
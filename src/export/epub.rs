@@ -0,0 +1,50 @@
+use crate::export::write_plain_text;
+use crate::Org;
+
+impl Org<'_> {
+    /// Splits this document into e-book chapters, one per level-1 headline.
+    ///
+    /// Returns `(chapter_name, html_content)` pairs, where `chapter_name` is
+    /// the headline title without markup, and `html_content` is the headline
+    /// and its contents rendered by [`node_to_html`]. Contents before the first
+    /// headline are not included.
+    ///
+    /// Assembling the actual EPUB file is left to the caller: each fragment
+    /// should be wrapped in an XHTML document, and listed in the package's
+    /// manifest, spine and navigation document, which are then zipped
+    /// together with the `mimetype` and `META-INF/container.xml` files.
+    ///
+    /// [`node_to_html`]: Org::node_to_html
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse("Preface.\n* The /First/ Day\nSunny.\n** Morning\n* Night\n");
+    ///
+    /// assert_eq!(
+    ///     org.to_epub_fragments(),
+    ///     vec![
+    ///         (
+    ///             "The First Day".to_string(),
+    ///             "<h1>The <i>First</i> Day</h1><section><p>Sunny.</p></section>\
+    ///              <h2>Morning</h2>"
+    ///                 .to_string()
+    ///         ),
+    ///         ("Night".to_string(), "<h1>Night</h1>".to_string()),
+    ///     ]
+    /// );
+    /// ```
+    pub fn to_epub_fragments(&self) -> Vec<(String, String)> {
+        self.document()
+            .children(self)
+            .map(|headline| {
+                let mut name = String::new();
+                write_plain_text(self, headline.title_node(), &mut name);
+                (
+                    name.trim().to_string(),
+                    self.node_to_html(headline.headline_node()),
+                )
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,421 @@
+use crate::elements::{Element, Table};
+use crate::export::pretty::dedent_contents;
+use crate::export::{DefaultOrgHandler, OrgFormatter, OrgHandler};
+use crate::{Event, Org};
+
+/// Formatting differences between two documents
+#[derive(Debug, Default)]
+pub struct OrgFormatDiff {
+    /// Places where formatting differs
+    pub changes: Vec<FormatChange>,
+    /// `true` if documents also differ in other ways than formatting
+    pub structural: bool,
+}
+
+impl OrgFormatDiff {
+    /// Returns `true` if both documents are written identically.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty() && !self.structural
+    }
+}
+
+/// Formatting difference between two documents
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatChange {
+    /// Line number in the first document, starting from 1
+    pub line: usize,
+    /// Column number in the first document, starting from 1
+    pub column: usize,
+    /// Text in the first document
+    pub found: String,
+    /// Text in the second document
+    pub expected: String,
+}
+
+/// A place where a document deviates from the formatter's rules
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatViolation {
+    /// Line number, starting from 1
+    pub line: usize,
+    /// Column number, starting from 1
+    pub column: usize,
+    /// Description of the violation
+    pub message: String,
+}
+
+impl Org<'_> {
+    /// Compares this document with `other`, and returns their differences
+    /// in formatting, such as indentation or blank lines.
+    ///
+    /// Both documents are written back in org syntax, and positions refer
+    /// to the text written for this document. Changes are reported in order
+    /// until texts differ in something else than whitespace, since following
+    /// positions can't be matched anymore. The alignment of tags and tables,
+    /// and the indentation of properties are discarded when parsing, so they
+    /// are never reported.
+    ///
+    /// Documents differ structurally if their elements differ, ignoring
+    /// blank lines, the indentation of list items and drawers, and the
+    /// common indentation of block contents.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse("* Title\n- a\n   continued\n\n\ntext\n");
+    /// let other = Org::parse("* Title\n- a\n  continued\n\ntext\n");
+    ///
+    /// let diff = org.diff_format(&other);
+    /// assert!(!diff.structural);
+    /// assert_eq!(diff.changes.len(), 2);
+    /// assert_eq!((diff.changes[0].line, diff.changes[0].column), (2, 4));
+    /// assert_eq!(diff.changes[0].found, "\n   ");
+    /// assert_eq!(diff.changes[0].expected, "\n  ");
+    ///
+    /// assert!(org.diff_format(&org).is_empty());
+    /// assert!(org.diff_format(&Org::parse("* Title\n- b\n")).structural);
+    /// ```
+    pub fn diff_format(&self, other: &Org) -> OrgFormatDiff {
+        let formatter = OrgFormatter::new();
+        let (source, other_source) = (
+            formatter.format_parsed(self),
+            formatter.format_parsed(other),
+        );
+        let (found, expected) = (tokenize(&source), tokenize(&other_source));
+
+        let mut diff = OrgFormatDiff {
+            changes: Vec::new(),
+            structural: !same_structure(self, other),
+        };
+        for (found, expected) in found.iter().zip(expected.iter()) {
+            if found.whitespace != expected.whitespace {
+                diff.changes.push(FormatChange {
+                    line: found.line,
+                    column: found.column,
+                    found: found.whitespace.into(),
+                    expected: expected.whitespace.into(),
+                });
+            }
+            if found.word != expected.word {
+                diff.changes.push(FormatChange {
+                    line: found.word_line,
+                    column: found.word_column,
+                    found: found.word.into(),
+                    expected: expected.word.into(),
+                });
+                break;
+            }
+        }
+
+        diff
+    }
+
+    /// Checks if this document is formatted by `formatter`, and returns all
+    /// places where it deviates from the formatter's rules, without
+    /// modifying the document.
+    ///
+    /// Positions refer to the document written back in org syntax. The
+    /// alignment of tags and tables, and the indentation of properties are
+    /// discarded when parsing, so they are never reported.
+    ///
+    /// ```rust
+    /// use orgize::export::OrgFormatter;
+    /// use orgize::Org;
+    ///
+    /// let formatter = OrgFormatter::new().blank_lines_before_headlines(1);
+    ///
+    /// let org = Org::parse("* A\ntext\n* B\n:LOGBOOK:\n   note\n:END:\n");
+    /// let violations = org.check_format(&formatter);
+    /// assert_eq!(violations.len(), 2);
+    /// assert_eq!((violations[0].line, violations[0].column), (2, 5));
+    /// assert_eq!(violations[0].message, "expected 1 blank line, found 0");
+    /// assert_eq!((violations[1].line, violations[1].column), (5, 1));
+    /// assert_eq!(violations[1].message, "expected indentation of 2 spaces, found 3");
+    ///
+    /// let formatted = Org::parse("* A\n:PROPERTIES:\n:ID: 1\n:END:\n").to_pretty_org(2);
+    /// assert!(Org::parse(&formatted).check_format(&OrgFormatter::new()).is_empty());
+    /// ```
+    pub fn check_format(&self, formatter: &OrgFormatter) -> Vec<FormatViolation> {
+        let (source, expected) = (formatter.format_parsed(self), formatter.format(self));
+        let (found, expected) = (tokenize(&source), tokenize(&expected));
+
+        let mut violations = Vec::new();
+        for (found, expected) in found.iter().zip(expected.iter()) {
+            if found.whitespace != expected.whitespace {
+                violations.push(whitespace_violation(found, expected));
+            }
+            if found.word != expected.word {
+                violations.push(FormatViolation {
+                    line: found.word_line,
+                    column: found.word_column,
+                    message: format!("expected `{}`, found `{}`", expected.word, found.word),
+                });
+                break;
+            }
+        }
+
+        violations
+    }
+}
+
+fn whitespace_violation(found: &Token, expected: &Token) -> FormatViolation {
+    let newlines = |token: &Token| token.whitespace.matches('\n').count();
+    // number of spaces after the last newline
+    let spaces = |token: &Token| {
+        token.whitespace[token.whitespace.rfind('\n').map(|i| i + 1).unwrap_or(0)..]
+            .chars()
+            .count()
+    };
+    let plural = |count: usize, word: &str| {
+        format!("{} {}{}", count, word, if count == 1 { "" } else { "s" })
+    };
+
+    let (found_newlines, expected_newlines) = (newlines(found), newlines(expected));
+    if found_newlines != expected_newlines {
+        FormatViolation {
+            line: found.line,
+            column: found.column,
+            message: format!(
+                "expected {}, found {}",
+                plural(expected_newlines.saturating_sub(1), "blank line"),
+                found_newlines.saturating_sub(1)
+            ),
+        }
+    } else if found_newlines > 0 {
+        FormatViolation {
+            line: found.line + found_newlines,
+            column: 1,
+            message: format!(
+                "expected indentation of {}, found {}",
+                plural(spaces(expected), "space"),
+                spaces(found)
+            ),
+        }
+    } else {
+        FormatViolation {
+            line: found.line,
+            column: found.column,
+            message: format!(
+                "expected {}, found {}",
+                plural(spaces(expected), "space"),
+                spaces(found)
+            ),
+        }
+    }
+}
+
+// returns `true` if both documents have the same elements, ignoring differences
+// which are only formatting
+fn same_structure(org: &Org, other: &Org) -> bool {
+    let (mut events, mut other_events) = (org.iter(), other.iter());
+    loop {
+        match (events.next(), other_events.next()) {
+            (None, None) => return true,
+            (Some(Event::Start(element)), Some(Event::Start(other))) => {
+                if normalized(element) != normalized(other) {
+                    return false;
+                }
+            }
+            (Some(Event::End(_)), Some(Event::End(_))) => (),
+            _ => return false,
+        }
+    }
+}
+
+// writes an element in org syntax, without its formatting
+fn normalized(element: &Element) -> String {
+    let trim_lines = |text: &str| -> String {
+        text.lines()
+            .map(|line| format!("{}\n", line.trim_start()))
+            .collect()
+    };
+
+    let mut element = element.clone();
+    if let Some(post_blank) = element.post_blank_mut() {
+        *post_blank = 0;
+    }
+    match &mut element {
+        Element::Document { pre_blank } => *pre_blank = 0,
+        Element::SpecialBlock(block) => block.pre_blank = 0,
+        Element::QuoteBlock(block) => block.pre_blank = 0,
+        Element::CenterBlock(block) => block.pre_blank = 0,
+        Element::VerseBlock(block) => block.pre_blank = 0,
+        Element::DynBlock(block) => block.pre_blank = 0,
+        Element::Drawer(drawer) => drawer.pre_blank = 0,
+        Element::ListItem(item) => item.indent = 0,
+        Element::Text { value } => {
+            *value = value
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ")
+                .into()
+        }
+        Element::CommentBlock(block) => block.contents = dedent_contents(&block.contents).into(),
+        Element::ExampleBlock(block) => block.contents = dedent_contents(&block.contents).into(),
+        Element::ExportBlock(block) => block.contents = dedent_contents(&block.contents).into(),
+        Element::SourceBlock(block) => block.contents = dedent_contents(&block.contents).into(),
+        Element::FixedWidth(fixed_width) => {
+            fixed_width.value = trim_lines(&fixed_width.value).into()
+        }
+        Element::Comment(comment) => comment.value = trim_lines(&comment.value).into(),
+        Element::Table(Table::TableEl { value, .. }) => *value = trim_lines(value).into(),
+        _ => (),
+    }
+
+    let mut writer = Vec::new();
+    let _ = DefaultOrgHandler.start(&mut writer, &element);
+    let _ = DefaultOrgHandler.end(&mut writer, &element);
+    String::from_utf8_lossy(&writer).into_owned()
+}
+
+// a word, and the whitespace preceding it, with positions counted in chars
+struct Token<'a> {
+    line: usize,
+    column: usize,
+    whitespace: &'a str,
+    word_line: usize,
+    word_column: usize,
+    word: &'a str,
+}
+
+// splits text into words, trailing whitespace is returned as a token with an empty word
+//
+// table separators are words on their own, so aligning table cells only
+// changes whitespace
+fn tokenize(text: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let (mut line, mut column) = (1, 1);
+    let mut rest = text;
+
+    while !rest.is_empty() {
+        let (start_line, start_column) = (line, column);
+        let whitespace_len = rest.len() - rest.trim_start().len();
+        let (whitespace, after) = rest.split_at(whitespace_len);
+        let word_len = if after.starts_with('|') {
+            1
+        } else {
+            after
+                .find(|c: char| c.is_whitespace() || c == '|')
+                .unwrap_or(after.len())
+        };
+        let (word, after) = after.split_at(word_len);
+
+        let mut advance = |text: &str| {
+            for c in text.chars() {
+                if c == '\n' {
+                    line += 1;
+                    column = 1;
+                } else {
+                    column += 1;
+                }
+            }
+            (line, column)
+        };
+        let (word_line, word_column) = advance(whitespace);
+        advance(word);
+        tokens.push(Token {
+            line: start_line,
+            column: start_column,
+            whitespace,
+            word_line,
+            word_column,
+            word,
+        });
+        rest = after;
+    }
+
+    tokens
+}
+
+#[test]
+fn check_format_positions() {
+    let formatter = OrgFormatter::new();
+
+    // property lines are indented by the formatter, but not kept by the parser
+    let org = Org::parse("* A\n:PROPERTIES:\n  :ID: 1\n:END:\n");
+    assert!(org.check_format(&formatter).is_empty());
+
+    // neither is the alignment of tags
+    assert!(Org::parse("* Été :été:\n")
+        .check_format(&formatter)
+        .is_empty());
+
+    let violations = Org::parse("- Été\n   suite\n").check_format(&formatter);
+    assert_eq!(
+        violations,
+        [FormatViolation {
+            line: 2,
+            column: 1,
+            message: String::from("expected indentation of 2 spaces, found 3"),
+        }]
+    );
+
+    let org = Org::parse("* A\n:PROPERTIES:\n:B: 1\n:A: 2\n:END:\n");
+    assert_eq!(
+        org.check_format(&formatter),
+        [FormatViolation {
+            line: 3,
+            column: 3,
+            message: String::from("expected `:A:`, found `:B:`"),
+        }]
+    );
+}
+
+#[test]
+fn diff_format_structure() {
+    let diff = |source: &str, other: &str| Org::parse(source).diff_format(&Org::parse(other));
+
+    // list nesting
+    let result = diff("- a\n  - b\n", "- a\n- b\n");
+    assert!(result.structural);
+    assert_eq!(result.changes[0].found, "\n  ");
+    assert_eq!(result.changes[0].expected, "\n");
+
+    // reindenting a whole list keeps its nesting
+    assert!(!diff("  - a\n    - b\n", "- a\n  - b\n").structural);
+
+    // relative indentation inside blocks
+    let source = "#+BEGIN_SRC python\nif a:\n    b\n#+END_SRC\n";
+    assert!(diff(source, "#+BEGIN_SRC python\nif a:\nb\n#+END_SRC\n").structural);
+    let source = "- item\n  #+BEGIN_SRC python\n  if a:\n      b\n  #+END_SRC\n";
+    let result = diff(
+        source,
+        "- item\n    #+BEGIN_SRC python\n    if a:\n        b\n    #+END_SRC\n",
+    );
+    assert!(!result.structural);
+    // the indentation of begin and end lines isn't kept by the parser
+    assert_eq!(
+        result
+            .changes
+            .iter()
+            .map(|change| change.line)
+            .collect::<Vec<_>>(),
+        [2, 3]
+    );
+
+    // table alignment isn't kept by the parser
+    assert!(diff("|a|b|\n|-+-|\n", "| a | b |\n|---+---|\n").is_empty());
+    assert!(diff("|a|b|\n", "| a | c |\n").structural);
+}
+
+#[test]
+fn check_format_tables() {
+    let formatter = OrgFormatter::new();
+
+    let org = Org::parse("* A\n| a | b |\n|---+---|\n| c | d |\n\ntext\n");
+    assert!(org.check_format(&formatter).is_empty());
+
+    // violations after the table are still reported
+    let org = Org::parse("| a | bc |\n|x|y|\ntext\n* B\n:NOTES:\n   note\n:END:\n");
+    let violations = org.check_format(&formatter);
+    assert_eq!(
+        violations
+            .iter()
+            .map(|violation| (violation.line, violation.column))
+            .collect::<Vec<_>>(),
+        [(6, 1)]
+    );
+    assert_eq!(
+        violations[0].message,
+        "expected indentation of 2 spaces, found 3"
+    );
+}
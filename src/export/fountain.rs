@@ -0,0 +1,209 @@
+use indextree::NodeId;
+
+use crate::elements::Element;
+use crate::Org;
+
+// prefixes of scene headings recognized by fountain
+const SCENE_PREFIXES: [&str; 7] = [
+    "INT.", "EXT.", "EST.", "INT/EXT", "INT./EXT", "EXT/INT", "I/E",
+];
+
+impl Org<'_> {
+    /// Exports this document as a [Fountain] screenplay.
+    ///
+    /// `#+TITLE` and `#+AUTHOR` keywords are written to the title page.
+    /// Level-1 headlines become scene headings, forced with a leading `.` if
+    /// they don't start with a prefix like `INT.` or `EXT.`, and deeper
+    /// headlines become sections. Paragraph lines like `CHARACTER: line`,
+    /// where the character name is in uppercase, become dialogue, and other
+    /// lines become action.
+    ///
+    /// [Fountain]: https://fountain.io/syntax
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse(
+    ///     "#+TITLE: Brick & Steel\n\
+    ///      * int. house - day\n\
+    ///      Steel enters, /slowly/.\n\n\
+    ///      STEEL: Where is Brick?\n\
+    ///      BRICK: Here.\n\
+    ///      * Flashback\n\
+    ///      ** Act one\n",
+    /// );
+    ///
+    /// assert_eq!(
+    ///     org.to_fountain(),
+    ///     "Title: Brick & Steel\n\n\
+    ///      INT. HOUSE - DAY\n\n\
+    ///      Steel enters, *slowly*.\n\n\
+    ///      STEEL\nWhere is Brick?\n\n\
+    ///      BRICK\nHere.\n\n\
+    ///      .FLASHBACK\n\n# Act one\n"
+    /// );
+    /// ```
+    pub fn to_fountain(&self) -> String {
+        let mut output = String::new();
+
+        let mut title_page = false;
+        for (key, name) in [("TITLE", "Title"), ("AUTHOR", "Author")].iter() {
+            if let Some(value) = self.keyword_value(key) {
+                output.push_str(&format!("{}: {}\n", name, value.trim()));
+                title_page = true;
+            }
+        }
+        if title_page {
+            output.push('\n');
+        }
+
+        write_children(self, self.root, &mut output);
+        output.truncate(output.trim_end().len());
+        if !output.is_empty() {
+            output.push('\n');
+        }
+        output
+    }
+}
+
+fn write_children(org: &Org, node: NodeId, output: &mut String) {
+    for child in node.children(&org.arena) {
+        write_node(org, child, output);
+    }
+}
+
+fn write_node(org: &Org, node: NodeId, output: &mut String) {
+    match &org[node] {
+        Element::Document { .. }
+        | Element::Section
+        | Element::Headline { .. }
+        | Element::List(_)
+        | Element::ListItem(_)
+        | Element::QuoteBlock(_)
+        | Element::CenterBlock(_)
+        | Element::SpecialBlock(_)
+        | Element::VerseBlock(_)
+        | Element::DynBlock(_)
+        | Element::RadioTarget => write_children(org, node, output),
+        Element::Title(title) => {
+            let mut contents = String::new();
+            write_children(org, node, &mut contents);
+            let contents = contents.trim();
+            if title.level == 1 {
+                let heading = contents.to_uppercase();
+                if !SCENE_PREFIXES.iter().any(|p| heading.starts_with(p)) {
+                    output.push('.');
+                }
+                output.push_str(&heading);
+            } else {
+                output.push_str(&"#".repeat(title.level - 1));
+                output.push(' ');
+                output.push_str(contents);
+            }
+            output.push_str("\n\n");
+        }
+        Element::Paragraph { .. } => {
+            let mut contents = String::new();
+            write_children(org, node, &mut contents);
+            write_paragraph(contents.trim(), output);
+        }
+        Element::Bold => write_emphasis(org, node, output, "**"),
+        Element::Italic => write_emphasis(org, node, output, "*"),
+        Element::Underline => write_emphasis(org, node, output, "_"),
+        Element::Strike => write_children(org, node, output),
+        Element::Text { value } => output.push_str(value),
+        Element::Code { value } | Element::Verbatim { value } => output.push_str(value),
+        Element::Cookie(cookie) => output.push_str(&cookie.value),
+        Element::Link(link) => output.push_str(link.desc.as_ref().unwrap_or(&link.path)),
+        Element::Comment(_)
+        | Element::CommentBlock(_)
+        | Element::BabelCall(_)
+        | Element::Clock(_)
+        | Element::Drawer(_)
+        | Element::ExampleBlock(_)
+        | Element::ExportBlock(_)
+        | Element::FixedWidth(_)
+        | Element::FnDef(_)
+        | Element::FnRef(_)
+        | Element::InlineCall(_)
+        | Element::InlineSrc(_)
+        | Element::Keyword(_)
+        | Element::Macros(_)
+        | Element::Rule(_)
+        | Element::Snippet(_)
+        | Element::SourceBlock(_)
+        | Element::Table(_)
+        | Element::TableRow(_)
+        | Element::TableCell(_)
+        | Element::Target(_)
+        | Element::Timestamp(_) => (),
+    }
+}
+
+// writes dialogue lines as separate blocks, and other lines as action
+fn write_paragraph(contents: &str, output: &mut String) {
+    let mut action = Vec::new();
+    for line in contents.lines().map(str::trim) {
+        match dialogue(line) {
+            Some((character, speech)) => {
+                write_action(&action, output);
+                action.clear();
+                output.push_str(character);
+                output.push('\n');
+                output.push_str(speech);
+                output.push_str("\n\n");
+            }
+            None => action.push(line),
+        }
+    }
+    write_action(&action, output);
+}
+
+fn write_action(lines: &[&str], output: &mut String) {
+    if lines.is_empty() {
+        return;
+    }
+    for line in lines {
+        // uppercase lines would be parsed as character names
+        if is_character(line) {
+            output.push('!');
+        }
+        output.push_str(line);
+        output.push('\n');
+    }
+    output.push('\n');
+}
+
+fn dialogue(line: &str) -> Option<(&str, &str)> {
+    let index = line.find(':')?;
+    let (character, speech) = (line[0..index].trim(), line[index + 1..].trim());
+    if is_character(character) && !speech.is_empty() {
+        Some((character, speech))
+    } else {
+        None
+    }
+}
+
+fn is_character(name: &str) -> bool {
+    name.chars().any(char::is_alphabetic) && !name.chars().any(char::is_lowercase)
+}
+
+fn write_emphasis(org: &Org, node: NodeId, output: &mut String, marker: &str) {
+    output.push_str(marker);
+    write_children(org, node, output);
+    output.push_str(marker);
+}
+
+#[test]
+fn to_fountain() {
+    assert_eq!(Org::parse("").to_fountain(), "");
+
+    let org = Org::parse("#+AUTHOR: Zoé\n* été\nÉLISE: Bonjour.\nBOOM!\nnote: lowercase\n");
+    assert_eq!(
+        org.to_fountain(),
+        "Author: Zoé\n\n\
+         .ÉTÉ\n\n\
+         ÉLISE\nBonjour.\n\n\
+         !BOOM!\nnote: lowercase\n"
+    );
+}
@@ -0,0 +1,200 @@
+use indextree::NodeId;
+use unicode_width::UnicodeWidthStr;
+
+use crate::elements::{Element, Table, TableRow};
+use crate::export::{column_widths, fixed_width_contents, join_lines, write_plain_text};
+use crate::Org;
+
+impl Org<'_> {
+    /// Exports this document as [Gemtext], the markup of Gemini pages.
+    ///
+    /// Headlines become `#`, `##` and `###` headings, deeper headlines use
+    /// `###`. Paragraphs are joined into single lines, as Gemtext doesn't
+    /// wrap lines. Links can't be inline in Gemtext, so each link of a
+    /// paragraph or list item is written as a `=>` link line after it.
+    /// List items become `*` items, quote blocks `>` lines, and source
+    /// blocks, example blocks and tables preformatted blocks.
+    ///
+    /// [Gemtext]: https://gemini.circumlunar.space/docs/gemtext.gmi
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse(
+    ///     "* Links\nSee *the* [[gemini://example.org][capsule\nhere]].\n\
+    ///      - one\n  - two\n\
+    ///      #+BEGIN_SRC rust\nfn main() {}\n#+END_SRC\n\
+    ///      | a | bc |\n|---+----|\n| de | f |\n",
+    /// );
+    ///
+    /// assert_eq!(
+    ///     org.to_gemtext(),
+    ///     "# Links\n\n\
+    ///      See the capsule here.\n\
+    ///      => gemini://example.org capsule here\n\n\
+    ///      * one\n* two\n\n\
+    ///      ```rust\nfn main() {}\n```\n\n\
+    ///      ```\na  | bc\n---+---\nde | f\n```\n"
+    /// );
+    /// ```
+    pub fn to_gemtext(&self) -> String {
+        let mut blocks = Vec::new();
+        write_blocks(self, self.root, &mut blocks);
+        let mut output = blocks.join("\n\n");
+        if !output.is_empty() {
+            output.push('\n');
+        }
+        output
+    }
+}
+
+fn write_blocks(org: &Org, node: NodeId, blocks: &mut Vec<String>) {
+    for child in node.children(&org.arena) {
+        write_block(org, child, blocks);
+    }
+}
+
+fn write_block(org: &Org, node: NodeId, blocks: &mut Vec<String>) {
+    match &org[node] {
+        Element::Document { .. }
+        | Element::Section
+        | Element::Headline { .. }
+        | Element::CenterBlock(_)
+        | Element::SpecialBlock(_)
+        | Element::DynBlock(_) => write_blocks(org, node, blocks),
+        Element::Title(title) => {
+            let mut text = String::new();
+            write_plain_text(org, node, &mut text);
+            blocks.push(format!(
+                "{} {}",
+                "#".repeat(title.level.min(3)),
+                join_lines(&text)
+            ));
+        }
+        Element::Paragraph { .. } => blocks.push(paragraph(org, node)),
+        Element::QuoteBlock(_) | Element::VerseBlock(_) => {
+            let mut lines = Vec::new();
+            for child in node.children(&org.arena) {
+                let mut text = String::new();
+                write_plain_text(org, child, &mut text);
+                lines.extend(text.lines().map(|line| format!("> {}", line.trim())));
+            }
+            blocks.push(lines.join("\n"));
+        }
+        Element::List(_) => {
+            let mut lines = Vec::new();
+            write_list(org, node, &mut lines);
+            blocks.push(lines.join("\n"));
+        }
+        Element::SourceBlock(block) => blocks.push(preformatted(&block.language, &block.contents)),
+        Element::ExampleBlock(block) => blocks.push(preformatted("", &block.contents)),
+        Element::FixedWidth(fixed_width) => {
+            blocks.push(preformatted("", &fixed_width_contents(&fixed_width.value)))
+        }
+        Element::Table(Table::Org { .. }) => blocks.push(preformatted("", &table(org, node))),
+        Element::Table(Table::TableEl { value, .. }) => blocks.push(preformatted("", value)),
+        _ => (),
+    }
+}
+
+// gemtext has no nesting, nested items are flattened
+fn write_list(org: &Org, list: NodeId, lines: &mut Vec<String>) {
+    for item in list.children(&org.arena) {
+        for child in item.children(&org.arena) {
+            match org[child] {
+                Element::Paragraph { .. } => {
+                    let paragraph = paragraph(org, child);
+                    let mut paragraph = paragraph.lines();
+                    if let Some(text) = paragraph.next() {
+                        lines.push(format!("* {}", text));
+                    }
+                    lines.extend(paragraph.map(String::from));
+                }
+                Element::List(_) => write_list(org, child, lines),
+                _ => {
+                    let mut blocks = Vec::new();
+                    write_block(org, child, &mut blocks);
+                    lines.extend(blocks);
+                }
+            }
+        }
+    }
+}
+
+// text of the paragraph on one line, followed by one line per link
+fn paragraph(org: &Org, node: NodeId) -> String {
+    let mut text = String::new();
+    write_plain_text(org, node, &mut text);
+    let mut lines = vec![join_lines(&text)];
+    for link in org.extract_links(node) {
+        let mut line = format!("=> {}", link.path.trim_start_matches("file:"));
+        if let Some(desc) = &link.desc {
+            line.push(' ');
+            line.push_str(&join_lines(desc));
+        }
+        lines.push(line);
+    }
+    lines.join("\n")
+}
+
+// cells are aligned by padding them with spaces to their display width
+fn table(org: &Org, node: NodeId) -> String {
+    let mut rows: Vec<Option<Vec<String>>> = Vec::new();
+    for row in node.children(&org.arena) {
+        match org[row] {
+            Element::TableRow(TableRow::Header) | Element::TableRow(TableRow::Body) => {
+                let cells = row
+                    .children(&org.arena)
+                    .map(|cell| {
+                        let mut text = String::new();
+                        write_plain_text(org, cell, &mut text);
+                        join_lines(&text)
+                    })
+                    .collect();
+                rows.push(Some(cells));
+            }
+            _ => rows.push(None),
+        }
+    }
+
+    let widths = column_widths(&rows, UnicodeWidthStr::width);
+
+    let lines: Vec<_> = rows
+        .iter()
+        .map(|row| match row {
+            Some(cells) => {
+                let cells: Vec<_> = widths
+                    .iter()
+                    .enumerate()
+                    .map(|(i, width)| {
+                        let cell = cells.get(i).map(String::as_str).unwrap_or_default();
+                        format!("{}{}", cell, " ".repeat(width - cell.width()))
+                    })
+                    .collect();
+                cells.join(" | ").trim_end().to_string()
+            }
+            None => {
+                let rules: Vec<_> = widths.iter().map(|width| "-".repeat(*width)).collect();
+                rules.join("-+-")
+            }
+        })
+        .collect();
+    lines.join("\n")
+}
+
+fn preformatted(alt: &str, contents: &str) -> String {
+    format!("```{}\n{}\n```", alt, contents.trim_end_matches('\n'))
+}
+
+#[test]
+fn to_gemtext() {
+    assert_eq!(Org::parse("").to_gemtext(), "");
+
+    let org = Org::parse("***** 深い [[a.org][é]]\n| 日本 | a |\n| b | c |\n- [[file:x.org]]\n");
+    assert_eq!(
+        org.to_gemtext(),
+        "### 深い é\n\n\
+         ```\n日本 | a\nb    | c\n```\n\n\
+         * file:x.org\n=> x.org\n"
+    );
+}
@@ -0,0 +1,127 @@
+use serde_json::{json, Map, Value};
+
+use crate::config::ParseConfig;
+use crate::elements::Timestamp;
+use crate::export::markdown::to_markdown;
+use crate::Org;
+
+impl Org<'_> {
+    /// Exports headlines with a todo keyword as GitHub issues.
+    ///
+    /// Returns a json array, each item of which can be used as the body of
+    /// GitHub's create-issue API. Tags and priority cookie become labels, the
+    /// section is converted into markdown, and the deadline date is stored in
+    /// a custom `deadline` field. Headlines with one of the done keywords in
+    /// `config` have a `closed` state.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ParseConfig};
+    /// use serde_json::{json, Value};
+    ///
+    /// let org = Org::parse(
+    ///     "* TODO [#A] Fix crash :bug:\nDEADLINE: <2019-04-08 Mon>\nSee =main.rs=.\n* Notes",
+    /// );
+    /// let issues = org.to_github_issues_json(&ParseConfig::default());
+    /// let issues: Value = serde_json::from_str(&issues).unwrap();
+    ///
+    /// assert_eq!(
+    ///     issues,
+    ///     json!([{
+    ///         "title": "Fix crash",
+    ///         "body": "See `main.rs`.",
+    ///         "labels": ["bug", "priority-A"],
+    ///         "state": "open",
+    ///         "deadline": "2019-04-08"
+    ///     }])
+    /// );
+    /// ```
+    pub fn to_github_issues_json(&self, config: &ParseConfig) -> String {
+        let issues: Vec<Value> = self
+            .headlines()
+            .filter_map(|headline| {
+                let title = headline.title(self);
+                let keyword = title.keyword.as_ref()?;
+
+                let mut labels: Vec<String> =
+                    title.tags.iter().map(|tag| tag.to_string()).collect();
+                if let Some(priority) = title.priority {
+                    labels.push(format!("priority-{}", priority));
+                }
+
+                let closed = config.todo_keywords.1.iter().any(|k| k == keyword);
+
+                let mut issue = Map::new();
+                issue.insert("title".into(), json!(title.raw.trim()));
+                issue.insert(
+                    "body".into(),
+                    json!(headline
+                        .section_node()
+                        .map(|section| to_markdown(self, section))
+                        .unwrap_or_default()),
+                );
+                issue.insert("labels".into(), json!(labels));
+                issue.insert(
+                    "state".into(),
+                    json!(if closed { "closed" } else { "open" }),
+                );
+                if let Some(deadline) = title.deadline() {
+                    if let Some(date) = timestamp_date(deadline) {
+                        issue.insert("deadline".into(), json!(date));
+                    }
+                }
+
+                Some(Value::Object(issue))
+            })
+            .collect();
+
+        Value::Array(issues).to_string()
+    }
+}
+
+fn timestamp_date(timestamp: &Timestamp) -> Option<String> {
+    match timestamp {
+        Timestamp::Active { start, .. }
+        | Timestamp::Inactive { start, .. }
+        | Timestamp::ActiveRange { start, .. }
+        | Timestamp::InactiveRange { start, .. } => Some(format!(
+            "{}-{:02}-{:02}",
+            start.year, start.month, start.day
+        )),
+        Timestamp::Diary { .. } => None,
+    }
+}
+
+#[test]
+fn export() {
+    let config = ParseConfig {
+        todo_keywords: (vec!["OPEN".into()], vec!["CLOSED".into()]),
+        ..Default::default()
+    };
+    let org = Org::parse_custom(
+        "* CLOSED Write docs :doc:\n\
+         - [X] README\n\
+         - [ ] examples\n\
+         * OPEN Release\n",
+        &config,
+    );
+
+    let issues: Value = serde_json::from_str(&org.to_github_issues_json(&config)).unwrap();
+
+    assert_eq!(
+        issues,
+        json!([
+            {
+                "title": "Write docs",
+                "body": "- [X] README\n- [ ] examples",
+                "labels": ["doc"],
+                "state": "closed"
+            },
+            {
+                "title": "Release",
+                "body": "",
+                "labels": [],
+                "state": "open"
+            }
+        ])
+    );
+}
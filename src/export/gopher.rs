@@ -0,0 +1,175 @@
+use indextree::NodeId;
+
+use crate::elements::{Element, Link};
+use crate::export::sitemap::page_name;
+use crate::export::{join_lines, write_plain_text};
+use crate::Org;
+
+// maximum width of information lines, which most clients display unwrapped
+const WIDTH: usize = 70;
+
+impl Org<'_> {
+    /// Exports this document as a gophermap, the menu file of Gopher
+    /// servers like Gophernicus or Bucktooth.
+    ///
+    /// Contents before the first headline become information lines, wrapped
+    /// at 70 characters, and each link in them becomes a menu item: `gopher://`
+    /// urls keep their item type, and other urls become `h` items with a
+    /// `URL:` selector. Each level-1 headline then becomes a directory
+    /// entry, whose selector is `/name/`, where `name` is built like in
+    /// [`sitemap`].
+    ///
+    /// Directory entries and web links have no host and port, which servers
+    /// fill in with their own.
+    ///
+    /// [`sitemap`]: Org::sitemap
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse(
+    ///     "Welcome to my *hole*.\n\n\
+    ///      [[gopher://example.org:7070/0/notes.txt][Notes]]\n\
+    ///      * Phlog\n* About me\n",
+    /// );
+    ///
+    /// assert_eq!(
+    ///     org.to_gopher_map(),
+    ///     "iWelcome to my hole.\t\tnull.host\t1\n\
+    ///      i\t\tnull.host\t1\n\
+    ///      0Notes\t/notes.txt\texample.org\t7070\n\
+    ///      1Phlog\t/phlog/\n\
+    ///      1About me\t/about-me/\n"
+    /// );
+    /// ```
+    pub fn to_gopher_map(&self) -> String {
+        let mut lines = Vec::new();
+        if let Some(section) = self.document().section_node() {
+            for child in section.children(&self.arena) {
+                if !lines.is_empty() {
+                    lines.push(info(""));
+                }
+                write_block(self, child, &mut lines);
+            }
+        }
+        for headline in self.document().children(self) {
+            let title = headline.title(self);
+            let mut text = String::new();
+            write_plain_text(self, headline.title_node(), &mut text);
+            lines.push(format!("1{}\t/{}/", join_lines(&text), page_name(title)));
+        }
+
+        let mut output = String::new();
+        for line in lines {
+            output.push_str(&line);
+            output.push('\n');
+        }
+        output
+    }
+}
+
+fn write_block(org: &Org, node: NodeId, lines: &mut Vec<String>) {
+    match &org[node] {
+        Element::Paragraph { .. } => {
+            let mut text = String::new();
+            write_plain_text(org, node, &mut text);
+            let text = join_lines(&text);
+            // paragraphs of links only are written as menu items only
+            let only_links = node.children(&org.arena).all(|child| match &org[child] {
+                Element::Link(_) => true,
+                Element::Text { value } => value.trim().is_empty(),
+                _ => false,
+            });
+            if !text.is_empty() && !only_links {
+                lines.extend(wrap(&text).into_iter().map(info));
+            }
+            for link in org.extract_links(node) {
+                lines.push(link_item(link));
+            }
+        }
+        Element::List(_) => {
+            for item in node.children(&org.arena) {
+                let mut item_lines = Vec::new();
+                for child in item.children(&org.arena) {
+                    write_block(org, child, &mut item_lines);
+                }
+                if let Some(first) = item_lines.first_mut() {
+                    if let Some(text) = first.strip_prefix('i') {
+                        *first = format!("i- {}", text);
+                    }
+                }
+                lines.extend(item_lines);
+            }
+        }
+        Element::SourceBlock(block) => {
+            lines.extend(block.contents.lines().map(info));
+        }
+        Element::ExampleBlock(block) => {
+            lines.extend(block.contents.lines().map(info));
+        }
+        Element::QuoteBlock(_)
+        | Element::CenterBlock(_)
+        | Element::VerseBlock(_)
+        | Element::SpecialBlock(_) => {
+            for child in node.children(&org.arena) {
+                write_block(org, child, lines);
+            }
+        }
+        _ => (),
+    }
+}
+
+// an information line, which has no selector
+fn info(text: &str) -> String {
+    format!("i{}\t\tnull.host\t1", text.replace('\t', "    "))
+}
+
+fn link_item(link: &Link) -> String {
+    let desc = join_lines(link.desc.as_ref().unwrap_or(&link.path));
+
+    if let Some(url) = link.path.strip_prefix("gopher://") {
+        let (address, path) = match url.find('/') {
+            Some(i) => (&url[..i], &url[i + 1..]),
+            None => (url, ""),
+        };
+        let (host, port) = match address.rfind(':') {
+            Some(i) => (&address[..i], &address[i + 1..]),
+            None => (address, "70"),
+        };
+        // the first character of the path is the item type
+        let mut chars = path.chars();
+        let (kind, selector) = match chars.next() {
+            Some(kind) => (kind, chars.as_str()),
+            None => ('1', ""),
+        };
+        format!("{}{}\t{}\t{}\t{}", kind, desc, selector, host, port)
+    } else {
+        format!("h{}\tURL:{}", desc, link.path)
+    }
+}
+
+fn wrap(text: &str) -> Vec<&str> {
+    let mut lines = Vec::new();
+    let mut rest = text;
+    while rest.chars().count() > WIDTH {
+        let limit = rest
+            .char_indices()
+            .nth(WIDTH)
+            .map(|(i, _)| i)
+            .unwrap_or(rest.len());
+        let end = rest[..limit].rfind(' ').unwrap_or(limit);
+        lines.push(&rest[..end]);
+        rest = rest[end..].trim_start();
+    }
+    lines.push(rest);
+    lines
+}
+
+#[test]
+fn wrap_lines() {
+    let text = "word ".repeat(20);
+    let lines = wrap(text.trim());
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0], "word ".repeat(14).trim());
+    assert_eq!(lines[1], "word ".repeat(6).trim());
+}
@@ -1,10 +1,13 @@
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::io::{Error, Result as IOResult, Write};
+use std::path::{Path, PathBuf};
 
 use jetscii::{bytes, BytesConst};
 
-use crate::elements::{Element, Table, TableCell, TableRow, Timestamp};
-use crate::export::write_datetime;
+use crate::elements::{Element, Table, TableCell, TableRow, Timestamp, Title};
+use crate::export::{is_image, write_datetime};
+use crate::headline::org_protocol_url;
 
 /// A wrapper for escaping sensitive characters in html.
 ///
@@ -55,11 +58,294 @@ pub trait HtmlHandler<E: From<Error>>: Default {
 }
 
 /// Default Html Handler
+///
+/// Links to image files without description are rendered as `<img>`. Its
+/// `width`, `height`, `alt`, `class`, `id` and `style` attributes can be
+/// set by a preceding `#+ATTR_HTML:` keyword.
+///
+/// Braced superscripts and subscripts, like `x^{2}` and `H_{2}O`, are
+/// rendered as `<sup>` and `<sub>`, except in LaTeX math fragments, which
+/// are left for MathJax.
+///
+/// ```rust
+/// use orgize::Org;
+///
+/// let org = Org::parse("#+ATTR_HTML: :width 100 :alt \"a cat\"\n[[file:cat.png]]\n");
+///
+/// let mut writer = Vec::new();
+/// org.write_html(&mut writer).unwrap();
+/// assert_eq!(
+///     String::from_utf8(writer).unwrap(),
+///     "<main><section><p><img src=\"cat.png\" width=\"100\" alt=\"a cat\"></p></section></main>"
+/// );
+/// ```
 #[derive(Default)]
-pub struct DefaultHtmlHandler;
+pub struct DefaultHtmlHandler {
+    // attributes of the last `#+ATTR_HTML:` keyword, applied to the next image
+    attr_html: Vec<(String, String)>,
+    // directory which image files are read from, for their dimensions
+    #[cfg(feature = "image")]
+    base_path: Option<PathBuf>,
+    // whether headlines are followed by an `org-protocol://` link
+    org_protocol_links: bool,
+    // file which `org-protocol://` links open, if headlines have no id
+    source_path: Option<PathBuf>,
+    // whether html5 sectioning elements are used for the document structure
+    semantic_html5: bool,
+    // number of headlines currently open, zero in the preamble
+    headline_depth: usize,
+    // whether aria roles and labels are added
+    aria: bool,
+    // whether sections, tables and preformatted blocks get `id` attributes
+    element_ids: bool,
+    // value of the last `#+NAME:` keyword, used as the next element's id
+    name: Option<String>,
+    // number of generated ids for each tag
+    id_counts: HashMap<&'static str, usize>,
+    // ids already written, so none of them is used twice
+    ids: HashSet<String>,
+    // whether headlines with article properties get schema.org microdata
+    microdata: bool,
+    // whether each open headline is a schema.org article, innermost last
+    articles: Vec<bool>,
+}
+
+impl DefaultHtmlHandler {
+    /// Adds a `<a class="org-protocol">` link at the end of each headline,
+    /// which opens the headline in Emacs, see [`Org::to_org_protocol_url`].
+    ///
+    /// Headlines without an `ID` property are linked to `source_path`, or
+    /// get no link if it's `None`. Links can be hidden by css until the
+    /// headline is hovered.
+    ///
+    /// [`Org::to_org_protocol_url`]: ../struct.Org.html#method.to_org_protocol_url
+    ///
+    /// ```rust
+    /// use orgize::export::DefaultHtmlHandler;
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse("* Notes\n:PROPERTIES:\n:ID: 2b5a\n:END:\n");
+    /// let mut handler = DefaultHtmlHandler::default().with_org_protocol_links(None);
+    ///
+    /// let mut writer = Vec::new();
+    /// org.write_html_custom(&mut writer, &mut handler).unwrap();
+    /// assert_eq!(
+    ///     String::from_utf8(writer).unwrap(),
+    ///     "<main><h1>Notes<a class=\"org-protocol\" \
+    ///      href=\"org-protocol://roam-node?node_id=2b5a\">📋</a></h1></main>"
+    /// );
+    /// ```
+    pub fn with_org_protocol_links(mut self, source_path: Option<PathBuf>) -> Self {
+        self.org_protocol_links = true;
+        self.source_path = source_path;
+        self
+    }
+
+    /// Uses html5 sectioning elements for the document structure: the
+    /// document is wrapped in `<article>`, the preamble before the first
+    /// headline in `<header>`, each headline with its contents and
+    /// subheadlines in `<section>`, and drawers in `<aside>`.
+    ///
+    /// ```rust
+    /// use orgize::export::DefaultHtmlHandler;
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse("Intro\n* Notes\n:LOGBOOK:\n- Note\n:END:\n** Details\n");
+    /// let mut handler = DefaultHtmlHandler::default().with_semantic_html5();
+    ///
+    /// let mut writer = Vec::new();
+    /// org.write_html_custom(&mut writer, &mut handler).unwrap();
+    /// assert_eq!(
+    ///     String::from_utf8(writer).unwrap(),
+    ///     "<article><header><p>Intro</p></header>\
+    ///      <section><h1>Notes</h1><aside><ul><li><p>Note</p></li></ul></aside>\
+    ///      <section><h2>Details</h2></section></section></article>"
+    /// );
+    /// ```
+    pub fn with_semantic_html5(mut self) -> Self {
+        self.semantic_html5 = true;
+        self
+    }
+
+    /// Adds aria roles to headings, lists, tables and source blocks.
+    ///
+    /// Headings also get an `aria-level` with the actual headline level,
+    /// since html headings stop at `<h6>`. With
+    /// [`with_semantic_html5`](#method.with_semantic_html5), drawers are
+    /// labeled by their names.
+    ///
+    /// ```rust
+    /// use orgize::export::DefaultHtmlHandler;
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse("******* Deep\n- item\n");
+    /// let mut handler = DefaultHtmlHandler::default().with_aria();
+    ///
+    /// let mut writer = Vec::new();
+    /// org.write_html_custom(&mut writer, &mut handler).unwrap();
+    /// assert_eq!(
+    ///     String::from_utf8(writer).unwrap(),
+    ///     "<main><h6 role=\"heading\" aria-level=\"7\">Deep</h6>\
+    ///      <section><ul role=\"list\"><li><p>item</p></li></ul></section></main>"
+    /// );
+    /// ```
+    pub fn with_aria(mut self) -> Self {
+        self.aria = true;
+        self
+    }
+
+    /// Adds an `id` attribute to each `<section>`, `<table>` and `<pre>`.
+    ///
+    /// Tables and blocks use the value of a preceding non-empty `#+NAME:`
+    /// keyword, other elements get sequential ids like `table-2`, counted
+    /// per tag. Ids are unique, a `-2`, `-3`, ... suffix is added to names
+    /// already used, and sequential ids taken by names are skipped.
+    ///
+    /// ```rust
+    /// use orgize::export::DefaultHtmlHandler;
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse("#+NAME: totals\n| 1 |\n\n#+BEGIN_EXAMPLE\nfixed\n#+END_EXAMPLE\n");
+    /// let mut handler = DefaultHtmlHandler::default().with_element_ids();
+    ///
+    /// let mut writer = Vec::new();
+    /// org.write_html_custom(&mut writer, &mut handler).unwrap();
+    /// assert_eq!(
+    ///     String::from_utf8(writer).unwrap(),
+    ///     "<main><section id=\"section-1\">\
+    ///      <table id=\"totals\"><tbody><tr><td>1</td></tr></tbody></table>\
+    ///      <pre id=\"pre-1\" class=\"example\">fixed\n</pre></section></main>"
+    /// );
+    /// ```
+    pub fn with_element_ids(mut self) -> Self {
+        self.element_ids = true;
+        self
+    }
+
+    /// Marks up headlines which have any of the `URL`, `AUTHOR`, `DATE` or
+    /// `DESCRIPTION` properties as a schema.org `Article`, with the title as
+    /// its `headline` and the properties as `url`, `author`,
+    /// `datePublished` and `description`. The article contains the section
+    /// and sub-headlines of the headline.
+    ///
+    /// `DATE` is converted to ISO 8601, and omitted if it is neither a
+    /// timestamp nor a date like `2024-01-15`.
+    ///
+    /// ```rust
+    /// use orgize::export::DefaultHtmlHandler;
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse(
+    ///     "* Release notes\n:PROPERTIES:\n:AUTHOR: Jane\n:DATE: <2024-01-15 Mon>\n:END:\nFixed.\n",
+    /// );
+    /// let mut handler = DefaultHtmlHandler::default().with_microdata();
+    ///
+    /// let mut writer = Vec::new();
+    /// org.write_html_custom(&mut writer, &mut handler).unwrap();
+    /// assert_eq!(
+    ///     String::from_utf8(writer).unwrap(),
+    ///     "<main><div itemscope itemtype=\"https://schema.org/Article\">\
+    ///      <h1 itemprop=\"headline\">Release notes</h1>\
+    ///      <meta itemprop=\"author\" content=\"Jane\">\
+    ///      <meta itemprop=\"datePublished\" content=\"2024-01-15\">\
+    ///      <section><p>Fixed.</p></section></div></main>"
+    /// );
+    /// ```
+    pub fn with_microdata(mut self) -> Self {
+        self.microdata = true;
+        self
+    }
+
+    // returns an `id` attribute if element ids are enabled, with a leading space
+    fn element_id(&mut self, tag: &'static str, name: Option<String>) -> String {
+        if !self.element_ids {
+            return String::new();
+        }
+        let id = match name {
+            Some(name) => (1..)
+                .map(|n| match n {
+                    1 => name.clone(),
+                    n => format!("{}-{}", name, n),
+                })
+                .find(|id| !self.ids.contains(id)),
+            None => {
+                let count = self.id_counts.entry(tag).or_insert(0);
+                let ids = &self.ids;
+                std::iter::repeat_with(|| {
+                    *count += 1;
+                    format!("{}-{}", tag, count)
+                })
+                .find(|id| !ids.contains(id))
+            }
+        }
+        .unwrap_or_default();
+        self.ids.insert(id.clone());
+        format!(" id=\"{}\"", HtmlEscape(id))
+    }
+
+    // returns a `role` attribute if aria is enabled, with a leading space
+    fn role(&self, role: &str) -> String {
+        if self.aria {
+            format!(" role=\"{}\"", role)
+        } else {
+            String::new()
+        }
+    }
+}
+
+#[cfg(feature = "image")]
+impl DefaultHtmlHandler {
+    /// Reads image files from `base_path`, and adds their actual `width` and
+    /// `height` to `<img>` tags, unless any of them is given by
+    /// `#+ATTR_HTML:`.
+    ///
+    /// Image files are only read when they are exported, and those that
+    /// can't be read are silently skipped.
+    ///
+    /// ```rust,no_run
+    /// use orgize::export::DefaultHtmlHandler;
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse("[[./images/cat.png]]");
+    /// let mut handler = DefaultHtmlHandler::default().with_base_path("notes");
+    ///
+    /// let mut writer = Vec::new();
+    /// org.write_html_custom(&mut writer, &mut handler).unwrap();
+    /// ```
+    pub fn with_base_path<P: Into<PathBuf>>(mut self, base_path: P) -> Self {
+        self.base_path = Some(base_path.into());
+        self
+    }
+
+    fn push_dimensions(&self, src: &str, attrs: &mut Vec<(String, String)>) {
+        if src.contains("://")
+            || attrs
+                .iter()
+                .any(|(key, _)| key == "width" || key == "height")
+        {
+            return;
+        }
+        if let Some(base_path) = &self.base_path {
+            if let Ok((width, height)) = image::image_dimensions(base_path.join(src)) {
+                attrs.push((String::from("width"), width.to_string()));
+                attrs.push((String::from("height"), height.to_string()));
+            }
+        }
+    }
+}
 
 impl HtmlHandler<Error> for DefaultHtmlHandler {
     fn start<W: Write>(&mut self, mut w: W, element: &Element) -> IOResult<()> {
+        // affiliated keywords only apply to a standalone image in the next paragraph
+        match element {
+            Element::Keyword(_) | Element::Paragraph { .. } | Element::Link(_) => (),
+            _ => self.attr_html.clear(),
+        }
+        let name = match element {
+            Element::Keyword(_) => None,
+            _ => self.name.take(),
+        };
+
         match element {
             // container elements
             Element::SpecialBlock(_) => (),
@@ -67,27 +353,35 @@ impl HtmlHandler<Error> for DefaultHtmlHandler {
             Element::CenterBlock(_) => write!(w, "<div class=\"center\">")?,
             Element::VerseBlock(_) => write!(w, "<p class=\"verse\">")?,
             Element::Bold => write!(w, "<b>")?,
+            Element::Document { .. } if self.semantic_html5 => write!(w, "<article>")?,
             Element::Document { .. } => write!(w, "<main>")?,
             Element::DynBlock(_dyn_block) => (),
+            Element::Headline { .. } if self.semantic_html5 => {
+                self.headline_depth += 1;
+                write!(w, "<section{}>", self.element_id("section", name))?;
+            }
             Element::Headline { .. } => (),
             Element::List(list) => {
-                if list.ordered {
-                    write!(w, "<ol>")?;
-                } else {
-                    write!(w, "<ul>")?;
-                }
+                let tag = if list.ordered { "ol" } else { "ul" };
+                write!(w, "<{}{}>", tag, self.role("list"))?;
             }
             Element::Italic => write!(w, "<i>")?,
             Element::ListItem(_) => write!(w, "<li>")?,
             Element::Paragraph { .. } => write!(w, "<p>")?,
-            Element::Section => write!(w, "<section>")?,
+            Element::Section if self.semantic_html5 => {
+                if self.headline_depth == 0 {
+                    write!(w, "<header>")?;
+                }
+            }
+            Element::Section => write!(w, "<section{}>", self.element_id("section", name))?,
             Element::Strike => write!(w, "<s>")?,
             Element::Underline => write!(w, "<u>")?,
             // non-container elements
             Element::CommentBlock(_) => (),
             Element::ExampleBlock(block) => write!(
                 w,
-                "<pre class=\"example\">{}</pre>",
+                "<pre{} class=\"example\">{}</pre>",
+                self.element_id("pre", name),
                 HtmlEscape(&block.contents)
             )?,
             Element::ExportBlock(block) => {
@@ -96,17 +390,22 @@ impl HtmlHandler<Error> for DefaultHtmlHandler {
                 }
             }
             Element::SourceBlock(block) => {
+                let id = self.element_id("pre", name);
                 if block.language.is_empty() {
                     write!(
                         w,
-                        "<pre class=\"example\">{}</pre>",
+                        "<pre{} class=\"example\"{}>{}</pre>",
+                        id,
+                        self.role("code"),
                         HtmlEscape(&block.contents)
                     )?;
                 } else {
                     write!(
                         w,
-                        "<div class=\"org-src-container\"><pre class=\"src src-{}\">{}</pre></div>",
+                        "<div class=\"org-src-container\"><pre{} class=\"src src-{}\"{}>{}</pre></div>",
+                        id,
                         block.language,
+                        self.role("code"),
                         HtmlEscape(&block.contents)
                     )?;
                 }
@@ -121,12 +420,31 @@ impl HtmlHandler<Error> for DefaultHtmlHandler {
             Element::Code { value } => write!(w, "<code>{}</code>", HtmlEscape(value))?,
             Element::FnRef(_fn_ref) => (),
             Element::InlineCall(_) => (),
-            Element::Link(link) => write!(
-                w,
-                "<a href=\"{}\">{}</a>",
-                HtmlEscape(&link.path),
-                HtmlEscape(link.desc.as_ref().unwrap_or(&link.path)),
-            )?,
+            Element::Link(link) if link.desc.is_none() && is_image(&link.path) => {
+                let src = link.path.trim_start_matches("file:");
+                #[allow(unused_mut)]
+                let mut attrs = std::mem::take(&mut self.attr_html);
+                #[cfg(feature = "image")]
+                self.push_dimensions(src, &mut attrs);
+                write!(w, "<img src=\"{}\"", HtmlEscape(src))?;
+                if !attrs.iter().any(|(key, _)| key == "alt") {
+                    let name = Path::new(src).file_name().and_then(|name| name.to_str());
+                    write!(w, " alt=\"{}\"", HtmlEscape(name.unwrap_or(src)))?;
+                }
+                for (key, value) in attrs {
+                    write!(w, " {}=\"{}\"", key, HtmlEscape(value))?;
+                }
+                write!(w, ">")?;
+            }
+            Element::Link(link) => {
+                self.attr_html.clear();
+                write!(
+                    w,
+                    "<a href=\"{}\">{}</a>",
+                    HtmlEscape(&link.path),
+                    HtmlEscape(link.desc.as_ref().unwrap_or(&link.path)),
+                )?
+            }
             Element::Macros(_macros) => (),
             Element::RadioTarget => (),
             Element::Snippet(snippet) => {
@@ -135,7 +453,7 @@ impl HtmlHandler<Error> for DefaultHtmlHandler {
                 }
             }
             Element::Target(_target) => (),
-            Element::Text { value } => write!(w, "{}", HtmlEscape(value))?,
+            Element::Text { value } => write_text(&mut w, value)?,
             Element::Timestamp(timestamp) => {
                 write!(
                     &mut w,
@@ -170,19 +488,46 @@ impl HtmlHandler<Error> for DefaultHtmlHandler {
             Element::Comment(_) => (),
             Element::FixedWidth(fixed_width) => write!(
                 w,
-                "<pre class=\"example\">{}</pre>",
+                "<pre{} class=\"example\">{}</pre>",
+                self.element_id("pre", name),
                 HtmlEscape(&fixed_width.value)
             )?,
+            Element::Keyword(keyword) if keyword.key.eq_ignore_ascii_case("ATTR_HTML") => {
+                self.attr_html.extend(parse_attr_html(&keyword.value));
+            }
+            Element::Keyword(keyword) if keyword.key.eq_ignore_ascii_case("NAME") => {
+                let name = keyword.value.trim();
+                self.name = Some(name.to_string()).filter(|_| !name.is_empty());
+            }
             Element::Keyword(_keyword) => (),
+            Element::Drawer(drawer) if self.semantic_html5 && self.aria => {
+                write!(w, "<aside aria-label=\"{}\">", HtmlEscape(&drawer.name))?
+            }
+            Element::Drawer(_drawer) if self.semantic_html5 => write!(w, "<aside>")?,
             Element::Drawer(_drawer) => (),
             Element::Rule(_) => write!(w, "<hr>")?,
             Element::Cookie(cookie) => write!(w, "<code>{}</code>", cookie.value)?,
             Element::Title(title) => {
-                write!(w, "<h{}>", if title.level <= 6 { title.level } else { 6 })?;
+                let article = self.microdata && is_article(title);
+                if self.microdata {
+                    self.articles.push(article);
+                }
+                if article {
+                    write!(w, "<div itemscope itemtype=\"https://schema.org/Article\">")?;
+                }
+                write!(w, "<h{}", if title.level <= 6 { title.level } else { 6 })?;
+                if article {
+                    write!(w, " itemprop=\"headline\"")?;
+                }
+                if self.aria {
+                    write!(w, " role=\"heading\" aria-level=\"{}\"", title.level)?;
+                }
+                write!(w, ">")?;
             }
             Element::Table(Table::TableEl { .. }) => (),
             Element::Table(Table::Org { has_header, .. }) => {
-                write!(w, "<table>")?;
+                let id = self.element_id("table", name);
+                write!(w, "<table{}{}>", id, self.role("table"))?;
                 if *has_header {
                     write!(w, "<thead>")?;
                 } else {
@@ -212,9 +557,18 @@ impl HtmlHandler<Error> for DefaultHtmlHandler {
             Element::CenterBlock(_) => write!(w, "</div>")?,
             Element::VerseBlock(_) => write!(w, "</p>")?,
             Element::Bold => write!(w, "</b>")?,
+            Element::Document { .. } if self.semantic_html5 => write!(w, "</article>")?,
             Element::Document { .. } => write!(w, "</main>")?,
             Element::DynBlock(_dyn_block) => (),
-            Element::Headline { .. } => (),
+            Element::Headline { .. } => {
+                if self.articles.pop() == Some(true) {
+                    write!(w, "</div>")?;
+                }
+                if self.semantic_html5 {
+                    self.headline_depth -= 1;
+                    write!(w, "</section>")?;
+                }
+            }
             Element::List(list) => {
                 if list.ordered {
                     write!(w, "</ol>")?;
@@ -225,11 +579,54 @@ impl HtmlHandler<Error> for DefaultHtmlHandler {
             Element::Italic => write!(w, "</i>")?,
             Element::ListItem(_) => write!(w, "</li>")?,
             Element::Paragraph { .. } => write!(w, "</p>")?,
+            Element::Section if self.semantic_html5 => {
+                if self.headline_depth == 0 {
+                    write!(w, "</header>")?;
+                }
+            }
             Element::Section => write!(w, "</section>")?,
             Element::Strike => write!(w, "</s>")?,
             Element::Underline => write!(w, "</u>")?,
             Element::Title(title) => {
-                write!(w, "</h{}>", if title.level <= 6 { title.level } else { 6 })?
+                if self.org_protocol_links {
+                    if let Some(url) = org_protocol_url(title, self.source_path.as_deref()) {
+                        write!(
+                            w,
+                            "<a class=\"org-protocol\" href=\"{}\">📋</a>",
+                            HtmlEscape(url)
+                        )?;
+                    }
+                }
+                write!(w, "</h{}>", if title.level <= 6 { title.level } else { 6 })?;
+                if self.microdata && is_article(title) {
+                    for (key, value) in title.properties.iter() {
+                        let value = value.trim();
+                        match ARTICLE_PROPERTIES
+                            .iter()
+                            .find(|(property, _)| key.eq_ignore_ascii_case(property))
+                        {
+                            Some((_, "url")) => {
+                                write!(w, "<link itemprop=\"url\" href=\"{}\">", HtmlEscape(value))?
+                            }
+                            Some((_, "datePublished")) => {
+                                if let Some(date) = iso_date(value) {
+                                    write!(
+                                        w,
+                                        "<meta itemprop=\"datePublished\" content=\"{}\">",
+                                        date
+                                    )?;
+                                }
+                            }
+                            Some((_, itemprop)) => write!(
+                                w,
+                                "<meta itemprop=\"{}\" content=\"{}\">",
+                                itemprop,
+                                HtmlEscape(value)
+                            )?,
+                            None => (),
+                        }
+                    }
+                }
             }
             Element::Table(Table::TableEl { .. }) => (),
             Element::Table(Table::Org { .. }) => {
@@ -242,6 +639,7 @@ impl HtmlHandler<Error> for DefaultHtmlHandler {
                 TableCell::Body => write!(w, "</td>")?,
                 TableCell::Header => write!(w, "</th>")?,
             },
+            Element::Drawer(_drawer) if self.semantic_html5 => write!(w, "</aside>")?,
             // non-container elements
             _ => debug_assert!(!element.is_container()),
         }
@@ -250,6 +648,382 @@ impl HtmlHandler<Error> for DefaultHtmlHandler {
     }
 }
 
+// headline properties and their schema.org article itemprops
+const ARTICLE_PROPERTIES: [(&str, &str); 4] = [
+    ("URL", "url"),
+    ("AUTHOR", "author"),
+    ("DATE", "datePublished"),
+    ("DESCRIPTION", "description"),
+];
+
+// converts a date, like `2024-01-15` or `<2024-01-15 Mon 10:00>`, to iso 8601
+fn iso_date(value: &str) -> Option<String> {
+    let timestamp = Timestamp::parse_active(value)
+        .or_else(|| Timestamp::parse_inactive(value))
+        .filter(|(rest, _)| rest.trim().is_empty());
+    let start = match timestamp {
+        Some((_, Timestamp::Active { start, .. }))
+        | Some((_, Timestamp::Inactive { start, .. }))
+        | Some((_, Timestamp::ActiveRange { start, .. }))
+        | Some((_, Timestamp::InactiveRange { start, .. })) => start,
+        Some((_, Timestamp::Diary { .. })) => return None,
+        None => {
+            let bytes = value.as_bytes();
+            let is_date = bytes.len() == 10
+                && bytes.iter().enumerate().all(|(i, b)| match i {
+                    4 | 7 => *b == b'-',
+                    _ => b.is_ascii_digit(),
+                });
+            let valid = is_date
+                && matches!(value[5..7].parse::<u8>(), Ok(1..=12))
+                && matches!(value[8..10].parse::<u8>(), Ok(1..=31));
+            return Some(value.to_string()).filter(|_| valid);
+        }
+    };
+
+    let mut date = format!("{:04}-{:02}-{:02}", start.year, start.month, start.day);
+    if let (Some(hour), Some(minute)) = (start.hour, start.minute) {
+        date.push_str(&format!("T{:02}:{:02}", hour, minute));
+    }
+    Some(date)
+}
+
+// returns `true` if the title has any schema.org article property
+fn is_article(title: &Title) -> bool {
+    title.properties.iter().any(|(key, _)| {
+        ARTICLE_PROPERTIES
+            .iter()
+            .any(|(property, _)| key.eq_ignore_ascii_case(property))
+    })
+}
+
+// writes text, turning `x^{2}` and `H_{2}O` into `<sup>` and `<sub>`
+//
+// latex math fragments are kept as is, so they can be rendered by mathjax
+fn write_text<W: Write>(mut w: W, text: &str) -> IOResult<()> {
+    let mut start = 0;
+    let mut i = 0;
+    while i < text.len() {
+        let rest = &text[i..];
+
+        let math_end = [("\\(", "\\)"), ("\\[", "\\]"), ("$", "$")]
+            .iter()
+            .find(|(open, _)| rest.starts_with(open))
+            .and_then(|(open, close)| {
+                Some(open.len() + rest[open.len()..].find(close)? + close.len())
+            });
+        if let Some(end) = math_end {
+            i += end;
+            continue;
+        }
+
+        let script = if rest.starts_with("^{") {
+            Some("sup")
+        } else if rest.starts_with("_{") {
+            Some("sub")
+        } else {
+            None
+        };
+        let after_word = matches!(text[..i].chars().next_back(), Some(c) if !c.is_whitespace());
+        if let Some(tag) = script.filter(|_| after_word) {
+            let contents = rest[2..]
+                .find(['{', '}'])
+                .filter(|end| rest[2 + end..].starts_with('}'))
+                .map(|end| &rest[2..2 + end])
+                .filter(|contents| !contents.is_empty());
+            if let Some(contents) = contents {
+                write!(
+                    w,
+                    "{}<{tag}>{}</{tag}>",
+                    HtmlEscape(&text[start..i]),
+                    HtmlEscape(contents),
+                    tag = tag
+                )?;
+                i += contents.len() + 3;
+                start = i;
+                continue;
+            }
+        }
+
+        i += rest.chars().next().map_or(1, char::len_utf8);
+    }
+    write!(w, "{}", HtmlEscape(&text[start..]))
+}
+
+// parses `:width 100 :alt "a cat"`, keeping only attributes supported by `<img>`
+pub(crate) fn parse_attr_html(value: &str) -> Vec<(String, String)> {
+    const ATTRIBUTES: [&str; 6] = ["width", "height", "alt", "class", "id", "style"];
+
+    let mut attrs = Vec::new();
+    let mut rest = value.trim();
+    while let Some(tail) = rest.strip_prefix(':') {
+        let end = tail.find(char::is_whitespace).unwrap_or(tail.len());
+        let key = tail[..end].to_ascii_lowercase();
+        let tail = tail[end..].trim_start();
+
+        let (value, tail) = match tail.strip_prefix('"') {
+            Some(quoted) => match quoted.find('"') {
+                Some(i) => (&quoted[..i], &quoted[i + 1..]),
+                None => (quoted, ""),
+            },
+            None => match tail.find(" :") {
+                Some(i) => (&tail[..i], &tail[i..]),
+                None => (tail, ""),
+            },
+        };
+
+        if ATTRIBUTES.contains(&&*key) {
+            attrs.retain(|(k, _)| *k != key);
+            attrs.push((key, value.trim().to_string()));
+        }
+        rest = tail.trim_start();
+    }
+    attrs
+}
+
+#[test]
+fn attr_html() {
+    assert_eq!(
+        parse_attr_html(r#":width 100 :alt "a :cat" :onclick alert() :class a b"#),
+        vec![
+            ("width".into(), "100".into()),
+            ("alt".into(), "a :cat".into()),
+            ("class".into(), "a b".into()),
+        ]
+    );
+
+    let org = crate::Org::parse(
+        "#+ATTR_HTML: :width 100\n#+ATTR_HTML: :class photo\n[[./cat.png]]\n\n[[dog.jpg]]\n",
+    );
+    let mut writer = Vec::new();
+    org.write_html(&mut writer).unwrap();
+    assert_eq!(
+        String::from_utf8(writer).unwrap(),
+        "<main><section>\
+         <p><img src=\"./cat.png\" alt=\"cat.png\" width=\"100\" class=\"photo\"></p>\
+         <p><img src=\"dog.jpg\" alt=\"dog.jpg\"></p>\
+         </section></main>"
+    );
+}
+
+#[test]
+fn sub_superscript() {
+    let org = crate::Org::parse("E = mc^{2} and H_{2}O, but not \\(x^{2}\\), a_b or _{x}\n");
+    let mut writer = Vec::new();
+    org.write_html(&mut writer).unwrap();
+    assert_eq!(
+        String::from_utf8(writer).unwrap(),
+        "<main><section><p>E = mc<sup>2</sup> and H<sub>2</sub>O, \
+         but not \\(x^{2}\\), a_b or _{x}</p></section></main>"
+    );
+}
+
+#[cfg(feature = "image")]
+#[test]
+fn image_dimensions() {
+    let dir = crate::test_dir("image-dimensions");
+    image::RgbImage::new(3, 2)
+        .save(dir.join("cat.png"))
+        .unwrap();
+
+    let org =
+        crate::Org::parse("[[cat.png]]\n\n#+ATTR_HTML: :width 6\n[[cat.png]]\n\n[[missing.png]]\n");
+    let mut writer = Vec::new();
+    let mut handler = DefaultHtmlHandler::default().with_base_path(&dir);
+    org.write_html_custom(&mut writer, &mut handler).unwrap();
+    assert_eq!(
+        String::from_utf8(writer).unwrap(),
+        "<main><section>\
+         <p><img src=\"cat.png\" alt=\"cat.png\" width=\"3\" height=\"2\"></p>\
+         <p><img src=\"cat.png\" alt=\"cat.png\" width=\"6\"></p>\
+         <p><img src=\"missing.png\" alt=\"missing.png\"></p>\
+         </section></main>"
+    );
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(test)]
+fn to_html(source: &str, mut handler: DefaultHtmlHandler) -> String {
+    let mut writer = Vec::new();
+    crate::Org::parse(source)
+        .write_html_custom(&mut writer, &mut handler)
+        .unwrap();
+    String::from_utf8(writer).unwrap()
+}
+
+#[test]
+fn org_protocol_links() {
+    let source = "* Été & co\n******* <deep>\n:PROPERTIES:\n:ID: a b\n:END:\n";
+
+    assert_eq!(
+        to_html(
+            source,
+            DefaultHtmlHandler::default().with_org_protocol_links(None)
+        ),
+        "<main><h1>Été &amp; co</h1><h6>&lt;deep&gt;<a class=\"org-protocol\" \
+         href=\"org-protocol://roam-node?node_id=a%20b\">📋</a></h6></main>"
+    );
+    // relative paths can't be opened
+    assert_eq!(
+        to_html(
+            "* a\n",
+            DefaultHtmlHandler::default().with_org_protocol_links(Some("notes.org".into()))
+        ),
+        "<main><h1>a</h1></main>"
+    );
+    assert_eq!(
+        to_html(
+            "* Été & co\n",
+            DefaultHtmlHandler::default().with_org_protocol_links(Some("/notes/é.org".into()))
+        ),
+        "<main><h1>Été &amp; co<a class=\"org-protocol\" \
+         href=\"org-protocol://open?url=file:///notes/%C3%A9.org::*%C3%89t%C3%A9%20%26%20co\">📋</a></h1></main>"
+    );
+    assert_eq!(
+        to_html(
+            "",
+            DefaultHtmlHandler::default().with_org_protocol_links(None)
+        ),
+        "<main></main>"
+    );
+}
+
+#[test]
+fn semantic_html5() {
+    let handler = || DefaultHtmlHandler::default().with_semantic_html5();
+
+    assert_eq!(to_html("", handler()), "<article></article>");
+    // no header without a preamble, and sections are closed at every level
+    assert_eq!(
+        to_html("* Été\n*** 日本\n* C\ntext\n", handler()),
+        "<article><section><h1>Été</h1><section><h3>日本</h3></section></section>\
+         <section><h1>C</h1><p>text</p></section></article>"
+    );
+}
+
+#[test]
+fn aria() {
+    assert_eq!(
+        to_html("", DefaultHtmlHandler::default().with_aria()),
+        "<main></main>"
+    );
+    assert_eq!(
+        to_html(
+            "1. one\n\n| a |\n\n#+BEGIN_SRC\nx\n#+END_SRC\n",
+            DefaultHtmlHandler::default().with_aria()
+        ),
+        "<main><section><ol role=\"list\"><li><p>one</p></li></ol>\
+         <table role=\"table\"><tbody><tr><td>a</td></tr></tbody></table>\
+         <pre class=\"example\" role=\"code\">x\n</pre></section></main>"
+    );
+    // drawers are only labeled with semantic html
+    assert_eq!(
+        to_html(
+            "* h\n:MY-NOTES:\ntext\n:END:\n",
+            DefaultHtmlHandler::default()
+                .with_aria()
+                .with_semantic_html5()
+        ),
+        "<article><section><h1 role=\"heading\" aria-level=\"1\">h</h1>\
+         <aside aria-label=\"MY-NOTES\"><p>text</p></aside></section></article>"
+    );
+}
+
+#[test]
+fn element_ids() {
+    let handler = || DefaultHtmlHandler::default().with_element_ids();
+
+    assert_eq!(to_html("", handler()), "<main></main>");
+    // names are escaped, empty names are ignored, and names before other
+    // elements are dropped
+    assert_eq!(
+        to_html(
+            "#+NAME: \"été\"\n| a |\n\n#+NAME:\n: x\n\n#+NAME: lost\ntext\n\n| b |\n* h\n",
+            handler()
+        ),
+        "<main><section id=\"section-1\">\
+         <table id=\"&quot;été&quot;\"><tbody><tr><td>a</td></tr></tbody></table>\
+         <pre id=\"pre-1\" class=\"example\">: x\n</pre><p>text</p>\
+         <table id=\"table-1\"><tbody><tr><td>b</td></tr></tbody></table></section>\
+         <h1>h</h1></main>"
+    );
+}
+
+#[test]
+fn duplicate_element_ids() {
+    assert_eq!(
+        to_html(
+            "#+NAME: t\n| a |\n\n#+NAME: t\n| b |\n\n#+NAME: table-1\n| c |\n\n| d |\n\n#+NAME: t-2\n| e |\n",
+            DefaultHtmlHandler::default().with_element_ids()
+        ),
+        "<main><section id=\"section-1\">\
+         <table id=\"t\"><tbody><tr><td>a</td></tr></tbody></table>\
+         <table id=\"t-2\"><tbody><tr><td>b</td></tr></tbody></table>\
+         <table id=\"table-1\"><tbody><tr><td>c</td></tr></tbody></table>\
+         <table id=\"table-2\"><tbody><tr><td>d</td></tr></tbody></table>\
+         <table id=\"t-2-2\"><tbody><tr><td>e</td></tr></tbody></table>\
+         </section></main>"
+    );
+}
+
+#[test]
+fn microdata() {
+    let handler = || DefaultHtmlHandler::default().with_microdata();
+
+    // headlines without article properties are not marked up
+    assert_eq!(
+        to_html("* a\n:PROPERTIES:\n:ID: 1\n:END:\n", handler()),
+        "<main><h1>a</h1></main>"
+    );
+    assert_eq!(
+        to_html(
+            "* Été\n:PROPERTIES:\n:url: https://example.com/?a=1&b=2\n:Description: \"日本\"\n:END:\n",
+            handler()
+        ),
+        "<main><div itemscope itemtype=\"https://schema.org/Article\">\
+         <h1 itemprop=\"headline\">Été</h1>\
+         <link itemprop=\"url\" href=\"https://example.com/?a=1&amp;b=2\">\
+         <meta itemprop=\"description\" content=\"&quot;日本&quot;\"></div></main>"
+    );
+}
+
+#[test]
+fn microdata_scope() {
+    // sections and sub-headlines are inside the article
+    assert_eq!(
+        to_html(
+            "* a\n:PROPERTIES:\n:DATE: [2024-01-15 Mon 09:05]\n:END:\nbody\n** b\n* c\n",
+            DefaultHtmlHandler::default()
+                .with_microdata()
+                .with_semantic_html5()
+        ),
+        "<article><section><div itemscope itemtype=\"https://schema.org/Article\">\
+         <h1 itemprop=\"headline\">a</h1>\
+         <meta itemprop=\"datePublished\" content=\"2024-01-15T09:05\">\
+         <p>body</p><section><h2>b</h2></section></div></section>\
+         <section><h1>c</h1></section></article>"
+    );
+
+    assert_eq!(iso_date("2024-01-15"), Some("2024-01-15".into()));
+    assert_eq!(
+        iso_date("<2024-01-15 Mon>--<2024-01-16 Tue>"),
+        Some("2024-01-15".into())
+    );
+    assert_eq!(iso_date("2024-13-01"), None);
+    assert_eq!(iso_date("January 2024"), None);
+    assert_eq!(iso_date("<%%(diary-float t 4 2)>"), None);
+    assert_eq!(iso_date("2024-01-１5"), None);
+    assert_eq!(
+        to_html(
+            "* a\n:PROPERTIES:\n:DATE: soon\n:END:\n",
+            DefaultHtmlHandler::default().with_microdata()
+        ),
+        "<main><div itemscope itemtype=\"https://schema.org/Article\">\
+         <h1 itemprop=\"headline\">a</h1></div></main>"
+    );
+}
+
 #[cfg(feature = "syntect")]
 mod syntect_handler {
     use super::*;
@@ -270,7 +1044,7 @@ mod syntect_handler {
     /// use orgize::Org;
     /// use orgize::export::{DefaultHtmlHandler, SyntectHtmlHandler};
     ///
-    /// let mut handler = SyntectHtmlHandler::new(DefaultHtmlHandler);
+    /// let mut handler = SyntectHtmlHandler::new(DefaultHtmlHandler::default());
     /// let org = Org::parse("src_rust{println!(\"Hello\")}");
     ///
     /// let mut vec = vec![];
@@ -295,7 +1069,7 @@ mod syntect_handler {
     ///     },
     ///     // specify theme
     ///     theme: String::from("Solarized (dark)"),
-    ///     inner: DefaultHtmlHandler,
+    ///     inner: DefaultHtmlHandler::default(),
     ///     ..Default::default()
     /// };
     ///
@@ -0,0 +1,96 @@
+use std::path::{Path, PathBuf};
+
+use crate::elements::{Element, Link};
+use crate::export::{html::parse_attr_html, is_image};
+use crate::Org;
+
+/// Image linked by a document
+#[derive(Debug, Clone)]
+pub struct ImageRef<'a> {
+    /// Link to the image
+    pub link: Link<'a>,
+    /// Path of the image file, resolved against the base directory
+    pub absolute_path: PathBuf,
+    /// Alternate text, from a preceding `#+ATTR_HTML:` keyword
+    pub alt: Option<String>,
+    /// Width, from a preceding `#+ATTR_HTML:` keyword
+    pub width: Option<u32>,
+    /// Height, from a preceding `#+ATTR_HTML:` keyword
+    pub height: Option<u32>,
+}
+
+impl<'a> Org<'a> {
+    /// Returns all images linked by this document, in document order.
+    ///
+    /// Only links to local files with an image extension are collected.
+    /// Relative paths are resolved against `base`.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    /// use std::path::Path;
+    ///
+    /// let org = Org::parse(
+    ///     "#+ATTR_HTML: :width 640 :alt \"A cat\"\n\
+    ///      [[file:images/cat.png]]\n\n\
+    ///      [[https://example.com/dog.png]] [[notes.org]] [[/tmp/bird.jpg]]\n",
+    /// );
+    ///
+    /// let images = org.collect_images(Path::new("/home/me/notes"));
+    /// assert_eq!(images.len(), 2);
+    /// assert_eq!(images[0].absolute_path, Path::new("/home/me/notes/images/cat.png"));
+    /// assert_eq!(images[0].alt.as_deref(), Some("A cat"));
+    /// assert_eq!(images[0].width, Some(640));
+    /// assert_eq!(images[1].absolute_path, Path::new("/tmp/bird.jpg"));
+    /// assert_eq!(images[1].width, None);
+    /// ```
+    pub fn collect_images(&self, base: &Path) -> Vec<ImageRef<'a>> {
+        let mut images = Vec::new();
+
+        for node in self.root.descendants(&self.arena) {
+            let link = match &self[node] {
+                Element::Link(link) => link,
+                _ => continue,
+            };
+            let path = match link.path.strip_prefix("file:") {
+                Some(path) => path,
+                None if link.path.contains(':') => continue,
+                None => &link.path,
+            };
+            if !is_image(path) {
+                continue;
+            }
+
+            // attributes from `#+ATTR_HTML:` keywords preceding the paragraph
+            let mut attrs = Vec::new();
+            if let Some(paragraph) = self.arena[node].parent() {
+                for sibling in paragraph.preceding_siblings(&self.arena).skip(1) {
+                    match &self[sibling] {
+                        Element::Keyword(keyword)
+                            if keyword.key.eq_ignore_ascii_case("ATTR_HTML") =>
+                        {
+                            attrs.extend(parse_attr_html(&keyword.value));
+                        }
+                        Element::Keyword(_) => (),
+                        _ => break,
+                    }
+                }
+            }
+            let attr = |key: &str| {
+                attrs
+                    .iter()
+                    .find(|(k, _)| k == key)
+                    .map(|(_, value)| value.clone())
+            };
+
+            images.push(ImageRef {
+                link: link.clone(),
+                absolute_path: base.join(path),
+                alt: attr("alt"),
+                width: attr("width").and_then(|width| width.parse().ok()),
+                height: attr("height").and_then(|height| height.parse().ok()),
+            });
+        }
+
+        images
+    }
+}
@@ -0,0 +1,88 @@
+use serde_json::{json, Map, Value};
+
+use crate::Org;
+
+impl Org<'_> {
+    /// Returns schema.org metadata of this document as a JSON-LD `Article`.
+    ///
+    /// `headline`, `author` and `datePublished` are taken from the `#+TITLE`,
+    /// `#+AUTHOR` and `#+DATE` keywords, and `keywords` from `#+FILETAGS`,
+    /// which can be written as `:a:b:` or `a b`. Missing keywords are
+    /// omitted.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    /// use serde_json::json;
+    ///
+    /// let org = Org::parse(
+    ///     "#+TITLE: Notes\n#+AUTHOR: Jane\n#+DATE: 2021-03-04\n#+FILETAGS: :rust:org:\n",
+    /// );
+    ///
+    /// assert_eq!(
+    ///     org.to_json_ld(),
+    ///     json!({
+    ///         "@context": "https://schema.org",
+    ///         "@type": "Article",
+    ///         "headline": "Notes",
+    ///         "author": { "@type": "Person", "name": "Jane" },
+    ///         "datePublished": "2021-03-04",
+    ///         "keywords": ["rust", "org"]
+    ///     })
+    /// );
+    /// ```
+    pub fn to_json_ld(&self) -> Value {
+        let mut metadata = Map::new();
+        metadata.insert("@context".into(), json!("https://schema.org"));
+        metadata.insert("@type".into(), json!("Article"));
+
+        let keyword = |key| {
+            self.keyword_value(key)
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+        };
+        if let Some(title) = keyword("TITLE") {
+            metadata.insert("headline".into(), json!(title));
+        }
+        if let Some(author) = keyword("AUTHOR") {
+            metadata.insert(
+                "author".into(),
+                json!({ "@type": "Person", "name": author }),
+            );
+        }
+        if let Some(date) = keyword("DATE") {
+            metadata.insert("datePublished".into(), json!(date));
+        }
+        if let Some(tags) = keyword("FILETAGS") {
+            let tags: Vec<_> = tags
+                .split(|c: char| c == ':' || c.is_whitespace())
+                .filter(|tag| !tag.is_empty())
+                .collect();
+            metadata.insert("keywords".into(), json!(tags));
+        }
+
+        Value::Object(metadata)
+    }
+
+    /// Returns a `<script type="application/ld+json">` tag containing
+    /// [`to_json_ld`], to be placed in the `<head>` of the page embedding the
+    /// html export of this document.
+    ///
+    /// [`to_json_ld`]: Org::to_json_ld
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse("#+TITLE: </script>\n");
+    ///
+    /// assert_eq!(
+    ///     org.to_json_ld_script(),
+    ///     "<script type=\"application/ld+json\">{\"@context\":\"https://schema.org\",\
+    ///      \"@type\":\"Article\",\"headline\":\"<\\/script>\"}</script>"
+    /// );
+    /// ```
+    pub fn to_json_ld_script(&self) -> String {
+        // `</` would end the script element early
+        let json = self.to_json_ld().to_string().replace("</", "<\\/");
+        format!("<script type=\"application/ld+json\">{}</script>", json)
+    }
+}
@@ -0,0 +1,367 @@
+use indextree::{NodeEdge, NodeId};
+
+use crate::elements::{Element, Table, TableRow};
+use crate::export::{
+    fixed_width_contents, is_image, table_columns, table_rows, write_timestamp, DefaultOrgHandler,
+    OrgHandler,
+};
+use crate::Org;
+
+/// Converts `Org` struct into a standalone LaTeX document.
+pub(crate) fn to_latex(org: &Org) -> String {
+    let mut output = String::from(
+        "\\documentclass{article}\n\
+         \\usepackage{graphicx}\n\
+         \\usepackage{hyperref}\n\
+         \\usepackage[normalem]{ulem}\n",
+    );
+    if org
+        .root
+        .descendants(&org.arena)
+        .any(|node| is_tikz(&org[node]))
+    {
+        output.push_str("\\usepackage{tikz}\n");
+    }
+
+    for keyword in org.keywords() {
+        match &*keyword.key.to_ascii_uppercase() {
+            "TITLE" => push_command(&mut output, "title", &keyword.value),
+            "AUTHOR" => push_command(&mut output, "author", &keyword.value),
+            "DATE" => push_command(&mut output, "date", &keyword.value),
+            _ => (),
+        }
+    }
+
+    output.push_str("\\begin{document}\n\n");
+    if org
+        .keywords()
+        .any(|keyword| keyword.key.eq_ignore_ascii_case("TITLE"))
+    {
+        output.push_str("\\maketitle\n\n");
+    }
+    write_children(org, org.root, &mut output);
+    output.push_str("\\end{document}\n");
+    output
+}
+
+fn push_command(output: &mut String, name: &str, value: &str) {
+    output.push('\\');
+    output.push_str(name);
+    output.push('{');
+    output.push_str(&escape(value.trim()));
+    output.push_str("}\n");
+}
+
+fn write_children(org: &Org, node: NodeId, output: &mut String) {
+    for child in node.children(&org.arena) {
+        write_node(org, child, output);
+    }
+}
+
+fn write_environment(org: &Org, node: NodeId, output: &mut String, name: &str) {
+    output.push_str("\\begin{");
+    output.push_str(name);
+    output.push_str("}\n");
+    write_children(org, node, output);
+    output.truncate(output.trim_end().len());
+    output.push_str("\n\\end{");
+    output.push_str(name);
+    output.push_str("}\n\n");
+}
+
+fn write_verbatim(output: &mut String, contents: &str) {
+    output.push_str("\\begin{verbatim}\n");
+    output.push_str(contents);
+    if !contents.ends_with('\n') {
+        output.push('\n');
+    }
+    output.push_str("\\end{verbatim}\n\n");
+}
+
+fn write_node(org: &Org, node: NodeId, output: &mut String) {
+    match &org[node] {
+        element if is_tikz(element) => write_tikz(org, node, output),
+        Element::Document { .. }
+        | Element::Section
+        | Element::Headline { .. }
+        | Element::DynBlock(_)
+        | Element::SpecialBlock(_)
+        | Element::ListItem(_)
+        | Element::RadioTarget => write_children(org, node, output),
+        Element::Title(title) => {
+            let command = match title.level {
+                1 => "section",
+                2 => "subsection",
+                3 => "subsubsection",
+                4 => "paragraph",
+                _ => "subparagraph",
+            };
+            output.push('\\');
+            output.push_str(command);
+            output.push('{');
+            write_children(org, node, output);
+            output.push_str("}\n\n");
+        }
+        Element::Paragraph { .. } => {
+            write_children(org, node, output);
+            output.push_str("\n\n");
+        }
+        Element::QuoteBlock(_) => write_environment(org, node, output, "quote"),
+        Element::CenterBlock(_) => write_environment(org, node, output, "center"),
+        Element::VerseBlock(_) => write_environment(org, node, output, "verse"),
+        Element::List(list) => {
+            let name = if list.ordered { "enumerate" } else { "itemize" };
+            output.push_str("\\begin{");
+            output.push_str(name);
+            output.push_str("}\n");
+            for item in node.children(&org.arena) {
+                output.push_str("\\item ");
+                write_children(org, item, output);
+                output.truncate(output.trim_end().len());
+                output.push('\n');
+            }
+            output.push_str("\\end{");
+            output.push_str(name);
+            output.push_str("}\n\n");
+        }
+        Element::Bold => write_command(org, node, output, "textbf"),
+        Element::Italic => write_command(org, node, output, "emph"),
+        Element::Underline => write_command(org, node, output, "uline"),
+        Element::Strike => write_command(org, node, output, "sout"),
+        Element::Code { value } | Element::Verbatim { value } => {
+            output.push_str("\\texttt{");
+            output.push_str(&escape(value));
+            output.push('}');
+        }
+        Element::Text { value } => output.push_str(&escape(value)),
+        Element::Link(link) => {
+            if is_image(&link.path) {
+                output.push_str("\\includegraphics[width=\\linewidth]{");
+                output.push_str(link.path.trim_start_matches("file:"));
+                output.push('}');
+            } else if let Some(desc) = &link.desc {
+                output.push_str("\\href{");
+                output.push_str(&link.path.replace('%', "\\%").replace('#', "\\#"));
+                output.push_str("}{");
+                output.push_str(&escape(desc));
+                output.push('}');
+            } else {
+                output.push_str("\\url{");
+                output.push_str(&link.path.replace('%', "\\%").replace('#', "\\#"));
+                output.push('}');
+            }
+        }
+        Element::Timestamp(timestamp) => {
+            let mut writer = Vec::new();
+            if write_timestamp(&mut writer, timestamp).is_ok() {
+                output.push_str(&escape(&String::from_utf8_lossy(&writer)));
+            }
+        }
+        Element::Cookie(cookie) => output.push_str(&escape(&cookie.value)),
+        Element::InlineSrc(inline_src) => {
+            output.push_str("\\texttt{");
+            output.push_str(&escape(&inline_src.body));
+            output.push('}');
+        }
+        Element::Snippet(snippet) => {
+            if snippet.name.eq_ignore_ascii_case("latex") {
+                output.push_str(&snippet.value);
+            }
+        }
+        Element::ExportBlock(block) => {
+            if block.data.eq_ignore_ascii_case("latex") {
+                output.push_str(&block.contents);
+                output.push('\n');
+            }
+        }
+        Element::FnRef(fn_ref) => {
+            if let Some(definition) = &fn_ref.definition {
+                output.push_str("\\footnote{");
+                output.push_str(&escape(definition));
+                output.push('}');
+            }
+        }
+        Element::SourceBlock(block) => write_verbatim(output, &block.contents),
+        Element::ExampleBlock(block) => write_verbatim(output, &block.contents),
+        Element::FixedWidth(fixed_width) => {
+            write_verbatim(output, &fixed_width_contents(&fixed_width.value))
+        }
+        Element::Table(Table::Org { .. }) => write_table(org, node, output),
+        Element::Table(Table::TableEl { value, .. }) => write_verbatim(output, value),
+        Element::Rule(_) => output.push_str("\\noindent\\rule{\\linewidth}{0.4pt}\n\n"),
+        Element::BabelCall(_)
+        | Element::Clock(_)
+        | Element::Comment(_)
+        | Element::CommentBlock(_)
+        | Element::Drawer(_)
+        | Element::FnDef(_)
+        | Element::InlineCall(_)
+        | Element::Keyword(_)
+        | Element::Macros(_)
+        | Element::Target(_)
+        | Element::TableRow(_)
+        | Element::TableCell(_) => (),
+    }
+}
+
+fn is_tikz(element: &Element) -> bool {
+    matches!(element, Element::SpecialBlock(block) if block.name.eq_ignore_ascii_case("tikz"))
+}
+
+// writes the contents of a `#+BEGIN_tikz` block verbatim, with the options
+// of a preceding `#+ATTR_LATEX: :options ...` keyword
+fn write_tikz(org: &Org, node: NodeId, output: &mut String) {
+    let options = node
+        .preceding_siblings(&org.arena)
+        .skip(1)
+        .map_while(|sibling| match &org[sibling] {
+            Element::Keyword(keyword) => Some(keyword),
+            _ => None,
+        })
+        .filter(|keyword| keyword.key.eq_ignore_ascii_case("ATTR_LATEX"))
+        .find_map(|keyword| attr_latex_options(&keyword.value));
+
+    output.push_str("\\begin{tikzpicture}");
+    if let Some(options) = options {
+        output.push('[');
+        output.push_str(options);
+        output.push(']');
+    }
+    output.push('\n');
+
+    let mut handler = DefaultOrgHandler;
+    let mut writer = Vec::new();
+    for child in node.children(&org.arena) {
+        for edge in child.traverse(&org.arena) {
+            let _ = match edge {
+                NodeEdge::Start(node) => handler.start(&mut writer, &org[node]),
+                NodeEdge::End(node) => handler.end(&mut writer, &org[node]),
+            };
+        }
+    }
+    let contents = String::from_utf8_lossy(&writer);
+    output.push_str(contents.trim_end());
+    output.push_str("\n\\end{tikzpicture}\n\n");
+}
+
+// extracts the value of `:options` from `:width 5cm :options "scale=0.5"`
+fn attr_latex_options(value: &str) -> Option<&str> {
+    let value = &value[value.find(":options")? + ":options".len()..];
+    let value = value.trim_start();
+    let options = match value.strip_prefix('"') {
+        Some(quoted) => &quoted[..quoted.find('"').unwrap_or(quoted.len())],
+        None => &value[..value.find(" :").unwrap_or(value.len())],
+    };
+    Some(options.trim()).filter(|options| !options.is_empty())
+}
+
+fn write_command(org: &Org, node: NodeId, output: &mut String, name: &str) {
+    output.push('\\');
+    output.push_str(name);
+    output.push('{');
+    write_children(org, node, output);
+    output.push('}');
+}
+
+fn write_table(org: &Org, node: NodeId, output: &mut String) {
+    let columns = table_columns(org, node);
+
+    output.push_str("\\begin{tabular}{");
+    for _ in 0..columns {
+        output.push('l');
+    }
+    output.push_str("}\n");
+    for row in table_rows(org, node) {
+        for (index, cell) in row.children(&org.arena).enumerate() {
+            if index > 0 {
+                output.push_str(" & ");
+            }
+            let mut contents = String::new();
+            write_children(org, cell, &mut contents);
+            output.push_str(contents.trim());
+        }
+        output.push_str(" \\\\\n");
+        if let Element::TableRow(TableRow::Header) = org[row] {
+            output.push_str("\\hline\n");
+        }
+    }
+    output.push_str("\\end{tabular}\n\n");
+}
+
+fn escape(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => output.push_str("\\textbackslash{}"),
+            '~' => output.push_str("\\textasciitilde{}"),
+            '^' => output.push_str("\\textasciicircum{}"),
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                output.push('\\');
+                output.push(c);
+            }
+            _ => output.push(c),
+        }
+    }
+    output
+}
+
+#[test]
+fn convert() {
+    let org = Org::parse(
+        r#"#+TITLE: Notes
+* Intro
+Some *bold* and [[https://example.com][link]], 50% off.
+
+- one
+- two
+
+[[./image.png]]
+
+| a | b |
+|---+---|
+| 1 | 2 |
+"#,
+    );
+
+    assert_eq!(
+        to_latex(&org),
+        "\\documentclass{article}\n\
+         \\usepackage{graphicx}\n\
+         \\usepackage{hyperref}\n\
+         \\usepackage[normalem]{ulem}\n\
+         \\title{Notes}\n\
+         \\begin{document}\n\n\
+         \\maketitle\n\n\
+         \\section{Intro}\n\n\
+         Some \\textbf{bold} and \\href{https://example.com}{link}, 50\\% off.\n\n\
+         \\begin{itemize}\n\
+         \\item one\n\
+         \\item two\n\
+         \\end{itemize}\n\n\
+         \\includegraphics[width=\\linewidth]{./image.png}\n\n\
+         \\begin{tabular}{ll}\n\
+         a & b \\\\\n\
+         \\hline\n\
+         1 & 2 \\\\\n\
+         \\end{tabular}\n\n\
+         \\end{document}\n"
+    );
+}
+
+#[test]
+fn tikz() {
+    let org = Org::parse(
+        "#+ATTR_LATEX: :options \"scale=0.5\"\n\
+         #+BEGIN_tikz\n\\draw (0,0) -- (1,1);\n#+END_tikz\n",
+    );
+    let latex = to_latex(&org);
+
+    assert!(latex.contains("\\usepackage{tikz}\n"));
+    assert!(latex
+        .contains("\\begin{tikzpicture}[scale=0.5]\n\\draw (0,0) -- (1,1);\n\\end{tikzpicture}\n"));
+    assert_eq!(
+        attr_latex_options(":width 5cm :options a=b :float t"),
+        Some("a=b")
+    );
+    assert_eq!(attr_latex_options(":width 5cm"), None);
+}
@@ -0,0 +1,319 @@
+use std::collections::HashMap;
+
+use indextree::NodeId;
+
+use crate::elements::{Element, Link, Table};
+use crate::export::{fixed_width_contents, is_image, table_rows, write_timestamp};
+use crate::Org;
+
+/// Markdown flavor options
+#[derive(Default)]
+pub(crate) struct MarkdownOptions {
+    /// Titles of headlines by their `ID` property, `[[id:...]]` links to
+    /// these headlines are written as `[[title]]` wikilinks
+    pub wikilinks: HashMap<String, String>,
+    /// Appends headline tags to titles as `#tag`
+    pub inline_tags: bool,
+    /// Subtracted from headline levels
+    pub level_offset: usize,
+}
+
+/// Converts the descendants of `node` into markdown text.
+pub(crate) fn to_markdown(org: &Org, node: NodeId) -> String {
+    to_markdown_with(org, node, &MarkdownOptions::default())
+}
+
+/// Converts the descendants of `node` into markdown text with `options`.
+pub(crate) fn to_markdown_with(org: &Org, node: NodeId, options: &MarkdownOptions) -> String {
+    let mut output = String::new();
+    write_children(org, options, node, &mut output);
+    output.truncate(output.trim_end().len());
+    output
+}
+
+fn write_children(org: &Org, options: &MarkdownOptions, node: NodeId, output: &mut String) {
+    for child in node.children(&org.arena) {
+        write_node(org, options, child, output);
+    }
+}
+
+fn write_node(org: &Org, options: &MarkdownOptions, node: NodeId, output: &mut String) {
+    match &org[node] {
+        Element::Document { .. }
+        | Element::Section
+        | Element::Headline { .. }
+        | Element::DynBlock(_)
+        | Element::CenterBlock(_)
+        | Element::SpecialBlock(_)
+        | Element::Underline
+        | Element::RadioTarget => write_children(org, options, node, output),
+        Element::Title(title) => {
+            for _ in 0..title.level.saturating_sub(options.level_offset).clamp(1, 6) {
+                output.push('#');
+            }
+            output.push(' ');
+            write_children(org, options, node, output);
+            if options.inline_tags {
+                for tag in &title.tags {
+                    output.push_str(" #");
+                    output.push_str(tag);
+                }
+            }
+            output.push_str("\n\n");
+        }
+        Element::Paragraph { .. } => {
+            write_children(org, options, node, output);
+            output.push_str("\n\n");
+        }
+        Element::VerseBlock(_) => {
+            let mut contents = String::new();
+            write_children(org, options, node, &mut contents);
+            for line in contents.trim().lines() {
+                output.push_str(line);
+                output.push_str("  \n");
+            }
+            output.push('\n');
+        }
+        Element::QuoteBlock(_) => {
+            let mut contents = String::new();
+            write_children(org, options, node, &mut contents);
+            for line in contents.trim().lines() {
+                output.push('>');
+                if !line.is_empty() {
+                    output.push(' ');
+                    output.push_str(line);
+                }
+                output.push('\n');
+            }
+            output.push('\n');
+        }
+        Element::List(list) => {
+            for (index, item) in node.children(&org.arena).enumerate() {
+                let bullet = if list.ordered {
+                    format!("{}. ", index + 1)
+                } else {
+                    String::from("- ")
+                };
+                let mut contents = String::new();
+                write_children(org, options, item, &mut contents);
+                output.push_str(&bullet);
+                // blank lines are dropped to keep the list tight
+                for (i, line) in contents.lines().filter(|l| !l.is_empty()).enumerate() {
+                    if i > 0 {
+                        for _ in 0..bullet.len() {
+                            output.push(' ');
+                        }
+                    }
+                    output.push_str(line);
+                    output.push('\n');
+                }
+                if contents.trim().is_empty() {
+                    output.push('\n');
+                }
+            }
+            output.push('\n');
+        }
+        Element::ListItem(_) => write_children(org, options, node, output),
+        Element::Bold => write_emphasis(org, options, node, output, "**"),
+        Element::Italic => write_emphasis(org, options, node, output, "*"),
+        Element::Strike => write_emphasis(org, options, node, output, "~~"),
+        Element::Code { value } | Element::Verbatim { value } => {
+            output.push('`');
+            output.push_str(value);
+            output.push('`');
+        }
+        Element::Text { value } => output.push_str(value),
+        Element::Link(link) if is_image(&link.path) => {
+            output.push_str("![](");
+            output.push_str(link.path.trim_start_matches("file:"));
+            output.push(')');
+        }
+        Element::Link(link) if wikilink(options, link).is_some() => {
+            let title = wikilink(options, link).unwrap_or_default();
+            output.push_str("[[");
+            output.push_str(title);
+            if let Some(desc) = link.desc.as_ref().filter(|desc| *desc != title) {
+                output.push('|');
+                output.push_str(desc);
+            }
+            output.push_str("]]");
+        }
+        Element::Link(link) => match &link.desc {
+            Some(desc) => {
+                output.push('[');
+                output.push_str(desc);
+                output.push_str("](");
+                output.push_str(&link.path);
+                output.push(')');
+            }
+            None if link.path.contains("://") => {
+                output.push('<');
+                output.push_str(&link.path);
+                output.push('>');
+            }
+            None => output.push_str(&link.path),
+        },
+        Element::Timestamp(timestamp) => {
+            let mut writer = Vec::new();
+            if write_timestamp(&mut writer, timestamp).is_ok() {
+                output.push_str(&String::from_utf8_lossy(&writer));
+            }
+        }
+        Element::Cookie(cookie) => output.push_str(&cookie.value),
+        Element::InlineSrc(inline_src) => {
+            output.push('`');
+            output.push_str(&inline_src.body);
+            output.push('`');
+        }
+        Element::Snippet(snippet) => {
+            if snippet.name.eq_ignore_ascii_case("md")
+                || snippet.name.eq_ignore_ascii_case("markdown")
+                || snippet.name.eq_ignore_ascii_case("html")
+            {
+                output.push_str(&snippet.value);
+            }
+        }
+        Element::FnRef(fn_ref) => {
+            output.push_str("[^");
+            output.push_str(&fn_ref.label);
+            output.push(']');
+        }
+        Element::FnDef(fn_def) => {
+            output.push_str("[^");
+            output.push_str(&fn_def.label);
+            output.push_str("]: ");
+            write_children(org, options, node, output);
+        }
+        Element::SourceBlock(block) => write_code_block(output, &block.language, &block.contents),
+        Element::ExampleBlock(block) => write_code_block(output, "", &block.contents),
+        Element::FixedWidth(fixed_width) => {
+            write_code_block(output, "", &fixed_width_contents(&fixed_width.value))
+        }
+        Element::ExportBlock(block) => {
+            if block.data.eq_ignore_ascii_case("md")
+                || block.data.eq_ignore_ascii_case("markdown")
+                || block.data.eq_ignore_ascii_case("html")
+            {
+                output.push_str(&block.contents);
+                output.push('\n');
+            }
+        }
+        Element::Table(Table::Org { .. }) => write_table(org, options, node, output),
+        Element::Table(Table::TableEl { value, .. }) => write_code_block(output, "", value),
+        Element::Rule(_) => output.push_str("---\n\n"),
+        Element::BabelCall(_)
+        | Element::Clock(_)
+        | Element::Comment(_)
+        | Element::CommentBlock(_)
+        | Element::Drawer(_)
+        | Element::InlineCall(_)
+        | Element::Keyword(_)
+        | Element::Macros(_)
+        | Element::Target(_)
+        | Element::TableRow(_)
+        | Element::TableCell(_) => (),
+    }
+}
+
+// returns the wikilink title of an `id:` link
+fn wikilink<'a>(options: &'a MarkdownOptions, link: &Link) -> Option<&'a str> {
+    let id = link.path.strip_prefix("id:")?.trim();
+    options.wikilinks.get(id).map(String::as_str)
+}
+
+fn write_emphasis(
+    org: &Org,
+    options: &MarkdownOptions,
+    node: NodeId,
+    output: &mut String,
+    marker: &str,
+) {
+    output.push_str(marker);
+    write_children(org, options, node, output);
+    output.push_str(marker);
+}
+
+fn write_code_block(output: &mut String, language: &str, contents: &str) {
+    output.push_str("```");
+    output.push_str(language);
+    output.push('\n');
+    output.push_str(contents);
+    if !contents.ends_with('\n') {
+        output.push('\n');
+    }
+    output.push_str("```\n\n");
+}
+
+// markdown tables always have one header row, so the first row is used as header
+fn write_table(org: &Org, options: &MarkdownOptions, node: NodeId, output: &mut String) {
+    let rows: Vec<Vec<String>> = table_rows(org, node)
+        .map(|row| {
+            row.children(&org.arena)
+                .map(|cell| {
+                    let mut contents = String::new();
+                    write_children(org, options, cell, &mut contents);
+                    contents.trim().replace('|', "\\|")
+                })
+                .collect()
+        })
+        .collect();
+
+    for (index, row) in rows.iter().enumerate() {
+        output.push('|');
+        for cell in row {
+            output.push(' ');
+            output.push_str(cell);
+            output.push_str(" |");
+        }
+        output.push('\n');
+        if index == 0 {
+            output.push('|');
+            for _ in row {
+                output.push_str(" --- |");
+            }
+            output.push('\n');
+        }
+    }
+    output.push('\n');
+}
+
+#[test]
+fn convert() {
+    let org = Org::parse(
+        r#"* Title
+Some *bold* and [[https://example.com][link]].
+
+1. one
+   - two
+2. three
+
+#+BEGIN_SRC rust
+fn main() {}
+#+END_SRC
+
+#+BEGIN_QUOTE
+quote
+#+END_QUOTE
+
+| a | b |
+|---+---|
+| 1 | 2 |
+"#,
+    );
+
+    assert_eq!(
+        to_markdown(&org, org.root),
+        "# Title\n\n\
+         Some **bold** and [link](https://example.com).\n\n\
+         1. one\n   \
+         - two\n\
+         2. three\n\n\
+         ```rust\n\
+         fn main() {}\n\
+         ```\n\n\
+         > quote\n\n\
+         | a | b |\n\
+         | --- | --- |\n\
+         | 1 | 2 |"
+    );
+}
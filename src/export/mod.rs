@@ -1,16 +1,64 @@
 //! Export `Org` struct to various formats.
 
+mod anki;
+mod ansi;
+mod csv;
+mod directory;
+mod epub;
+mod format;
+mod fountain;
+mod gemtext;
+#[cfg(feature = "serde_json")]
+mod github;
+mod gopher;
 mod html;
+mod image;
+#[cfg(feature = "serde_json")]
+mod json_ld;
+mod latex;
+mod markdown;
+#[cfg(feature = "serde_json")]
+mod notion;
+mod obsidian;
+mod open_graph;
+mod opml;
 mod org;
+mod outline;
+mod pretty;
+mod reveal;
+#[cfg(feature = "serde_json")]
+mod roam;
+mod sitemap;
+mod sql;
+#[cfg(feature = "serde_json")]
+mod thebrain;
+mod troff;
+mod typst;
+#[cfg(feature = "xlsx")]
+mod xlsx;
 
+pub use anki::{AnkiCard, AnkiConfig};
+pub use ansi::{ColorSupport, TerminalConfig};
+pub use csv::OutlineField;
+pub use directory::{ExportConfig, ExportFormat, ExportedFiles};
+pub use format::{FormatChange, FormatViolation, OrgFormatDiff};
 #[cfg(feature = "syntect")]
 pub use html::SyntectHtmlHandler;
 pub use html::{DefaultHtmlHandler, HtmlEscape, HtmlHandler};
+pub use image::ImageRef;
 pub use org::{DefaultOrgHandler, OrgHandler};
+pub use outline::OutlineNode;
+pub use pretty::OrgFormatter;
+pub use reveal::RevealJsConfig;
 
+pub(crate) use org::write_timestamp;
+
+use indextree::NodeId;
 use std::io::{Error, Write};
+use std::path::Path;
 
-use crate::elements::Datetime;
+use crate::elements::{Datetime, Element, TableRow};
+use crate::Org;
 
 pub(crate) fn write_datetime<W: Write>(
     mut w: W,
@@ -29,3 +77,84 @@ pub(crate) fn write_datetime<W: Write>(
     }
     write!(w, "{}", end)
 }
+
+/// Returns `true` if `path` links to an image file.
+pub(crate) fn is_image(path: &str) -> bool {
+    const IMAGE_EXTENSIONS: [&str; 7] = ["png", "jpg", "jpeg", "gif", "svg", "webp", "bmp"];
+
+    let extension = Path::new(path).extension().and_then(|ext| ext.to_str());
+    matches!(extension, Some(extension) if IMAGE_EXTENSIONS
+        .iter()
+        .any(|ext| ext.eq_ignore_ascii_case(extension)))
+}
+
+/// Writes the text of `node`'s descendants, without markup.
+pub(crate) fn write_plain_text(org: &Org, node: NodeId, output: &mut String) {
+    for child in node.children(&org.arena) {
+        match &org[child] {
+            Element::Text { value } | Element::Code { value } | Element::Verbatim { value } => {
+                output.push_str(value)
+            }
+            Element::Link(link) => output.push_str(link.desc.as_ref().unwrap_or(&link.path)),
+            Element::Cookie(cookie) => output.push_str(&cookie.value),
+            _ => write_plain_text(org, child, output),
+        }
+    }
+}
+
+/// Joins the lines of `text` into one line, collapsing whitespaces.
+pub(crate) fn join_lines(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Returns the contents of a fixed-width area, without the leading `: `
+/// of each line.
+pub(crate) fn fixed_width_contents(value: &str) -> String {
+    let lines: Vec<_> = value
+        .lines()
+        .map(|line| {
+            let line = line.trim_start();
+            line.strip_prefix(": ")
+                .unwrap_or_else(|| line.trim_start_matches(':'))
+        })
+        .collect();
+    lines.join("\n")
+}
+
+/// Returns the header and body rows of table `node`, skipping rules.
+pub(crate) fn table_rows<'a>(org: &'a Org, node: NodeId) -> impl Iterator<Item = NodeId> + 'a {
+    node.children(&org.arena).filter(move |row| {
+        matches!(
+            org[*row],
+            Element::TableRow(TableRow::Header) | Element::TableRow(TableRow::Body)
+        )
+    })
+}
+
+/// Returns the number of columns of table `node`, which is the number of
+/// cells in its longest row.
+pub(crate) fn table_columns(org: &Org, node: NodeId) -> usize {
+    table_rows(org, node)
+        .map(|row| row.children(&org.arena).count())
+        .max()
+        .unwrap_or_default()
+}
+
+/// Returns the widths of columns, measured with `width`, of rows where
+/// `None` stands for a rule.
+pub(crate) fn column_widths(
+    rows: &[Option<Vec<String>>],
+    width: impl Fn(&str) -> usize,
+) -> Vec<usize> {
+    let mut widths: Vec<usize> = Vec::new();
+    for row in rows.iter().flatten() {
+        for (index, cell) in row.iter().enumerate() {
+            let width = width(cell);
+            match widths.get_mut(index) {
+                Some(w) => *w = (*w).max(width),
+                None => widths.push(width),
+            }
+        }
+    }
+    widths
+}
@@ -0,0 +1,306 @@
+use indextree::NodeId;
+use serde_json::{json, Map, Value};
+
+use crate::elements::{Element, Table};
+use crate::export::{fixed_width_contents, table_columns, table_rows};
+use crate::Org;
+
+impl Org<'_> {
+    /// Exports this document as a json array of Notion blocks, which can be
+    /// used as `children` of Notion's append-block-children API.
+    ///
+    /// Headlines become `heading_1`, `heading_2` or `heading_3` blocks,
+    /// deeper headlines use `heading_3`. Paragraphs, quote blocks, source
+    /// and example blocks, tables and rules become `paragraph`, `quote`,
+    /// `code`, `table` and `divider` blocks. List items become
+    /// `bulleted_list_item` or `numbered_list_item` blocks, or `to_do` blocks
+    /// if they start with a checkbox, with nested lists as `children`. Bold,
+    /// italic, underline, strike-through and code texts are annotated.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    /// use serde_json::{json, Value};
+    ///
+    /// let org = Org::parse("* Plan\nBe *bold*.\n- [X] done\n");
+    /// let blocks: Value = serde_json::from_str(&org.to_notion_json()).unwrap();
+    ///
+    /// assert_eq!(
+    ///     blocks,
+    ///     json!([
+    ///         {
+    ///             "object": "block",
+    ///             "type": "heading_1",
+    ///             "heading_1": {
+    ///                 "rich_text": [{ "type": "text", "text": { "content": "Plan" } }]
+    ///             }
+    ///         },
+    ///         {
+    ///             "object": "block",
+    ///             "type": "paragraph",
+    ///             "paragraph": {
+    ///                 "rich_text": [
+    ///                     { "type": "text", "text": { "content": "Be " } },
+    ///                     {
+    ///                         "type": "text",
+    ///                         "text": { "content": "bold" },
+    ///                         "annotations": { "bold": true }
+    ///                     },
+    ///                     { "type": "text", "text": { "content": "." } }
+    ///                 ]
+    ///             }
+    ///         },
+    ///         {
+    ///             "object": "block",
+    ///             "type": "to_do",
+    ///             "to_do": {
+    ///                 "rich_text": [{ "type": "text", "text": { "content": "done" } }],
+    ///                 "checked": true
+    ///             }
+    ///         }
+    ///     ])
+    /// );
+    /// ```
+    pub fn to_notion_json(&self) -> String {
+        let mut blocks = Vec::new();
+        write_blocks(self, self.root, &mut blocks);
+        Value::Array(blocks).to_string()
+    }
+}
+
+fn write_blocks(org: &Org, node: NodeId, blocks: &mut Vec<Value>) {
+    for child in node.children(&org.arena) {
+        write_block(org, child, blocks);
+    }
+}
+
+fn write_block(org: &Org, node: NodeId, blocks: &mut Vec<Value>) {
+    match &org[node] {
+        Element::Document { .. }
+        | Element::Section
+        | Element::Headline { .. }
+        | Element::CenterBlock(_)
+        | Element::SpecialBlock(_)
+        | Element::DynBlock(_) => write_blocks(org, node, blocks),
+        Element::Title(title) => {
+            let kind = format!("heading_{}", title.level.min(3));
+            blocks.push(block(&kind, json!({ "rich_text": rich_text(org, node) })));
+        }
+        Element::Paragraph { .. } => {
+            blocks.push(block(
+                "paragraph",
+                json!({ "rich_text": rich_text(org, node) }),
+            ));
+        }
+        Element::QuoteBlock(_) | Element::VerseBlock(_) => {
+            let mut segments = Vec::new();
+            for child in node.children(&org.arena) {
+                if !segments.is_empty() {
+                    segments.push(text_segment("\n", Annotations::default()));
+                }
+                segments.extend(rich_text(org, child));
+            }
+            blocks.push(block("quote", json!({ "rich_text": segments })));
+        }
+        Element::SourceBlock(source) => {
+            let language: &str = if source.language.is_empty() {
+                "plain text"
+            } else {
+                &source.language
+            };
+            blocks.push(code(&source.contents, language));
+        }
+        Element::ExampleBlock(example) => blocks.push(code(&example.contents, "plain text")),
+        Element::FixedWidth(fixed_width) => blocks.push(code(
+            &fixed_width_contents(&fixed_width.value),
+            "plain text",
+        )),
+        Element::List(list) => {
+            for item in node.children(&org.arena) {
+                blocks.push(list_item(org, item, list.ordered));
+            }
+        }
+        Element::Table(Table::Org { has_header, .. }) => {
+            let rows: Vec<Value> = table_rows(org, node)
+                .map(|row| {
+                    let cells: Vec<_> = row
+                        .children(&org.arena)
+                        .map(|cell| Value::Array(rich_text(org, cell)))
+                        .collect();
+                    block("table_row", json!({ "cells": cells }))
+                })
+                .collect();
+            let width = table_columns(org, node);
+            blocks.push(block(
+                "table",
+                json!({
+                    "table_width": width,
+                    "has_column_header": has_header,
+                    "has_row_header": false,
+                    "children": rows,
+                }),
+            ));
+        }
+        Element::Table(Table::TableEl { value, .. }) => blocks.push(code(value, "plain text")),
+        Element::Rule(_) => blocks.push(block("divider", json!({}))),
+        _ => (),
+    }
+}
+
+fn block(kind: &str, content: Value) -> Value {
+    json!({ "object": "block", "type": kind, kind: content })
+}
+
+fn code(contents: &str, language: &str) -> Value {
+    block(
+        "code",
+        json!({
+            "rich_text": [text_segment(contents.trim_end(), Annotations::default())],
+            "language": language,
+        }),
+    )
+}
+
+fn list_item(org: &Org, item: NodeId, ordered: bool) -> Value {
+    let mut segments = Vec::new();
+    let mut children = Vec::new();
+    for child in item.children(&org.arena) {
+        match org[child] {
+            Element::Paragraph { .. } if children.is_empty() => {
+                if !segments.is_empty() {
+                    segments.push(text_segment("\n", Annotations::default()));
+                }
+                segments.extend(rich_text(org, child));
+            }
+            _ => write_block(org, child, &mut children),
+        }
+    }
+
+    // checkbox is kept in the text by the parser
+    let checkbox = segments
+        .first()
+        .and_then(|segment| segment["text"]["content"].as_str())
+        .and_then(|content| {
+            let checked = if content.starts_with("[ ]") {
+                false
+            } else if content.starts_with("[X]") || content.starts_with("[x]") {
+                true
+            } else {
+                return None;
+            };
+            Some((checked, content[3..].trim_start().to_string()))
+        });
+
+    let mut content = Map::new();
+    let kind = match checkbox {
+        Some((checked, rest)) => {
+            if rest.is_empty() {
+                segments.remove(0);
+            } else {
+                segments[0]["text"]["content"] = json!(rest);
+            }
+            content.insert("checked".into(), json!(checked));
+            "to_do"
+        }
+        None if ordered => "numbered_list_item",
+        None => "bulleted_list_item",
+    };
+    content.insert("rich_text".into(), Value::Array(segments));
+    if !children.is_empty() {
+        content.insert("children".into(), Value::Array(children));
+    }
+
+    block(kind, Value::Object(content))
+}
+
+#[derive(Clone, Copy, Default)]
+struct Annotations {
+    bold: bool,
+    italic: bool,
+    strikethrough: bool,
+    underline: bool,
+    code: bool,
+}
+
+fn text_segment(content: &str, annotations: Annotations) -> Value {
+    let mut segment = json!({ "type": "text", "text": { "content": content } });
+    let flags = [
+        ("bold", annotations.bold),
+        ("italic", annotations.italic),
+        ("strikethrough", annotations.strikethrough),
+        ("underline", annotations.underline),
+        ("code", annotations.code),
+    ];
+    let enabled: Map<String, Value> = flags
+        .iter()
+        .filter(|(_, enabled)| *enabled)
+        .map(|(name, _)| (name.to_string(), json!(true)))
+        .collect();
+    if !enabled.is_empty() {
+        segment["annotations"] = Value::Object(enabled);
+    }
+    segment
+}
+
+// converts the descendants of `node` into rich text segments
+fn rich_text(org: &Org, node: NodeId) -> Vec<Value> {
+    let mut segments = Vec::new();
+    write_rich_text(org, node, Annotations::default(), &mut segments);
+
+    // line breaks inside paragraphs are not significant
+    for segment in &mut segments {
+        if let Some(content) = segment["text"]["content"].as_str() {
+            let content = content.replace('\n', " ");
+            segment["text"]["content"] = json!(content);
+        }
+    }
+    if let Some(content) = segments
+        .last()
+        .and_then(|segment| segment["text"]["content"].as_str())
+    {
+        let content = content.trim_end().to_string();
+        if content.is_empty() {
+            segments.pop();
+        } else if let Some(segment) = segments.last_mut() {
+            segment["text"]["content"] = json!(content);
+        }
+    }
+
+    segments
+}
+
+fn write_rich_text(org: &Org, node: NodeId, annotations: Annotations, segments: &mut Vec<Value>) {
+    for child in node.children(&org.arena) {
+        let mut annotations = annotations;
+        match &org[child] {
+            Element::Bold => annotations.bold = true,
+            Element::Italic => annotations.italic = true,
+            Element::Strike => annotations.strikethrough = true,
+            Element::Underline => annotations.underline = true,
+            Element::Text { value } => {
+                segments.push(text_segment(value, annotations));
+                continue;
+            }
+            Element::Code { value } | Element::Verbatim { value } => {
+                annotations.code = true;
+                segments.push(text_segment(value, annotations));
+                continue;
+            }
+            Element::Link(link) => {
+                let mut segment =
+                    text_segment(link.desc.as_ref().unwrap_or(&link.path), annotations);
+                if link.path.contains("://") {
+                    segment["text"]["link"] = json!({ "url": link.path });
+                }
+                segments.push(segment);
+                continue;
+            }
+            Element::Cookie(cookie) => {
+                segments.push(text_segment(&cookie.value, annotations));
+                continue;
+            }
+            Element::Timestamp(_) | Element::FnRef(_) | Element::Snippet(_) => continue,
+            _ => (),
+        }
+        write_rich_text(org, child, annotations, segments);
+    }
+}
@@ -0,0 +1,246 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::Result as IOResult;
+use std::path::{Path, PathBuf};
+
+use crate::export::markdown::{to_markdown_with, MarkdownOptions};
+use crate::{Headline, Org};
+
+impl Org<'_> {
+    /// Exports this document as an Obsidian markdown note.
+    ///
+    /// `#+TITLE` and all headline tags are written to the YAML frontmatter,
+    /// and tags are also appended to their headlines as `#tag`. Links like
+    /// `[[id:...]]` to a headline with a matching `ID` property become
+    /// `[[title]]` wikilinks.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse(
+    ///     "#+TITLE: Notes\n* Rust :lang:\n:PROPERTIES:\n:ID: 42\n:END:\n\
+    ///      * Links\nSee [[id:42][this]] and [[id:42][Rust]].\n",
+    /// );
+    ///
+    /// assert_eq!(
+    ///     org.to_obsidian_markdown(),
+    ///     "---\ntitle: \"Notes\"\ntags:\n  - lang\n---\n\n# Rust #lang\n\n# Links\n\n\
+    ///      See [[Rust|this]] and [[Rust]].\n"
+    /// );
+    /// ```
+    pub fn to_obsidian_markdown(&self) -> String {
+        let mut tags: Vec<String> = Vec::new();
+        for headline in self.headlines() {
+            for tag in &headline.title(self).tags {
+                if !tags.iter().any(|t| t == tag) {
+                    tags.push(tag.to_string());
+                }
+            }
+        }
+
+        let mut frontmatter = Vec::new();
+        if let Some(title) = self.keyword_value("TITLE") {
+            frontmatter.push(format!("title: {}", yaml_string(title.trim())));
+        }
+        push_tags(&mut frontmatter, &tags);
+
+        let options = MarkdownOptions {
+            wikilinks: self
+                .headlines()
+                .filter_map(|headline| Some((id(self, headline)?, title(self, headline))))
+                .collect(),
+            inline_tags: true,
+            level_offset: 0,
+        };
+
+        let mut output = String::new();
+        push_frontmatter(&mut output, &frontmatter);
+        output.push_str(&to_markdown_with(self, self.root, &options));
+        output.push('\n');
+        output
+    }
+
+    /// Exports this document into `dir` as an Obsidian vault.
+    ///
+    /// Each level-1 headline is written to a markdown file named after its
+    /// title, with its tags and properties as YAML frontmatter, and its
+    /// section and sub-headlines as contents. Characters not allowed in
+    /// file names or wikilinks are replaced by `-`. Links like
+    /// `[[id:...]]` become wikilinks to the target file, or to a heading of
+    /// it, like `[[title#heading]]`.
+    ///
+    /// Creates `dir` if it doesn't exist, and returns the paths of created
+    /// files.
+    ///
+    /// ```rust,no_run
+    /// use orgize::Org;
+    /// use std::path::Path;
+    ///
+    /// let org = Org::parse("* Rust :lang:\n** Ownership\n* Go\n");
+    ///
+    /// // vault/Rust.md and vault/Go.md
+    /// let files = org.to_obsidian_vault(Path::new("vault")).unwrap();
+    /// assert_eq!(files.len(), 2);
+    /// ```
+    pub fn to_obsidian_vault(&self, dir: &Path) -> IOResult<Vec<PathBuf>> {
+        fs::create_dir_all(dir)?;
+
+        // file names of level-1 headlines
+        let mut names: HashMap<_, String> = HashMap::new();
+        for headline in self.document().children(self) {
+            let slug = slug(&title(self, headline));
+            let mut name = slug.clone();
+            let mut count = 1;
+            while names.values().any(|n| n.eq_ignore_ascii_case(&name)) {
+                count += 1;
+                name = format!("{} {}", slug, count);
+            }
+            names.insert(headline.headline_node(), name);
+        }
+
+        let mut wikilinks = HashMap::new();
+        for headline in self.headlines() {
+            if let Some(id) = id(self, headline) {
+                let mut top = headline;
+                while let Some(parent) = top.parent(self) {
+                    top = parent;
+                }
+                let name = &names[&top.headline_node()];
+                let target = if top.headline_node() == headline.headline_node() {
+                    name.clone()
+                } else {
+                    format!("{}#{}", name, slug(&title(self, headline)))
+                };
+                wikilinks.insert(id, target);
+            }
+        }
+        let options = MarkdownOptions {
+            wikilinks,
+            inline_tags: false,
+            level_offset: 1,
+        };
+
+        let mut files = Vec::new();
+        for headline in self.document().children(self) {
+            let title = headline.title(self);
+
+            let mut frontmatter = Vec::new();
+            let tags: Vec<_> = title.tags.iter().map(|tag| tag.to_string()).collect();
+            push_tags(&mut frontmatter, &tags);
+            for (key, value) in title.properties.iter() {
+                frontmatter.push(format!("{}: {}", yaml_string(key), yaml_string(value)));
+            }
+
+            let mut contents = Vec::new();
+            if let Some(section) = headline.section_node() {
+                contents.push(to_markdown_with(self, section, &options));
+            }
+            for child in headline.children(self) {
+                contents.push(to_markdown_with(self, child.headline_node(), &options));
+            }
+            contents.retain(|c| !c.is_empty());
+
+            let mut output = String::new();
+            push_frontmatter(&mut output, &frontmatter);
+            output.push_str(&contents.join("\n\n"));
+            output.push('\n');
+
+            let path = dir.join(format!("{}.md", names[&headline.headline_node()]));
+            fs::write(&path, output)?;
+            files.push(path);
+        }
+
+        Ok(files)
+    }
+}
+
+fn id(org: &Org, headline: Headline) -> Option<String> {
+    headline
+        .title(org)
+        .properties
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case("ID"))
+        .map(|(_, value)| value.trim().to_string())
+}
+
+fn title(org: &Org, headline: Headline) -> String {
+    headline.title(org).raw.trim().to_string()
+}
+
+// replaces characters not allowed in file names or wikilinks
+fn slug(title: &str) -> String {
+    let slug: String = title
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' | '#' | '^' | '[' | ']' => '-',
+            c => c,
+        })
+        .collect();
+    let slug = slug.trim().trim_start_matches('.');
+    if slug.is_empty() {
+        String::from("Untitled")
+    } else {
+        slug.to_string()
+    }
+}
+
+fn yaml_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn push_tags(frontmatter: &mut Vec<String>, tags: &[String]) {
+    if !tags.is_empty() {
+        frontmatter.push(String::from("tags:"));
+        for tag in tags {
+            frontmatter.push(format!("  - {}", tag));
+        }
+    }
+}
+
+fn push_frontmatter(output: &mut String, frontmatter: &[String]) {
+    if frontmatter.is_empty() {
+        return;
+    }
+    output.push_str("---\n");
+    for line in frontmatter {
+        output.push_str(line);
+        output.push('\n');
+    }
+    output.push_str("---\n\n");
+}
+
+#[test]
+fn to_obsidian_vault() {
+    let dir = crate::test_dir("to-obsidian-vault");
+
+    let org = Org::parse(
+        "* Rust: the language :lang:\n:PROPERTIES:\n:ID: 1\n:END:\n\
+         Fast.\n\
+         ** Ownership\n:PROPERTIES:\n:ID: 2\n:END:\n\
+         * Notes\n\
+         See [[id:1][Rust]] and [[id:2][ownership]].\n\
+         * Notes\n",
+    );
+    let files = org.to_obsidian_vault(&dir).unwrap();
+
+    assert_eq!(
+        files,
+        vec![
+            dir.join("Rust- the language.md"),
+            dir.join("Notes.md"),
+            dir.join("Notes 2.md")
+        ]
+    );
+    assert_eq!(
+        fs::read_to_string(&files[0]).unwrap(),
+        "---\ntags:\n  - lang\n\"ID\": \"1\"\n---\n\nFast.\n\n# Ownership\n"
+    );
+    assert_eq!(
+        fs::read_to_string(&files[1]).unwrap(),
+        "See [[Rust- the language|Rust]] and \
+         [[Rust- the language#Ownership|ownership]].\n"
+    );
+    assert_eq!(fs::read_to_string(&files[2]).unwrap(), "\n");
+
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,132 @@
+use crate::elements::Element;
+use crate::export::{is_image, HtmlEscape};
+use crate::Org;
+
+impl Org<'_> {
+    /// Returns Open Graph `<meta>` tags of this document, one per line, to be
+    /// placed in the `<head>` of the page embedding the html export of this
+    /// document.
+    ///
+    /// - `og:title` is taken from `#+TITLE`,
+    /// - `og:description` from `#+DESCRIPTION`, or `Org::summary` in 200 characters,
+    /// - `og:image` from `#+OG_IMAGE`, or the first link to an image file,
+    /// - `og:type` from `#+OG_TYPE`, defaulting to `article`.
+    ///
+    /// Tags without a value are omitted.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse(
+    ///     "#+TITLE: Cats & dogs\n* Intro\nAll about\n*pets*.\n\n[[file:cat.png]]\n",
+    /// );
+    ///
+    /// assert_eq!(
+    ///     org.to_open_graph_meta(),
+    ///     "<meta property=\"og:title\" content=\"Cats &amp; dogs\">\n\
+    ///      <meta property=\"og:description\" content=\"All about pets.\">\n\
+    ///      <meta property=\"og:image\" content=\"cat.png\">\n\
+    ///      <meta property=\"og:type\" content=\"article\">\n"
+    /// );
+    /// ```
+    pub fn to_open_graph_meta(&self) -> String {
+        let keyword = |key| keyword(self, key);
+        let properties = [
+            ("og:title", keyword("TITLE")),
+            ("og:description", description(self)),
+            (
+                "og:image",
+                keyword("OG_IMAGE").or_else(|| first_image(self)),
+            ),
+            (
+                "og:type",
+                keyword("OG_TYPE").or_else(|| Some("article".into())),
+            ),
+        ];
+
+        write_meta_tags("property", &properties)
+    }
+
+    /// Returns Twitter Card `<meta>` tags of this document, one per line, to
+    /// be placed in the `<head>` of the page embedding the html export of
+    /// this document.
+    ///
+    /// Title, description and image are found like in [`to_open_graph_meta`],
+    /// except that `#+TWITTER_IMAGE` is used for the image before
+    /// `#+OG_IMAGE`. `twitter:card` is taken from `#+TWITTER_CARD`,
+    /// defaulting to `summary_large_image` if the document has an image, or
+    /// `summary` otherwise.
+    ///
+    /// [`to_open_graph_meta`]: Org::to_open_graph_meta
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse("#+TITLE: Notes\n#+DESCRIPTION: My notes\n");
+    ///
+    /// assert_eq!(
+    ///     org.to_twitter_card_html(),
+    ///     "<meta name=\"twitter:card\" content=\"summary\">\n\
+    ///      <meta name=\"twitter:title\" content=\"Notes\">\n\
+    ///      <meta name=\"twitter:description\" content=\"My notes\">\n"
+    /// );
+    /// ```
+    pub fn to_twitter_card_html(&self) -> String {
+        let keyword = |key| keyword(self, key);
+        let image = keyword("TWITTER_IMAGE")
+            .or_else(|| keyword("OG_IMAGE"))
+            .or_else(|| first_image(self));
+        let card = keyword("TWITTER_CARD").unwrap_or_else(|| {
+            if image.is_some() {
+                "summary_large_image".into()
+            } else {
+                "summary".into()
+            }
+        });
+        let properties = [
+            ("twitter:card", Some(card)),
+            ("twitter:title", keyword("TITLE")),
+            ("twitter:description", description(self)),
+            ("twitter:image", image),
+        ];
+
+        write_meta_tags("name", &properties)
+    }
+}
+
+fn keyword(org: &Org, key: &str) -> Option<String> {
+    org.keyword_value(key)
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+// `#+DESCRIPTION`, or a summary of the document
+fn description(org: &Org) -> Option<String> {
+    keyword(org, "DESCRIPTION").or_else(|| Some(org.summary(200)).filter(|text| !text.is_empty()))
+}
+
+fn first_image(org: &Org) -> Option<String> {
+    org.root
+        .descendants(&org.arena)
+        .find_map(|node| match &org[node] {
+            Element::Link(link) if is_image(&link.path) => {
+                Some(link.path.trim_start_matches("file:").to_string())
+            }
+            _ => None,
+        })
+}
+
+fn write_meta_tags(attribute: &str, properties: &[(&str, Option<String>)]) -> String {
+    let mut output = String::new();
+    for (property, content) in properties {
+        if let Some(content) = content.as_ref().filter(|content| !content.is_empty()) {
+            output.push_str(&format!(
+                "<meta {}=\"{}\" content=\"{}\">\n",
+                attribute,
+                property,
+                HtmlEscape(content)
+            ));
+        }
+    }
+    output
+}
@@ -0,0 +1,93 @@
+use crate::export::HtmlEscape as Escape;
+use crate::{Headline, Org};
+
+impl Org<'_> {
+    /// Exports the headline hierarchy of this document as an [OPML] 2.0
+    /// outline.
+    ///
+    /// Each headline becomes an `<outline>` element, with its title as the
+    /// `text` attribute. Headlines with a `XMLURL` property are written as
+    /// feed subscriptions, with `type="rss"` and the `xmlUrl` attribute, and
+    /// headlines with a `URL` property are written as links. `#+TITLE` is
+    /// used as the title of the outline.
+    ///
+    /// [OPML]: http://opml.org/spec2.opml
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse(
+    ///     "#+TITLE: Feeds\n\
+    ///      * Podcasts & more\n\
+    ///      ** Show\n:PROPERTIES:\n:XMLURL: https://example.com/feed.xml\n:END:\n\
+    ///      * Blog\n:PROPERTIES:\n:URL: https://example.com\n:END:\n",
+    /// );
+    ///
+    /// assert_eq!(
+    ///     org.to_opml(),
+    ///     r#"<?xml version="1.0" encoding="UTF-8"?>
+    /// <opml version="2.0">
+    ///   <head>
+    ///     <title>Feeds</title>
+    ///   </head>
+    ///   <body>
+    ///     <outline text="Podcasts &amp; more">
+    ///       <outline text="Show" type="rss" xmlUrl="https://example.com/feed.xml"/>
+    ///     </outline>
+    ///     <outline text="Blog" type="link" url="https://example.com"/>
+    ///   </body>
+    /// </opml>
+    /// "#
+    /// );
+    /// ```
+    pub fn to_opml(&self) -> String {
+        let mut output = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        output.push_str("<opml version=\"2.0\">\n  <head>\n");
+        if let Some(title) = self.keyword_value("TITLE") {
+            output.push_str(&format!("    <title>{}</title>\n", Escape(title.trim())));
+        }
+        output.push_str("  </head>\n  <body>\n");
+        for headline in self.document().children(self) {
+            write_outline(self, headline, 2, &mut output);
+        }
+        output.push_str("  </body>\n</opml>\n");
+        output
+    }
+}
+
+fn write_outline(org: &Org, headline: Headline, depth: usize, output: &mut String) {
+    let title = headline.title(org);
+    let property = |key: &str| {
+        title
+            .properties
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, value)| value)
+    };
+
+    let indent = "  ".repeat(depth);
+    output.push_str(&format!(
+        "{}<outline text=\"{}\"",
+        indent,
+        Escape(&title.raw)
+    ));
+    if let Some(url) = property("XMLURL") {
+        output.push_str(&format!(" type=\"rss\" xmlUrl=\"{}\"", Escape(url)));
+        if let Some(url) = property("HTMLURL") {
+            output.push_str(&format!(" htmlUrl=\"{}\"", Escape(url)));
+        }
+    } else if let Some(url) = property("URL") {
+        output.push_str(&format!(" type=\"link\" url=\"{}\"", Escape(url)));
+    }
+
+    let mut children = headline.children(org).peekable();
+    if children.peek().is_none() {
+        output.push_str("/>\n");
+        return;
+    }
+    output.push_str(">\n");
+    for child in children {
+        write_outline(org, child, depth + 1, output);
+    }
+    output.push_str(&format!("{}</outline>\n", indent));
+}
@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::io::{Error, Result as IOResult, Write};
 
 use crate::elements::{Clock, Element, Table, Timestamp};
@@ -129,9 +130,7 @@ impl OrgHandler<Error> for DefaultOrgHandler {
                 write_timestamp(&mut w, &timestamp)?;
             }
             Element::Verbatim { value } => write!(w, "={}=", value)?,
-            Element::FnDef(fn_def) => {
-                write_blank_lines(w, fn_def.post_blank)?;
-            }
+            Element::FnDef(fn_def) => write!(w, "[fn:{}]", fn_def.label)?,
             Element::Clock(clock) => {
                 write!(w, "CLOCK: ")?;
 
@@ -176,7 +175,7 @@ impl OrgHandler<Error> for DefaultOrgHandler {
                 writeln!(w, "-----")?;
                 write_blank_lines(&mut w, rule.post_blank)?;
             }
-            Element::Cookie(_cookie) => (),
+            Element::Cookie(cookie) => write!(w, "{}", cookie.value)?,
             Element::Title(title) => {
                 for _ in 0..title.level {
                     write!(&mut w, "*")?;
@@ -189,7 +188,8 @@ impl OrgHandler<Error> for DefaultOrgHandler {
                 }
                 write!(&mut w, " ")?;
             }
-            Element::Table(_) => (),
+            Element::Table(Table::TableEl { value, .. }) => write!(w, "{}", value)?,
+            Element::Table(Table::Org { .. }) => (),
             Element::TableRow(_) => (),
             Element::TableCell(_) => (),
         }
@@ -247,25 +247,7 @@ impl OrgHandler<Error> for DefaultOrgHandler {
                 }
                 writeln!(&mut w)?;
                 if let Some(planning) = &title.planning {
-                    if let Some(scheduled) = &planning.scheduled {
-                        write!(&mut w, "SCHEDULED: ")?;
-                        write_timestamp(&mut w, &scheduled)?;
-                    }
-                    if let Some(deadline) = &planning.deadline {
-                        if planning.scheduled.is_some() {
-                            write!(&mut w, " ")?;
-                        }
-                        write!(&mut w, "DEADLINE: ")?;
-                        write_timestamp(&mut w, &deadline)?;
-                    }
-                    if let Some(closed) = &planning.closed {
-                        if planning.deadline.is_some() {
-                            write!(&mut w, " ")?;
-                        }
-                        write!(&mut w, "CLOSED: ")?;
-                        write_timestamp(&mut w, &closed)?;
-                    }
-                    writeln!(&mut w)?;
+                    write!(&mut w, "{}", planning.to_org())?;
                 }
                 if !title.properties.is_empty() {
                     writeln!(&mut w, ":PROPERTIES:")?;
@@ -284,6 +266,9 @@ impl OrgHandler<Error> for DefaultOrgHandler {
             }
             Element::TableRow(_) => (),
             Element::TableCell(_) => (),
+            Element::FnDef(fn_def) => {
+                write_blank_lines(w, fn_def.post_blank)?;
+            }
             // non-container elements
             _ => debug_assert!(!element.is_container()),
         }
@@ -299,23 +284,64 @@ fn write_blank_lines<W: Write>(mut w: W, count: usize) -> Result<(), Error> {
     Ok(())
 }
 
-fn write_timestamp<W: Write>(mut w: W, timestamp: &Timestamp) -> Result<(), Error> {
+pub(crate) fn write_timestamp<W: Write>(mut w: W, timestamp: &Timestamp) -> Result<(), Error> {
     match timestamp {
-        Timestamp::Active { start, .. } => {
-            write_datetime(w, "<", start, ">")?;
+        Timestamp::Active {
+            start,
+            repeater,
+            delay,
+        } => {
+            write_datetime(&mut w, "<", start, "")?;
+            write_repeater_and_delay(&mut w, repeater, delay)?;
+            write!(w, ">")?;
         }
-        Timestamp::Inactive { start, .. } => {
-            write_datetime(w, "[", start, "]")?;
+        Timestamp::Inactive {
+            start,
+            repeater,
+            delay,
+        } => {
+            write_datetime(&mut w, "[", start, "")?;
+            write_repeater_and_delay(&mut w, repeater, delay)?;
+            write!(w, "]")?;
         }
-        Timestamp::ActiveRange { start, end, .. } => {
-            write_datetime(&mut w, "<", start, ">--")?;
-            write_datetime(&mut w, "<", end, ">")?;
+        // ranges only keep the repeater and delay of their start
+        Timestamp::ActiveRange {
+            start,
+            end,
+            repeater,
+            delay,
+        } => {
+            write_datetime(&mut w, "<", start, "")?;
+            write_repeater_and_delay(&mut w, repeater, delay)?;
+            write_datetime(&mut w, ">--<", end, "")?;
+            write!(w, ">")?;
         }
-        Timestamp::InactiveRange { start, end, .. } => {
-            write_datetime(&mut w, "[", start, "]--")?;
-            write_datetime(&mut w, "[", end, "]")?;
+        Timestamp::InactiveRange {
+            start,
+            end,
+            repeater,
+            delay,
+        } => {
+            write_datetime(&mut w, "[", start, "")?;
+            write_repeater_and_delay(&mut w, repeater, delay)?;
+            write_datetime(&mut w, "]--[", end, "")?;
+            write!(w, "]")?;
         }
         Timestamp::Diary { value } => write!(w, "<%%({})>", value)?,
     }
     Ok(())
 }
+
+fn write_repeater_and_delay<W: Write>(
+    mut w: W,
+    repeater: &Option<Cow<str>>,
+    delay: &Option<Cow<str>>,
+) -> Result<(), Error> {
+    if let Some(repeater) = repeater {
+        write!(w, " {}", repeater)?;
+    }
+    if let Some(delay) = delay {
+        write!(w, " {}", delay)?;
+    }
+    Ok(())
+}
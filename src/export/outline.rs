@@ -0,0 +1,128 @@
+use crate::{Headline, Org};
+
+/// A node of the document outline
+///
+/// The root node represents the document itself, with a level of `0` and
+/// the `#+TITLE` of document as its title.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "ser", derive(serde::Serialize))]
+pub struct OutlineNode {
+    /// Headline title, without todo keyword, priority or tags
+    pub title: String,
+    /// Headline level
+    pub level: usize,
+    /// Value of `CUSTOM_ID` property, or `ID` property if absent
+    pub id: Option<String>,
+    /// Headline todo keyword
+    pub keyword: Option<String>,
+    /// Headline tags
+    pub tags: Vec<String>,
+    /// Child headlines
+    pub children: Vec<OutlineNode>,
+}
+
+impl Org<'_> {
+    /// Returns the outline of this document, as a tree of headlines.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse(
+    ///     "#+TITLE: Notes\n* TODO Plan :work:\n:PROPERTIES:\n:ID: plan\n:END:\n** Steps\n* Ideas\n",
+    /// );
+    ///
+    /// let outline = org.to_structured_outline();
+    /// assert_eq!(outline.title, "Notes");
+    /// assert_eq!(outline.children.len(), 2);
+    ///
+    /// let plan = &outline.children[0];
+    /// assert_eq!(plan.title, "Plan");
+    /// assert_eq!(plan.id.as_deref(), Some("plan"));
+    /// assert_eq!(plan.keyword.as_deref(), Some("TODO"));
+    /// assert_eq!(plan.tags, vec!["work"]);
+    /// assert_eq!(plan.children[0].title, "Steps");
+    /// assert_eq!(plan.children[0].level, 2);
+    /// ```
+    pub fn to_structured_outline(&self) -> OutlineNode {
+        OutlineNode {
+            title: self
+                .keywords()
+                .filter(|keyword| keyword.key.eq_ignore_ascii_case("TITLE"))
+                .map(|keyword| keyword.value.trim().to_string())
+                .last()
+                .unwrap_or_default(),
+            level: 0,
+            id: None,
+            keyword: None,
+            tags: Vec::new(),
+            children: self
+                .document()
+                .children(self)
+                .map(|headline| outline_node(headline, self))
+                .collect(),
+        }
+    }
+
+    /// Returns the outline of this document in json.
+    ///
+    /// See [`Org::to_structured_outline`] for details.
+    ///
+    /// [`Org::to_structured_outline`]: #method.to_structured_outline
+    #[cfg(all(feature = "ser", feature = "serde_json"))]
+    pub fn to_structured_outline_json(&self) -> String {
+        serde_json::to_string(&self.to_structured_outline())
+            .expect("outline is always serializable")
+    }
+}
+
+fn outline_node(headline: Headline, org: &Org) -> OutlineNode {
+    let title = headline.title(org);
+    let property = |key: &str| {
+        title
+            .properties
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, value)| value.to_string())
+    };
+
+    OutlineNode {
+        title: title.raw.to_string(),
+        level: headline.level(),
+        id: property("CUSTOM_ID").or_else(|| property("ID")),
+        keyword: title.keyword.as_ref().map(|keyword| keyword.to_string()),
+        tags: title.tags.iter().map(|tag| tag.to_string()).collect(),
+        children: headline
+            .children(org)
+            .map(|child| outline_node(child, org))
+            .collect(),
+    }
+}
+
+#[test]
+fn to_structured_outline() {
+    assert_eq!(
+        Org::parse("").to_structured_outline(),
+        OutlineNode::default()
+    );
+
+    let config = crate::ParseConfig {
+        todo_keywords: (vec!["À_FAIRE".into()], vec![]),
+        ..Default::default()
+    };
+    let org = Org::parse_custom(
+        "#+TITLE: old\n#+title:  日本 \n* À_FAIRE été :ä:\n:PROPERTIES:\n:ID: 1\n:custom_id: c\n:END:\n*** deep\n",
+        &config,
+    );
+    let outline = org.to_structured_outline();
+    assert_eq!(outline.title, "日本");
+
+    let node = &outline.children[0];
+    assert_eq!(node.title, "été");
+    assert_eq!(node.keyword.as_deref(), Some("À_FAIRE"));
+    assert_eq!(node.tags, ["ä"]);
+    // custom id is preferred
+    assert_eq!(node.id.as_deref(), Some("c"));
+    // skipped levels are kept as they are
+    assert_eq!(node.children[0].level, 3);
+    assert!(node.children[0].children.is_empty());
+}
@@ -0,0 +1,553 @@
+use unicode_width::UnicodeWidthStr;
+
+use crate::elements::{Element, PropertiesMap, Table, TableRow};
+use crate::export::{DefaultOrgHandler, OrgHandler};
+use crate::{Event, Org};
+
+impl Org<'_> {
+    /// Writes `Org` struct in org syntax with a canonical formatting.
+    ///
+    /// Contents of list items and drawers, including properties drawers, are
+    /// indented by `indent` spaces relative to their parent. Since list item
+    /// contents must be indented in org syntax, at least one space is used
+    /// for list items. Headline tags are right-aligned to column 77,
+    /// properties are sorted by their keys, and table cells are padded to
+    /// the width of their column.
+    ///
+    /// See [`OrgFormatter`] for more options.
+    ///
+    /// [`OrgFormatter`]: export/struct.OrgFormatter.html
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse(
+    ///     "* TODO Task :work:\n:PROPERTIES:\n:b: 2\n:A: 1\n:END:\n\
+    ///      :NOTES:\nnote\n:END:\n- a\n    continued\n    - b\n",
+    /// );
+    ///
+    /// assert_eq!(
+    ///     org.to_pretty_org(2),
+    ///     format!(
+    ///         "* TODO Task{}:work:\n:PROPERTIES:\n  :A: 1\n  :b: 2\n:END:\n\
+    ///          :NOTES:\n  note\n:END:\n- a\n  continued\n  - b\n",
+    ///         " ".repeat(77 - "* TODO Task".len() - ":work:".len())
+    ///     )
+    /// );
+    /// ```
+    pub fn to_pretty_org(&self, indent: usize) -> String {
+        OrgFormatter::new().indent(indent).format(self)
+    }
+}
+
+/// Canonical org formatter
+///
+/// ```rust
+/// use orgize::export::OrgFormatter;
+/// use orgize::Org;
+///
+/// let org = Org::parse("* A :tag:\n:LOGBOOK:\nnote\n:END:\ntext\n* B\n\n** C\n");
+///
+/// let formatter = OrgFormatter::new()
+///     .tags_column(12)
+///     .indent_drawers(false)
+///     .blank_lines_before_headlines(1)
+///     .blank_lines_between_headlines(0);
+/// assert_eq!(
+///     formatter.format(&org),
+///     "* A    :tag:\n:LOGBOOK:\nnote\n:END:\ntext\n\n* B\n** C\n"
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct OrgFormatter {
+    tags_column: usize,
+    indent: usize,
+    indent_drawers: bool,
+    sort_properties: bool,
+    blank_lines_between_headlines: Option<usize>,
+    blank_lines_before_headlines: Option<usize>,
+}
+
+impl Default for OrgFormatter {
+    fn default() -> Self {
+        OrgFormatter {
+            tags_column: 77,
+            indent: 2,
+            indent_drawers: true,
+            sort_properties: true,
+            blank_lines_between_headlines: None,
+            blank_lines_before_headlines: None,
+        }
+    }
+}
+
+impl OrgFormatter {
+    /// Creates a formatter with default options, same as
+    /// [`Org::to_pretty_org`] with an indentation of 2.
+    ///
+    /// [`Org::to_pretty_org`]: ../struct.Org.html#method.to_pretty_org
+    pub fn new() -> Self {
+        OrgFormatter::default()
+    }
+
+    /// Sets the column where headline tags end, default is 77, same as
+    /// `org-tags-column` in Emacs. Wide characters count as two columns.
+    pub fn tags_column(mut self, column: usize) -> Self {
+        self.tags_column = column;
+        self
+    }
+
+    /// Sets the indentation of list item and drawer contents, default is 2.
+    pub fn indent(mut self, indent: usize) -> Self {
+        self.indent = indent;
+        self
+    }
+
+    /// Sets whether drawer contents, including properties, are indented,
+    /// default is `true`.
+    pub fn indent_drawers(mut self, indent_drawers: bool) -> Self {
+        self.indent_drawers = indent_drawers;
+        self
+    }
+
+    /// Sets whether properties are sorted by their keys, default is `true`.
+    pub fn sort_properties(mut self, sort_properties: bool) -> Self {
+        self.sort_properties = sort_properties;
+        self
+    }
+
+    /// Sets the number of blank lines between a headline without section
+    /// and the following headline. By default, blank lines are kept.
+    pub fn blank_lines_between_headlines(mut self, count: usize) -> Self {
+        self.blank_lines_between_headlines = Some(count);
+        self
+    }
+
+    /// Sets the number of blank lines between a section and the following
+    /// headline. By default, blank lines are kept.
+    pub fn blank_lines_before_headlines(mut self, count: usize) -> Self {
+        self.blank_lines_before_headlines = Some(count);
+        self
+    }
+
+    /// Formats `Org` struct in org syntax.
+    pub fn format(&self, org: &Org) -> String {
+        self.print(org, false)
+    }
+
+    // writes `org` with the layout kept by the parser, only formatting tags,
+    // properties and tables, whose layout is discarded when parsing
+    pub(crate) fn format_parsed(&self, org: &Org) -> String {
+        self.print(org, true)
+    }
+
+    fn print(&self, org: &Org, parsed: bool) -> String {
+        let mut printer = Printer {
+            formatter: self,
+            parsed,
+            output: String::new(),
+            margins: Vec::new(),
+            headline: None,
+            table: None,
+            after_title: false,
+        };
+        for event in org.iter() {
+            match event {
+                Event::Start(element) => printer.start(element),
+                Event::End(element) => printer.end(element),
+            }
+        }
+        printer.output
+    }
+
+    fn drawer_indent(&self) -> usize {
+        if self.indent_drawers {
+            self.indent
+        } else {
+            0
+        }
+    }
+}
+
+struct Printer<'a> {
+    formatter: &'a OrgFormatter,
+    // `true` if the layout kept by the parser is written unchanged
+    parsed: bool,
+    output: String,
+    // indentation added by each enclosing list item or drawer
+    margins: Vec<usize>,
+    // headline line, while its title is being written
+    headline: Option<String>,
+    // rows of cells, while an org table is being written, `None` for rules
+    table: Option<Vec<Option<Vec<String>>>>,
+    // `true` if nothing but blank lines was written since last title
+    after_title: bool,
+}
+
+impl Printer<'_> {
+    fn start(&mut self, element: &Element) {
+        match element {
+            Element::Headline { .. } if self.parsed => return,
+            Element::Headline { .. } => {
+                let count = if self.after_title {
+                    self.formatter.blank_lines_between_headlines
+                } else {
+                    self.formatter.blank_lines_before_headlines
+                };
+                if let (Some(count), false) = (count, self.output.is_empty()) {
+                    self.output
+                        .truncate(self.output.trim_end_matches('\n').len() + 1);
+                    self.output.push_str(&"\n".repeat(count));
+                }
+                return;
+            }
+            Element::Title(_) => self.headline = Some(default_start(element)),
+            Element::Table(Table::Org { .. }) => self.table = Some(Vec::new()),
+            Element::TableRow(row) => {
+                let cells = match row {
+                    TableRow::Header | TableRow::Body => Some(Vec::new()),
+                    TableRow::HeaderRule | TableRow::BodyRule => None,
+                };
+                if let Some(rows) = &mut self.table {
+                    rows.push(cells);
+                }
+            }
+            Element::TableCell(_) => {
+                if let Some(Some(Some(cells))) = self.table.as_mut().map(|rows| rows.last_mut()) {
+                    cells.push(String::new());
+                }
+            }
+            Element::ListItem(_) | Element::Drawer(_) if self.parsed => {
+                self.push(&default_start(element))
+            }
+            Element::ListItem(item) => {
+                self.push(&item.bullet);
+                self.margins.push(self.formatter.indent.max(1));
+            }
+            Element::Drawer(_) => {
+                self.push(&default_start(element));
+                self.margins.push(self.formatter.drawer_indent());
+            }
+            Element::Text { value } if !self.parsed && self.margin() > 0 => {
+                for (index, line) in value.split('\n').enumerate() {
+                    if index > 0 {
+                        self.push("\n");
+                    }
+                    if self.at_line_start() {
+                        self.push(line.trim_start());
+                    } else {
+                        self.push(line);
+                    }
+                }
+            }
+            Element::CommentBlock(_)
+            | Element::ExampleBlock(_)
+            | Element::ExportBlock(_)
+            | Element::SourceBlock(_)
+                if !self.parsed && self.margin() > 0 =>
+            {
+                self.push(&dedent_contents(&default_start(element)));
+            }
+            _ => self.push(&default_start(element)),
+        }
+        self.after_title = false;
+    }
+
+    fn end(&mut self, element: &Element) {
+        match element {
+            Element::Title(title) => {
+                let mut headline = self.headline.take().unwrap_or_default();
+                if !title.tags.is_empty() {
+                    let tags = format!(":{}:", title.tags.join(":"));
+                    let width = headline.width() + tags.width();
+                    let padding = self.formatter.tags_column.saturating_sub(width).max(1);
+                    headline.push_str(&" ".repeat(padding));
+                    headline.push_str(&tags);
+                }
+                self.push(&headline);
+
+                // planning line, properties are written below
+                let mut planning = title.clone();
+                planning.tags = Vec::new();
+                planning.properties = PropertiesMap::new();
+                planning.post_blank = 0;
+                self.push(&default_end(&Element::Title(planning)));
+
+                if !title.properties.is_empty() {
+                    let mut properties: Vec<_> = title.properties.iter().collect();
+                    if self.formatter.sort_properties && !self.parsed {
+                        properties.sort_by_key(|(key, _)| key.to_lowercase());
+                    }
+                    self.push(":PROPERTIES:\n");
+                    self.margins.push(self.formatter.drawer_indent());
+                    for (key, value) in properties {
+                        self.push(&format!(":{}: {}\n", key, value));
+                    }
+                    self.margins.pop();
+                    self.push(":END:\n");
+                }
+                self.push(&"\n".repeat(title.post_blank));
+                self.after_title = true;
+            }
+            Element::ListItem(_) | Element::Drawer(_) if self.parsed => {
+                self.push(&default_end(element))
+            }
+            Element::ListItem(_) => {
+                self.margins.pop();
+            }
+            Element::Drawer(_) => {
+                self.margins.pop();
+                self.push(&default_end(element));
+            }
+            Element::Table(Table::Org { .. }) => {
+                let rows = self.table.take().unwrap_or_default();
+                self.push(&align_table(&rows));
+                self.push(&default_end(element));
+            }
+            Element::TableRow(_) | Element::TableCell(_) => (),
+            _ => self.push(&default_end(element)),
+        }
+    }
+
+    fn margin(&self) -> usize {
+        self.margins.iter().sum()
+    }
+
+    fn at_line_start(&self) -> bool {
+        self.headline.is_none()
+            && self.table.is_none()
+            && (self.output.is_empty() || self.output.ends_with('\n'))
+    }
+
+    // writes text, indenting each non-empty line by current margin
+    fn push(&mut self, text: &str) {
+        let margin = self.margin();
+        for c in text.chars() {
+            if c != '\n' && self.at_line_start() {
+                self.output.push_str(&" ".repeat(margin));
+            }
+            match (&mut self.headline, &mut self.table) {
+                (Some(headline), _) => headline.push(c),
+                (None, Some(rows)) => {
+                    if let Some(Some(cell)) = rows.last_mut().map(|row| row.as_mut()?.last_mut()) {
+                        cell.push(c);
+                    }
+                }
+                (None, None) => self.output.push(c),
+            }
+        }
+    }
+}
+
+fn default_start(element: &Element) -> String {
+    let mut writer = Vec::new();
+    let _ = DefaultOrgHandler.start(&mut writer, element);
+    String::from_utf8_lossy(&writer).into_owned()
+}
+
+fn default_end(element: &Element) -> String {
+    let mut writer = Vec::new();
+    let _ = DefaultOrgHandler.end(&mut writer, element);
+    String::from_utf8_lossy(&writer).into_owned()
+}
+
+// writes table rows with cells padded to the width of their column
+fn align_table(rows: &[Option<Vec<String>>]) -> String {
+    let mut widths: Vec<usize> = Vec::new();
+    for cells in rows.iter().flatten() {
+        for (index, cell) in cells.iter().enumerate() {
+            let width = cell.width().max(1);
+            match widths.get_mut(index) {
+                Some(max) => *max = (*max).max(width),
+                None => widths.push(width),
+            }
+        }
+    }
+
+    let mut output = String::new();
+    for row in rows {
+        match row {
+            Some(cells) => {
+                output.push('|');
+                for (index, width) in widths.iter().enumerate() {
+                    let cell = cells.get(index).map(String::as_str).unwrap_or_default();
+                    output.push(' ');
+                    output.push_str(cell);
+                    output.push_str(&" ".repeat(width - cell.width() + 1));
+                    output.push('|');
+                }
+            }
+            None => {
+                let dashes: Vec<_> = widths.iter().map(|width| "-".repeat(width + 2)).collect();
+                output.push('|');
+                output.push_str(&dashes.join("+"));
+                output.push('|');
+            }
+        }
+        output.push('\n');
+    }
+    output
+}
+
+// removes the common indentation of lines between the begin and end lines of a block
+pub(crate) fn dedent_contents(block: &str) -> String {
+    let is_contents = |line: &&str| !line.trim().is_empty() && !line.starts_with("#+");
+    let common = block
+        .lines()
+        .filter(is_contents)
+        // only ascii indentation is removed, so `common` is always a char boundary
+        .map(|line| line.len() - line.trim_start_matches([' ', '\t']).len())
+        .min()
+        .unwrap_or_default();
+
+    block
+        .split_inclusive('\n')
+        .map(|line| {
+            if is_contents(&line) {
+                &line[common..]
+            } else {
+                line
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn dedent_non_ascii_whitespace() {
+    assert_eq!(
+        dedent_contents("#+BEGIN_SRC\n   a\n  \u{a0}b\n#+END_SRC\n"),
+        "#+BEGIN_SRC\n a\n\u{a0}b\n#+END_SRC\n"
+    );
+
+    let org = Org::parse("- item\n  #+BEGIN_SRC\n   a\n  \u{a0}b\n  #+END_SRC\n");
+    assert!(org.to_pretty_org(2).contains("\u{a0}b"));
+}
+
+#[test]
+fn tables() {
+    let check = |input: &str, expected: &str| {
+        let output = Org::parse(input).to_pretty_org(2);
+        assert_eq!(output, expected);
+        assert_eq!(Org::parse(&output).to_pretty_org(2), output);
+    };
+
+    check(
+        "| a | b |\n|---+---|\n| c | d |\n",
+        "| a | b |\n|---+---|\n| c | d |\n",
+    );
+    check(
+        "|a|long cell|\n|-\n| *bold* | é |\n\ntext\n",
+        "| a      | long cell |\n|--------+-----------|\n| *bold* | é         |\n\ntext\n",
+    );
+    check("- [ ] two\n  | a | b |\n", "- [ ] two\n  | a | b |\n");
+    check(
+        "- item\n      |a|\n      |-|\n      |bc|\n- next\n",
+        "- item\n  | a  |\n  |----|\n  | bc |\n- next\n",
+    );
+    check("|日本|a|\n|b|c|\n", "| 日本 | a |\n| b    | c |\n");
+    check(
+        "* A\n:NOTES:\n|x|y|\n:END:\n",
+        "* A\n:NOTES:\n  | x | y |\n:END:\n",
+    );
+    check("+---+\n| a |\n+---+\n", "+---+\n| a |\n+---+\n");
+}
+
+#[test]
+fn tags_column() {
+    let org = Org::parse("* TODO 日本語 :a:b:\n* Long title :x:\n");
+
+    assert_eq!(
+        OrgFormatter::new().tags_column(20).format(&org),
+        "* TODO 日本語  :a:b:\n* Long title     :x:\n"
+    );
+    // tags never touch the title
+    assert_eq!(
+        OrgFormatter::new().tags_column(5).format(&org),
+        "* TODO 日本語 :a:b:\n* Long title :x:\n"
+    );
+    // the last headline in the document
+    assert_eq!(
+        OrgFormatter::new()
+            .tags_column(12)
+            .format(&Org::parse("* A :t:")),
+        "* A      :t:\n"
+    );
+}
+
+#[test]
+fn indent_drawers() {
+    let org = Org::parse("* A\n:PROPERTIES:\n:ID: 1\n:END:\n:NOTES:\nnote\n:END:\n");
+
+    assert_eq!(
+        OrgFormatter::new().indent(4).format(&org),
+        "* A\n:PROPERTIES:\n    :ID: 1\n:END:\n:NOTES:\n    note\n:END:\n"
+    );
+    assert_eq!(
+        OrgFormatter::new()
+            .indent(4)
+            .indent_drawers(false)
+            .format(&org),
+        "* A\n:PROPERTIES:\n:ID: 1\n:END:\n:NOTES:\nnote\n:END:\n"
+    );
+    // list items are always indented
+    assert_eq!(
+        OrgFormatter::new()
+            .indent(0)
+            .format(&Org::parse("- a\n    b\n")),
+        "- a\n b\n"
+    );
+}
+
+#[test]
+fn sort_properties() {
+    let org = Org::parse("* A\n:PROPERTIES:\n:b: 2\n:C: 3\n:a: 1\n:END:\n");
+
+    assert_eq!(
+        OrgFormatter::new().indent_drawers(false).format(&org),
+        "* A\n:PROPERTIES:\n:a: 1\n:b: 2\n:C: 3\n:END:\n"
+    );
+    assert_eq!(
+        OrgFormatter::new()
+            .indent_drawers(false)
+            .sort_properties(false)
+            .format(&org),
+        "* A\n:PROPERTIES:\n:b: 2\n:C: 3\n:a: 1\n:END:\n"
+    );
+}
+
+#[test]
+fn blank_lines() {
+    let org = Org::parse("* A\n\n\n* B\ntext\n** C\n\n* D\n\n");
+
+    // blank lines are kept by default
+    assert_eq!(
+        OrgFormatter::new().format(&org),
+        "* A\n\n\n* B\ntext\n** C\n\n* D\n\n"
+    );
+    assert_eq!(
+        OrgFormatter::new()
+            .blank_lines_between_headlines(1)
+            .format(&org),
+        "* A\n\n* B\ntext\n** C\n\n* D\n\n"
+    );
+    assert_eq!(
+        OrgFormatter::new()
+            .blank_lines_before_headlines(2)
+            .format(&org),
+        "* A\n\n\n* B\ntext\n\n\n** C\n\n* D\n\n"
+    );
+    assert_eq!(
+        OrgFormatter::new()
+            .blank_lines_between_headlines(0)
+            .blank_lines_before_headlines(0)
+            .format(&org),
+        "* A\n* B\ntext\n** C\n* D\n\n"
+    );
+    // nothing is written before the first headline
+    assert_eq!(
+        OrgFormatter::new()
+            .blank_lines_before_headlines(1)
+            .format(&Org::parse("* A\n")),
+        "* A\n"
+    );
+}
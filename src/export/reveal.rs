@@ -0,0 +1,194 @@
+use indextree::{NodeEdge, NodeId};
+use std::io::{Error, Result as IOResult, Write};
+
+use crate::elements::Element;
+use crate::export::{DefaultHtmlHandler, HtmlEscape, HtmlHandler};
+use crate::Org;
+
+const REVEAL_ROOT: &str = "https://cdn.jsdelivr.net/npm/reveal.js@4";
+
+/// reveal.js presentation configuration
+#[derive(Clone, Debug)]
+pub struct RevealJsConfig {
+    /// Name of the presentation theme, default is `"black"`
+    pub theme: String,
+    /// Slide transition style, default is `"slide"`
+    pub transition: String,
+    /// Name of the highlight.js theme for source blocks, default is `"monokai"`
+    pub highlight_theme: String,
+}
+
+impl Default for RevealJsConfig {
+    fn default() -> Self {
+        RevealJsConfig {
+            theme: String::from("black"),
+            transition: String::from("slide"),
+            highlight_theme: String::from("monokai"),
+        }
+    }
+}
+
+impl Org<'_> {
+    /// Exports this document as a reveal.js presentation.
+    ///
+    /// `#+TITLE` and `#+AUTHOR` make up the title slide. Each level-1
+    /// headline becomes a slide, and its sub-headlines become vertical slides
+    /// below it. Contents before the first headline are not exported.
+    /// `#+BEGIN_NOTES` blocks become speaker notes, and source blocks are
+    /// highlighted by reveal.js's highlight plugin. reveal.js itself is
+    /// loaded from jsDelivr.
+    ///
+    /// ```rust
+    /// use orgize::export::RevealJsConfig;
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse(
+    ///     "#+TITLE: Talk\n* Intro\nHello.\n#+BEGIN_NOTES\nSmile.\n#+END_NOTES\n\
+    ///      * Details\n** First\n** Second\n",
+    /// );
+    /// let html = org.to_reveal_html(&RevealJsConfig::default());
+    ///
+    /// assert!(html.contains(
+    ///     "<div class=\"slides\">\n\
+    ///      <section><h1>Talk</h1></section>\n\
+    ///      <section><h1>Intro</h1><section><p>Hello.</p>\
+    ///      <aside class=\"notes\"><p>Smile.</p></aside></section></section>\n\
+    ///      <section>\n\
+    ///      <section><h1>Details</h1></section>\n\
+    ///      <section><h2>First</h2></section>\n\
+    ///      <section><h2>Second</h2></section>\n\
+    ///      </section>\n\
+    ///      </div>"
+    /// ));
+    /// assert!(html.contains("transition: \"slide\""));
+    /// ```
+    pub fn to_reveal_html(&self, config: &RevealJsConfig) -> String {
+        let title = self.keyword_value("TITLE").map(str::trim);
+        let author = self.keyword_value("AUTHOR").map(str::trim);
+
+        let mut output =
+            String::from("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+        if let Some(title) = title {
+            output.push_str(&format!("<title>{}</title>\n", HtmlEscape(title)));
+        }
+        for stylesheet in &[
+            String::from("dist/reveal.css"),
+            format!("dist/theme/{}.css", config.theme),
+            format!("plugin/highlight/{}.css", config.highlight_theme),
+        ] {
+            output.push_str(&format!(
+                "<link rel=\"stylesheet\" href=\"{}/{}\">\n",
+                REVEAL_ROOT,
+                HtmlEscape(stylesheet)
+            ));
+        }
+        output.push_str("</head>\n<body>\n<div class=\"reveal\">\n<div class=\"slides\">\n");
+
+        if title.is_some() || author.is_some() {
+            output.push_str("<section>");
+            if let Some(title) = title {
+                output.push_str(&format!("<h1>{}</h1>", HtmlEscape(title)));
+            }
+            if let Some(author) = author {
+                output.push_str(&format!("<p>{}</p>", HtmlEscape(author)));
+            }
+            output.push_str("</section>\n");
+        }
+
+        for headline in self.document().children(self) {
+            let mut slide = render(self, headline.title_node());
+            if let Some(section) = headline.section_node() {
+                slide.push_str(&render(self, section));
+            }
+
+            if headline.children(self).next().is_none() {
+                output.push_str(&format!("<section>{}</section>\n", slide));
+            } else {
+                output.push_str(&format!("<section>\n<section>{}</section>\n", slide));
+                for child in headline.children(self) {
+                    output.push_str(&format!(
+                        "<section>{}</section>\n",
+                        render(self, child.headline_node())
+                    ));
+                }
+                output.push_str("</section>\n");
+            }
+        }
+
+        output.push_str("</div>\n</div>\n");
+        for plugin in &[
+            "dist/reveal.js",
+            "plugin/notes/notes.js",
+            "plugin/highlight/highlight.js",
+        ] {
+            output.push_str(&format!(
+                "<script src=\"{}/{}\"></script>\n",
+                REVEAL_ROOT, plugin
+            ));
+        }
+        output.push_str(&format!(
+            "<script>Reveal.initialize({{ transition: {}, plugins: [RevealHighlight, RevealNotes] }});</script>\n",
+            js_string(&config.transition)
+        ));
+        output.push_str("</body>\n</html>\n");
+        output
+    }
+}
+
+// renders `node` and its descendants with `RevealHtmlHandler`
+fn render(org: &Org, node: NodeId) -> String {
+    let mut handler = RevealHtmlHandler::default();
+    let mut writer = Vec::new();
+    for edge in node.traverse(&org.arena) {
+        let _ = match edge {
+            NodeEdge::Start(node) => handler.start(&mut writer, &org[node]),
+            NodeEdge::End(node) => handler.end(&mut writer, &org[node]),
+        };
+    }
+    String::from_utf8_lossy(&writer).into_owned()
+}
+
+#[derive(Default)]
+struct RevealHtmlHandler(DefaultHtmlHandler);
+
+impl HtmlHandler<Error> for RevealHtmlHandler {
+    fn start<W: Write>(&mut self, mut w: W, element: &Element) -> IOResult<()> {
+        match element {
+            Element::SpecialBlock(block) if block.name.eq_ignore_ascii_case("NOTES") => {
+                write!(w, "<aside class=\"notes\">")
+            }
+            Element::SourceBlock(block) => write!(
+                w,
+                "<pre><code class=\"language-{}\" data-trim>{}</code></pre>",
+                HtmlEscape(&block.language),
+                HtmlEscape(&block.contents)
+            ),
+            _ => self.0.start(w, element),
+        }
+    }
+
+    fn end<W: Write>(&mut self, mut w: W, element: &Element) -> IOResult<()> {
+        match element {
+            Element::SpecialBlock(block) if block.name.eq_ignore_ascii_case("NOTES") => {
+                write!(w, "</aside>")
+            }
+            _ => self.0.end(w, element),
+        }
+    }
+}
+
+// writes a javascript string literal, which is safe to put in a `<script>`
+fn js_string(value: &str) -> String {
+    let mut output = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '<' => output.push_str("\\u003c"),
+            '\n' => output.push_str("\\n"),
+            c => output.push(c),
+        }
+    }
+    output.push('"');
+    output
+}
@@ -0,0 +1,228 @@
+use indextree::NodeId;
+use serde_json::{json, Map, Value};
+
+use crate::elements::Element;
+use crate::{Headline, Org};
+
+impl Org<'_> {
+    /// Exports this document in Roam Research's json import format.
+    ///
+    /// Each level-1 headline becomes a page, and its section and
+    /// sub-headlines become nested blocks: paragraphs, list items and source
+    /// blocks become blocks, and sub-headlines become blocks with their
+    /// contents nested below. Internal links become `[[page]]` references,
+    /// or `[description]([[page]])` aliases if they have a description, bold and italic texts use Roam's `**` and `__` markers, and footnotes
+    /// become child blocks of the block referencing them. Contents before
+    /// the first headline are not exported.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    /// use serde_json::{json, Value};
+    ///
+    /// let org = Org::parse(
+    ///     "* Reading\n*Books* about [[Rust]][fn::and /more/].\n\
+    ///      ** Queue\n- one\n  - two\n",
+    /// );
+    /// let pages: Value = serde_json::from_str(&org.to_roam_research_json()).unwrap();
+    ///
+    /// assert_eq!(
+    ///     pages,
+    ///     json!([{
+    ///         "title": "Reading",
+    ///         "children": [
+    ///             {
+    ///                 "string": "**Books** about [[Rust]].",
+    ///                 "children": [{ "string": "and __more__" }]
+    ///             },
+    ///             {
+    ///                 "string": "Queue",
+    ///                 "children": [{
+    ///                     "string": "one",
+    ///                     "children": [{ "string": "two" }]
+    ///                 }]
+    ///             }
+    ///         ]
+    ///     }])
+    /// );
+    /// ```
+    pub fn to_roam_research_json(&self) -> String {
+        let pages: Vec<Value> = self
+            .document()
+            .children(self)
+            .map(|headline| {
+                json!({
+                    "title": headline.title(self).raw.trim(),
+                    "children": headline_blocks(self, headline),
+                })
+            })
+            .collect();
+
+        Value::Array(pages).to_string()
+    }
+}
+
+// blocks of the section and sub-headlines of `headline`
+fn headline_blocks(org: &Org, headline: Headline) -> Vec<Value> {
+    let mut blocks = Vec::new();
+    if let Some(section) = headline.section_node() {
+        for child in section.children(&org.arena) {
+            element_blocks(org, child, &mut blocks);
+        }
+    }
+    for child in headline.children(org) {
+        let mut notes = Vec::new();
+        let string = inline_text(org, child.title_node(), &mut notes);
+        let mut children = notes;
+        children.extend(headline_blocks(org, child));
+        blocks.push(block(string, children));
+    }
+    blocks
+}
+
+fn element_blocks(org: &Org, node: NodeId, blocks: &mut Vec<Value>) {
+    match &org[node] {
+        Element::Paragraph { .. } => {
+            let mut notes = Vec::new();
+            let string = inline_text(org, node, &mut notes);
+            blocks.push(block(string, notes));
+        }
+        Element::List(_) => {
+            for item in node.children(&org.arena) {
+                let mut notes = Vec::new();
+                let mut strings = Vec::new();
+                let mut children = Vec::new();
+                for child in item.children(&org.arena) {
+                    match org[child] {
+                        Element::Paragraph { .. } => {
+                            strings.push(inline_text(org, child, &mut notes))
+                        }
+                        _ => element_blocks(org, child, &mut children),
+                    }
+                }
+                notes.extend(children);
+                blocks.push(block(strings.join("\n"), notes));
+            }
+        }
+        Element::QuoteBlock(_) | Element::CenterBlock(_) | Element::SpecialBlock(_) => {
+            for child in node.children(&org.arena) {
+                element_blocks(org, child, blocks);
+            }
+        }
+        Element::SourceBlock(source) => {
+            let contents = source.contents.trim_end();
+            blocks.push(block(
+                format!("```{}\n{}```", source.language, contents),
+                Vec::new(),
+            ));
+        }
+        _ => (),
+    }
+}
+
+fn block(string: String, children: Vec<Value>) -> Value {
+    let mut block = Map::new();
+    block.insert("string".into(), json!(string.trim()));
+    if !children.is_empty() {
+        block.insert("children".into(), Value::Array(children));
+    }
+    Value::Object(block)
+}
+
+// converts the descendants of `node` into Roam markup, footnotes are pushed into `notes`
+fn inline_text(org: &Org, node: NodeId, notes: &mut Vec<Value>) -> String {
+    let mut output = String::new();
+    for child in node.children(&org.arena) {
+        write_inline(org, child, &mut output, notes);
+    }
+    output
+}
+
+fn write_inline(org: &Org, node: NodeId, output: &mut String, notes: &mut Vec<Value>) {
+    match &org[node] {
+        Element::Bold => write_emphasis(org, node, output, notes, "**"),
+        Element::Italic => write_emphasis(org, node, output, notes, "__"),
+        Element::Strike => write_emphasis(org, node, output, notes, "~~"),
+        Element::Underline | Element::Paragraph { .. } => {
+            output.push_str(&inline_text(org, node, notes))
+        }
+        Element::Code { value } | Element::Verbatim { value } => {
+            output.push('`');
+            output.push_str(value);
+            output.push('`');
+        }
+        Element::Text { value } => output.push_str(value),
+        Element::Link(link) if link.path.contains("://") => match &link.desc {
+            Some(desc) => output.push_str(&format!("[{}]({})", desc, link.path)),
+            None => output.push_str(&link.path),
+        },
+        Element::Link(link) => {
+            let page = link.path.trim_start_matches("file:");
+            match &link.desc {
+                Some(desc) => output.push_str(&format!("[{}]([[{}]])", desc, page)),
+                None => output.push_str(&format!("[[{}]]", page)),
+            }
+        }
+        Element::FnRef(fn_ref) => match &fn_ref.definition {
+            Some(definition) => {
+                let note = Org::parse(definition);
+                let mut note_notes = Vec::new();
+                let string = note
+                    .document()
+                    .section_node()
+                    .map(|section| inline_text(&note, section, &mut note_notes))
+                    .unwrap_or_default();
+                notes.push(block(string, note_notes));
+            }
+            None => {
+                let definition = org
+                    .root
+                    .descendants(&org.arena)
+                    .find(|&n| matches!(&org[n], Element::FnDef(def) if def.label == fn_ref.label));
+                if let Some(definition) = definition {
+                    let mut note_notes = Vec::new();
+                    let string = inline_text(org, definition, &mut note_notes);
+                    notes.push(block(string, note_notes));
+                }
+            }
+        },
+        Element::Cookie(cookie) => output.push_str(&cookie.value),
+        Element::InlineSrc(inline_src) => {
+            output.push('`');
+            output.push_str(&inline_src.body);
+            output.push('`');
+        }
+        _ => (),
+    }
+}
+
+fn write_emphasis(
+    org: &Org,
+    node: NodeId,
+    output: &mut String,
+    notes: &mut Vec<Value>,
+    marker: &str,
+) {
+    output.push_str(marker);
+    output.push_str(&inline_text(org, node, notes));
+    output.push_str(marker);
+}
+
+#[test]
+fn to_roam_research_json() {
+    assert_eq!(Org::parse("").to_roam_research_json(), "[]");
+    assert_eq!(Org::parse("intro\n").to_roam_research_json(), "[]");
+
+    let org =
+        Org::parse("* \"Été\" 日本\n[[https://example.com][site]] ~x~ [[Rust][the language]]\n\n[fn:1] orphan\n* Empty\n");
+    let pages: Value = serde_json::from_str(&org.to_roam_research_json()).unwrap();
+    assert_eq!(
+        pages,
+        json!([
+            {
+                "title": "\"Été\" 日本",
+                "children": [{ "string": "[site](https://example.com) `x` [the language]([[Rust]])" }]
+            },
+            { "title": "Empty", "children": [] }
+        ])
+    );
+}
@@ -0,0 +1,129 @@
+use crate::elements::{Timestamp, Title};
+use crate::export::HtmlEscape;
+use crate::headline::url_encode;
+use crate::Org;
+
+impl Org<'_> {
+    /// Generates an XML sitemap for a site with one page per level-1
+    /// headline, like those written by [`to_obsidian_vault`].
+    ///
+    /// Each page is located at `base_url/name/`, where `name` is the
+    /// `EXPORT_FILE_NAME` property of the headline, or its title in lowercase
+    /// with other characters than letters and digits replaced by `-`, which
+    /// is percent-encoded in the url. Its
+    /// `<lastmod>` is the date of the `CLOSED` planning timestamp, or of the
+    /// `CREATED` property.
+    ///
+    /// If `published_headlines_only` is `true`, headlines with a todo keyword
+    /// other than `DONE`, and commented or archived headlines, are skipped.
+    ///
+    /// [`to_obsidian_vault`]: Org::to_obsidian_vault
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse(
+    ///     "* DONE Hello, World!\nCLOSED: [2021-03-04 Thu 10:00]\n\
+    ///      * TODO Draft\n\
+    ///      * About\n:PROPERTIES:\n:EXPORT_FILE_NAME: about-me\n:END:\n",
+    /// );
+    ///
+    /// assert_eq!(
+    ///     org.sitemap("https://example.com/", true),
+    ///     "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+    ///      <urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n\
+    ///      <url><loc>https://example.com/hello-world/</loc><lastmod>2021-03-04</lastmod></url>\n\
+    ///      <url><loc>https://example.com/about-me/</loc></url>\n\
+    ///      </urlset>\n"
+    /// );
+    /// ```
+    pub fn sitemap(&self, base_url: &str, published_headlines_only: bool) -> String {
+        let base_url = base_url.trim_end_matches('/');
+
+        let mut output = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+        );
+        for headline in self.document().children(self) {
+            let title = headline.title(self);
+            if published_headlines_only && !is_published(title) {
+                continue;
+            }
+
+            let path: Vec<_> = page_name(title).split('/').map(url_encode).collect();
+            let loc = format!("{}/{}/", base_url, path.join("/"));
+            output.push_str(&format!("<url><loc>{}</loc>", HtmlEscape(&loc)));
+            if let Some(lastmod) = lastmod(title) {
+                output.push_str(&format!("<lastmod>{}</lastmod>", lastmod));
+            }
+            output.push_str("</url>\n");
+        }
+        output.push_str("</urlset>\n");
+        output
+    }
+}
+
+fn is_published(title: &Title) -> bool {
+    title.keyword.iter().all(|keyword| keyword == "DONE")
+        && !title.is_commented()
+        && !title.is_archived()
+}
+
+// `EXPORT_FILE_NAME` property, or slugified title
+pub(crate) fn page_name(title: &Title) -> String {
+    let name = title
+        .properties
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case("EXPORT_FILE_NAME"))
+        .map(|(_, value)| value.trim());
+    if let Some(name) = name.filter(|name| !name.is_empty()) {
+        return name.to_string();
+    }
+
+    let raw = title.raw.to_lowercase();
+    let words: Vec<_> = raw
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect();
+    words.join("-")
+}
+
+fn lastmod(title: &Title) -> Option<String> {
+    if let Some(closed) = title.closed() {
+        return timestamp_date(closed);
+    }
+    let (_, created) = title
+        .properties
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case("CREATED"))?;
+    let (_, timestamp) = Timestamp::parse_inactive(created.trim())
+        .or_else(|| Timestamp::parse_active(created.trim()))?;
+    timestamp_date(&timestamp)
+}
+
+fn timestamp_date(timestamp: &Timestamp) -> Option<String> {
+    match timestamp {
+        Timestamp::Active { start, .. }
+        | Timestamp::Inactive { start, .. }
+        | Timestamp::ActiveRange { start, .. }
+        | Timestamp::InactiveRange { start, .. } => Some(format!(
+            "{}-{:02}-{:02}",
+            start.year, start.month, start.day
+        )),
+        Timestamp::Diary { .. } => None,
+    }
+}
+
+#[test]
+fn sitemap() {
+    let org =
+        Org::parse("* Été à Zürich\n* Notes\n:PROPERTIES:\n:EXPORT_FILE_NAME: blog/a&b c\n:END:\n");
+    assert_eq!(
+        org.sitemap("https://example.com", false),
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n\
+         <url><loc>https://example.com/%C3%A9t%C3%A9-%C3%A0-z%C3%BCrich/</loc></url>\n\
+         <url><loc>https://example.com/blog/a%26b%20c/</loc></url>\n\
+         </urlset>\n"
+    );
+}
@@ -0,0 +1,199 @@
+use crate::elements::Timestamp;
+use crate::export::write_timestamp;
+use crate::Org;
+
+// columns of `headlines` table, besides the ones for properties
+const COLUMNS: [(&str, &str); 10] = [
+    ("headline_id", "INTEGER PRIMARY KEY"),
+    ("parent_id", "INTEGER REFERENCES headlines (headline_id)"),
+    ("level", "INTEGER NOT NULL"),
+    ("keyword", "TEXT"),
+    ("priority", "TEXT"),
+    ("title", "TEXT NOT NULL"),
+    ("tags", "TEXT"),
+    ("scheduled", "TEXT"),
+    ("deadline", "TEXT"),
+    ("closed", "TEXT"),
+];
+
+impl Org<'_> {
+    /// Generates a SQLite DDL script creating a `headlines` table for this
+    /// document.
+    ///
+    /// Each headline is mapped to one row, with a column for each property
+    /// key found anywhere in the document. Property keys are compared
+    /// case-insensitively, and those colliding with built-in columns, like
+    /// `level` or `title`, are skipped.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse("* Task\n:PROPERTIES:\n:EFFORT: 1:00\n:END:\n** Sub\n:PROPERTIES:\n:ID: 42\n:END:\n");
+    ///
+    /// assert_eq!(
+    ///     org.to_sql_schema(),
+    ///     "CREATE TABLE headlines (\n\
+    ///     \x20 headline_id INTEGER PRIMARY KEY,\n\
+    ///     \x20 parent_id INTEGER REFERENCES headlines (headline_id),\n\
+    ///     \x20 level INTEGER NOT NULL,\n\
+    ///     \x20 keyword TEXT,\n\
+    ///     \x20 priority TEXT,\n\
+    ///     \x20 title TEXT NOT NULL,\n\
+    ///     \x20 tags TEXT,\n\
+    ///     \x20 scheduled TEXT,\n\
+    ///     \x20 deadline TEXT,\n\
+    ///     \x20 closed TEXT,\n\
+    ///     \x20 \"EFFORT\" TEXT,\n\
+    ///     \x20 \"ID\" TEXT\n\
+    ///     );\n"
+    /// );
+    /// ```
+    pub fn to_sql_schema(&self) -> String {
+        let mut columns: Vec<_> = COLUMNS
+            .iter()
+            .map(|(name, definition)| format!("  {} {}", name, definition))
+            .collect();
+        for key in property_keys(self) {
+            columns.push(format!("  {} TEXT", identifier(&key)));
+        }
+
+        format!("CREATE TABLE headlines (\n{}\n);\n", columns.join(",\n"))
+    }
+
+    /// Generates `INSERT` statements populating the table created by
+    /// [`Org::to_sql_schema`] with all headlines.
+    ///
+    /// Headlines are numbered from 1 in document order. Tags are written
+    /// as `:tag1:tag2:`, and properties missing from a headline are `NULL`.
+    ///
+    /// [`Org::to_sql_schema`]: #method.to_sql_schema
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse("* TODO [#A] Bob's task :work:\n:PROPERTIES:\n:EFFORT: 1:00\n:END:\n** Sub\nDEADLINE: <2019-04-08 Mon>\n");
+    ///
+    /// assert_eq!(
+    ///     org.to_sql_inserts(),
+    ///     "INSERT INTO headlines (headline_id, parent_id, level, keyword, priority, title, tags, scheduled, deadline, closed, \"EFFORT\") \
+    ///      VALUES (1, NULL, 1, 'TODO', 'A', 'Bob''s task', ':work:', NULL, NULL, NULL, '1:00');\n\
+    ///      INSERT INTO headlines (headline_id, parent_id, level, keyword, priority, title, tags, scheduled, deadline, closed, \"EFFORT\") \
+    ///      VALUES (2, 1, 2, NULL, NULL, 'Sub', NULL, NULL, '<2019-04-08 Mon>', NULL, NULL);\n"
+    /// );
+    /// ```
+    pub fn to_sql_inserts(&self) -> String {
+        let keys = property_keys(self);
+        let headlines: Vec<_> = self.headlines().collect();
+
+        let mut columns: Vec<_> = COLUMNS.iter().map(|(name, _)| name.to_string()).collect();
+        columns.extend(keys.iter().map(|key| identifier(key)));
+        let columns = columns.join(", ");
+
+        let mut output = String::new();
+        for (index, headline) in headlines.iter().enumerate() {
+            let title = headline.title(self);
+            let parent = headline.parent(self).and_then(|parent| {
+                headlines
+                    .iter()
+                    .position(|h| h.headline_node() == parent.headline_node())
+            });
+
+            let mut values = vec![
+                (index + 1).to_string(),
+                parent.map_or_else(null, |parent| (parent + 1).to_string()),
+                title.level.to_string(),
+                title.keyword.as_deref().map_or_else(null, literal),
+                title
+                    .priority
+                    .map_or_else(null, |priority| literal(&priority.to_string())),
+                literal(&title.raw),
+                if title.tags.is_empty() {
+                    null()
+                } else {
+                    literal(&format!(":{}:", title.tags.join(":")))
+                },
+                title.scheduled().map_or_else(null, timestamp),
+                title.deadline().map_or_else(null, timestamp),
+                title.closed().map_or_else(null, timestamp),
+            ];
+            for key in &keys {
+                let value = title
+                    .properties
+                    .iter()
+                    .find(|(k, _)| k.eq_ignore_ascii_case(key))
+                    .map(|(_, value)| value);
+                values.push(value.map_or_else(null, |value| literal(value)));
+            }
+
+            output.push_str(&format!(
+                "INSERT INTO headlines ({}) VALUES ({});\n",
+                columns,
+                values.join(", ")
+            ));
+        }
+        output
+    }
+}
+
+// returns property keys in the order of their first appearance
+fn property_keys(org: &Org) -> Vec<String> {
+    let mut keys: Vec<String> = Vec::new();
+    for headline in org.headlines() {
+        for (key, _) in headline.title(org).properties.iter() {
+            if !COLUMNS
+                .iter()
+                .any(|(name, _)| name.eq_ignore_ascii_case(key))
+                && !keys.iter().any(|k| k.eq_ignore_ascii_case(key))
+            {
+                keys.push(key.to_string());
+            }
+        }
+    }
+    keys
+}
+
+fn identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+fn literal(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+fn null() -> String {
+    String::from("NULL")
+}
+
+fn timestamp(timestamp: &Timestamp) -> String {
+    let mut writer = Vec::new();
+    match write_timestamp(&mut writer, timestamp) {
+        Ok(()) => literal(&String::from_utf8_lossy(&writer)),
+        Err(_) => null(),
+    }
+}
+
+#[test]
+fn to_sql_schema() {
+    let schema = Org::parse("").to_sql_schema();
+    assert!(schema.ends_with("  closed TEXT\n);\n"));
+
+    // keys colliding with built-in columns or other keys are skipped
+    let org = Org::parse("* a\n:PROPERTIES:\n:Title: x\n:say \"hi\": y\n:Été: z\n:END:\n* b\n:PROPERTIES:\n:été: w\n:SAY \"HI\": v\n:END:\n");
+    assert!(org.to_sql_schema().ends_with(
+        "  closed TEXT,\n  \"say \"\"hi\"\"\" TEXT,\n  \"Été\" TEXT,\n  \"été\" TEXT\n);\n"
+    ));
+}
+
+#[test]
+fn to_sql_inserts() {
+    assert_eq!(Org::parse("").to_sql_inserts(), "");
+
+    let org = Org::parse("* l'été\n:PROPERTIES:\n:it's \"a\" key: 日本's\n:END:\n** b\n");
+    assert_eq!(
+        org.to_sql_inserts(),
+        "INSERT INTO headlines (headline_id, parent_id, level, keyword, priority, title, tags, scheduled, deadline, closed, \"it's \"\"a\"\" key\") \
+         VALUES (1, NULL, 1, NULL, NULL, 'l''été', NULL, NULL, NULL, NULL, '日本''s');\n\
+         INSERT INTO headlines (headline_id, parent_id, level, keyword, priority, title, tags, scheduled, deadline, closed, \"it's \"\"a\"\" key\") \
+         VALUES (2, 1, 2, NULL, NULL, 'b', NULL, NULL, NULL, NULL, NULL);\n"
+    );
+}
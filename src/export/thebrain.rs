@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+
+use serde_json::{json, Map, Value};
+
+use crate::Org;
+
+impl Org<'_> {
+    /// Exports headlines as thoughts of a TheBrain mind map.
+    ///
+    /// Returns a json object with a `thoughts` array and a `links` array.
+    /// Each headline becomes a thought, identified by its `ID` property, or
+    /// by its position in the document if absent. Todo keyword is used as
+    /// the thought type, and properties are exported as its metadata.
+    /// Headlines are connected to their parents by `child` links, and
+    /// `[[id:...]]` links in a headline become `jump` links to the target
+    /// thought.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    /// use serde_json::{json, Value};
+    ///
+    /// let org = Org::parse(
+    ///     "* TODO Project\n:PROPERTIES:\n:ID: p1\n:END:\n\
+    ///      ** Design\nSee [[id:p1][project]].\n",
+    /// );
+    /// let brain: Value = serde_json::from_str(&org.to_thebrain_json()).unwrap();
+    ///
+    /// assert_eq!(
+    ///     brain,
+    ///     json!({
+    ///         "thoughts": [
+    ///             { "id": "p1", "name": "Project", "type": "TODO", "metadata": { "ID": "p1" } },
+    ///             { "id": "2", "name": "Design", "metadata": {} }
+    ///         ],
+    ///         "links": [
+    ///             { "thoughtIdA": "p1", "thoughtIdB": "2", "relation": "child" },
+    ///             { "thoughtIdA": "2", "thoughtIdB": "p1", "relation": "jump" }
+    ///         ]
+    ///     })
+    /// );
+    /// ```
+    pub fn to_thebrain_json(&self) -> String {
+        let headlines: Vec<_> = self.headlines().collect();
+
+        // thought id of each headline
+        let ids: HashMap<_, _> = headlines
+            .iter()
+            .enumerate()
+            .map(|(index, headline)| {
+                let id = headline
+                    .title(self)
+                    .properties
+                    .iter()
+                    .find(|(key, _)| key.eq_ignore_ascii_case("ID"))
+                    .map(|(_, value)| value.to_string())
+                    .unwrap_or_else(|| (index + 1).to_string());
+                (headline.headline_node(), id)
+            })
+            .collect();
+
+        let mut thoughts = Vec::new();
+        let mut links = Vec::new();
+
+        for headline in &headlines {
+            let title = headline.title(self);
+            let id = &ids[&headline.headline_node()];
+
+            let mut thought = Map::new();
+            thought.insert("id".into(), json!(id));
+            thought.insert("name".into(), json!(title.raw.trim()));
+            if let Some(keyword) = &title.keyword {
+                thought.insert("type".into(), json!(keyword));
+            }
+            let metadata: Map<String, Value> = title
+                .properties
+                .iter()
+                .map(|(key, value)| (key.to_string(), json!(value)))
+                .collect();
+            thought.insert("metadata".into(), Value::Object(metadata));
+            thoughts.push(Value::Object(thought));
+
+            if let Some(parent) = headline.parent(self) {
+                links.push(json!({
+                    "thoughtIdA": ids[&parent.headline_node()],
+                    "thoughtIdB": id,
+                    "relation": "child",
+                }));
+            }
+
+            let nodes = Some(headline.title_node())
+                .into_iter()
+                .chain(headline.section_node());
+            for node in nodes {
+                for link in self.extract_links(node) {
+                    let target = match link.path.strip_prefix("id:") {
+                        Some(target) => target.trim(),
+                        None => continue,
+                    };
+                    if ids.values().any(|id| id == target) {
+                        links.push(json!({
+                            "thoughtIdA": id,
+                            "thoughtIdB": target,
+                            "relation": "jump",
+                        }));
+                    }
+                }
+            }
+        }
+
+        json!({ "thoughts": thoughts, "links": links }).to_string()
+    }
+}
@@ -0,0 +1,263 @@
+use indextree::NodeId;
+
+use crate::elements::{Element, Table};
+use crate::export::{fixed_width_contents, join_lines, table_columns, table_rows, write_timestamp};
+use crate::Org;
+
+impl Org<'_> {
+    /// Exports this document as a man page, using the troff `man` macros.
+    ///
+    /// `#+TITLE` and `#+DATE` make up the `.TH` title line, with the manual
+    /// section taken from `#+MAN_SECTION`, defaulting to `1`. Level-1
+    /// headlines become `.SH` sections and deeper headlines `.SS`
+    /// subsections. Bold and code texts are written in bold, italic and
+    /// underlined texts in italic. Source and example blocks are indented
+    /// and unfilled, and tables are written for the `tbl` preprocessor.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse(
+    ///     "#+TITLE: greet\n* Name\ngreet - say *hello*\n\
+    ///      * Options\n- ~-v~ is /verbose/\n\
+    ///      ** Example\n#+BEGIN_SRC sh\ngreet -v\n.hidden\n#+END_SRC\n",
+    /// );
+    ///
+    /// assert_eq!(
+    ///     org.to_troff(),
+    ///     ".TH \"GREET\" \"1\"\n\
+    ///      .SH Name\n\
+    ///      .PP\n\
+    ///      greet - say \\fBhello\\fR\n\
+    ///      .SH Options\n\
+    ///      .IP \\(bu 2\n\
+    ///      \\fB-v\\fR is \\fIverbose\\fR\n\
+    ///      .SS Example\n\
+    ///      .PP\n\
+    ///      .RS 4\n\
+    ///      .nf\n\
+    ///      greet -v\n\
+    ///      \\&.hidden\n\
+    ///      .fi\n\
+    ///      .RE\n"
+    /// );
+    /// ```
+    pub fn to_troff(&self) -> String {
+        let mut output = String::new();
+        if let Some(title) = self.keyword_value("TITLE") {
+            let section = self.keyword_value("MAN_SECTION").unwrap_or("1");
+            output.push_str(&format!(
+                ".TH {} {}",
+                quote(&title.trim().to_uppercase()),
+                quote(section.trim())
+            ));
+            if let Some(date) = self.keyword_value("DATE") {
+                output.push(' ');
+                output.push_str(&quote(date.trim()));
+            }
+            output.push('\n');
+        }
+        write_children(self, self.root, &mut output);
+        new_line(&mut output);
+        output
+    }
+}
+
+fn write_children(org: &Org, node: NodeId, output: &mut String) {
+    for child in node.children(&org.arena) {
+        write_node(org, child, output);
+    }
+}
+
+fn write_node(org: &Org, node: NodeId, output: &mut String) {
+    match &org[node] {
+        Element::Document { .. }
+        | Element::Section
+        | Element::Headline { .. }
+        | Element::DynBlock(_)
+        | Element::SpecialBlock(_)
+        | Element::RadioTarget
+        | Element::Strike => write_children(org, node, output),
+        Element::Title(title) => {
+            let mut text = String::new();
+            write_children(org, node, &mut text);
+            new_line(output);
+            output.push_str(if title.level == 1 { ".SH " } else { ".SS " });
+            output.push_str(&join_lines(&text));
+            output.push('\n');
+        }
+        Element::Paragraph { .. } => {
+            request(output, ".PP");
+            write_children(org, node, output);
+        }
+        Element::QuoteBlock(_) | Element::CenterBlock(_) => {
+            request(output, ".RS 4");
+            write_children(org, node, output);
+            request(output, ".RE");
+        }
+        Element::VerseBlock(_) => {
+            request(output, ".RS 4");
+            request(output, ".nf");
+            write_children(org, node, output);
+            request(output, ".fi");
+            request(output, ".RE");
+        }
+        Element::List(list) => {
+            for (i, item) in node.children(&org.arena).enumerate() {
+                if list.ordered {
+                    request(output, &format!(".IP {}. 4", i + 1));
+                } else {
+                    request(output, ".IP \\(bu 2");
+                }
+
+                for (j, child) in item.children(&org.arena).enumerate() {
+                    // the first paragraph follows the item's request
+                    if j == 0 && matches!(org[child], Element::Paragraph { .. }) {
+                        write_children(org, child, output);
+                    } else {
+                        write_node(org, child, output);
+                    }
+                }
+            }
+        }
+        Element::Bold => write_font(org, node, output, "\\fB"),
+        Element::Italic | Element::Underline => write_font(org, node, output, "\\fI"),
+        Element::Code { value } | Element::Verbatim { value } => {
+            output.push_str("\\fB");
+            write_text(output, value);
+            output.push_str("\\fR");
+        }
+        Element::Text { value } => write_text(output, value),
+        Element::Link(link) => match &link.desc {
+            Some(desc) => {
+                write_text(output, desc);
+                write_text(output, &format!(" <{}>", link.path));
+            }
+            None => write_text(output, &link.path),
+        },
+        Element::Timestamp(timestamp) => {
+            let mut writer = Vec::new();
+            if write_timestamp(&mut writer, timestamp).is_ok() {
+                write_text(output, &String::from_utf8_lossy(&writer));
+            }
+        }
+        Element::Cookie(cookie) => write_text(output, &cookie.value),
+        Element::InlineSrc(inline_src) => {
+            output.push_str("\\fB");
+            write_text(output, &inline_src.body);
+            output.push_str("\\fR");
+        }
+        Element::Snippet(snippet) => {
+            if snippet.name.eq_ignore_ascii_case("man") {
+                output.push_str(&snippet.value);
+            }
+        }
+        Element::ExportBlock(block) => {
+            if block.data.eq_ignore_ascii_case("man") {
+                new_line(output);
+                output.push_str(&block.contents);
+            }
+        }
+        Element::SourceBlock(block) => write_unfilled(output, &block.contents),
+        Element::ExampleBlock(block) => write_unfilled(output, &block.contents),
+        Element::FixedWidth(fixed_width) => {
+            write_unfilled(output, &fixed_width_contents(&fixed_width.value))
+        }
+        Element::Table(Table::Org { .. }) => write_table(org, node, output),
+        Element::Table(Table::TableEl { value, .. }) => write_unfilled(output, value),
+        Element::BabelCall(_)
+        | Element::Clock(_)
+        | Element::Comment(_)
+        | Element::CommentBlock(_)
+        | Element::Drawer(_)
+        | Element::FnDef(_)
+        | Element::FnRef(_)
+        | Element::InlineCall(_)
+        | Element::Keyword(_)
+        | Element::ListItem(_)
+        | Element::Macros(_)
+        | Element::Rule(_)
+        | Element::Target(_)
+        | Element::TableRow(_)
+        | Element::TableCell(_) => (),
+    }
+}
+
+fn write_font(org: &Org, node: NodeId, output: &mut String, font: &str) {
+    output.push_str(font);
+    write_children(org, node, output);
+    output.push_str("\\fR");
+}
+
+fn write_unfilled(output: &mut String, contents: &str) {
+    request(output, ".PP");
+    request(output, ".RS 4");
+    request(output, ".nf");
+    // leading spaces are kept in no-fill mode
+    for line in contents.lines() {
+        if line.starts_with('.') || line.starts_with('\'') {
+            output.push_str("\\&");
+        }
+        output.push_str(&line.replace('\\', "\\e"));
+        output.push('\n');
+    }
+    request(output, ".fi");
+    request(output, ".RE");
+}
+
+fn write_table(org: &Org, node: NodeId, output: &mut String) {
+    let columns = table_columns(org, node);
+
+    request(output, ".TS");
+    output.push_str("allbox tab(\t);\n");
+    output.push_str(&vec!["l"; columns.max(1)].join(" "));
+    output.push_str(".\n");
+    for row in table_rows(org, node) {
+        let cells: Vec<_> = row
+            .children(&org.arena)
+            .map(|cell| {
+                let mut contents = String::new();
+                write_children(org, cell, &mut contents);
+                contents.trim().replace('\t', " ")
+            })
+            .collect();
+        output.push_str(&cells.join("\t"));
+        output.push('\n');
+    }
+    request(output, ".TE");
+}
+
+// writes `text`, escaping backslashes and control characters at line starts
+fn write_text(output: &mut String, text: &str) {
+    for c in text.chars() {
+        let line_start = output.is_empty() || output.ends_with('\n');
+        if line_start && c.is_whitespace() {
+            // leading spaces would cause a break
+            continue;
+        }
+        if line_start && (c == '.' || c == '\'') {
+            output.push_str("\\&");
+        }
+        if c == '\\' {
+            output.push_str("\\e");
+        } else {
+            output.push(c);
+        }
+    }
+}
+
+fn request(output: &mut String, request: &str) {
+    new_line(output);
+    output.push_str(request);
+    output.push('\n');
+}
+
+fn new_line(output: &mut String) {
+    if !output.is_empty() && !output.ends_with('\n') {
+        output.push('\n');
+    }
+}
+
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('"', "\"\""))
+}
@@ -0,0 +1,298 @@
+use indextree::NodeId;
+
+use crate::elements::{Element, Table};
+use crate::export::{fixed_width_contents, is_image, table_columns, table_rows, write_timestamp};
+use crate::Org;
+
+impl Org<'_> {
+    /// Exports this document as [Typst] markup.
+    ///
+    /// Headlines become `=` headings, source blocks become raw blocks, and
+    /// tables use the `#table` function. `#+TITLE` and `#+AUTHOR` are set as
+    /// document metadata. LaTeX math fragments, like `\(x^2\)` or `$x^2$`, are
+    /// written as Typst math, without converting their contents.
+    ///
+    /// [Typst]: https://typst.app/docs/reference/syntax/
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse(
+    ///     "#+TITLE: Notes\n* Intro\n*Bold*, /italic/ and \\(x^2\\) for 5$.\n\
+    ///      - one\n  - two\n\
+    ///      #+BEGIN_SRC rust\nfn main() {}\n#+END_SRC\n\
+    ///      | a | b |\n|---+---|\n| 1 | 2 |\n",
+    /// );
+    ///
+    /// assert_eq!(
+    ///     org.to_typst(),
+    ///     "#set document(title: \"Notes\")\n\n\
+    ///      = Intro\n\n\
+    ///      *Bold*, _italic_ and $x^2$ for 5\\$.\n\n\
+    ///      - one\n  - two\n\n\
+    ///      ```rust\nfn main() {}\n```\n\n\
+    ///      #table(\n  columns: 2,\n  [a], [b],\n  [1], [2],\n)\n"
+    /// );
+    /// ```
+    pub fn to_typst(&self) -> String {
+        let mut metadata = Vec::new();
+        for (key, name) in [("TITLE", "title"), ("AUTHOR", "author")].iter() {
+            if let Some(value) = self.keyword_value(key) {
+                metadata.push(format!("{}: {}", name, string(value.trim())));
+            }
+        }
+
+        let mut output = String::new();
+        if !metadata.is_empty() {
+            output.push_str(&format!("#set document({})\n\n", metadata.join(", ")));
+        }
+        write_children(self, self.root, &mut output);
+        output.truncate(output.trim_end().len());
+        output.push('\n');
+        output
+    }
+}
+
+fn write_children(org: &Org, node: NodeId, output: &mut String) {
+    for child in node.children(&org.arena) {
+        write_node(org, child, output);
+    }
+}
+
+fn write_node(org: &Org, node: NodeId, output: &mut String) {
+    match &org[node] {
+        Element::Document { .. }
+        | Element::Section
+        | Element::Headline { .. }
+        | Element::DynBlock(_)
+        | Element::SpecialBlock(_)
+        | Element::ListItem(_)
+        | Element::RadioTarget => write_children(org, node, output),
+        Element::Title(title) => {
+            output.push_str(&"=".repeat(title.level));
+            output.push(' ');
+            write_children(org, node, output);
+            output.push_str("\n\n");
+        }
+        Element::Paragraph { .. } => {
+            write_children(org, node, output);
+            output.truncate(output.trim_end().len());
+            output.push_str("\n\n");
+        }
+        Element::QuoteBlock(_) => write_function(org, node, output, "quote(block: true)"),
+        Element::CenterBlock(_) => write_function(org, node, output, "align(center)"),
+        Element::VerseBlock(_) => {
+            let mut contents = String::new();
+            write_children(org, node, &mut contents);
+            for line in contents.trim().lines() {
+                output.push_str(line);
+                output.push_str(" \\\n");
+            }
+            output.push('\n');
+        }
+        Element::List(list) => {
+            let marker = if list.ordered { "+ " } else { "- " };
+            for item in node.children(&org.arena) {
+                let mut contents = String::new();
+                write_children(org, item, &mut contents);
+                output.push_str(marker);
+                // blank lines are dropped to keep the list tight
+                for (i, line) in contents.lines().filter(|l| !l.is_empty()).enumerate() {
+                    if i > 0 {
+                        output.push_str("  ");
+                    }
+                    output.push_str(line);
+                    output.push('\n');
+                }
+                if contents.trim().is_empty() {
+                    output.push('\n');
+                }
+            }
+            output.push('\n');
+        }
+        Element::Bold => write_markup(org, node, output, "*", "*"),
+        Element::Italic => write_markup(org, node, output, "_", "_"),
+        Element::Underline => write_markup(org, node, output, "#underline[", "]"),
+        Element::Strike => write_markup(org, node, output, "#strike[", "]"),
+        Element::Code { value } | Element::Verbatim { value } => write_raw(output, value),
+        Element::Text { value } => write_text(output, value),
+        Element::Link(link) => {
+            let path = link.path.trim_start_matches("file:");
+            if is_image(&link.path) {
+                output.push_str(&format!("#image({})", string(path)));
+            } else {
+                output.push_str(&format!("#link({})", string(&link.path)));
+                if let Some(desc) = &link.desc {
+                    output.push('[');
+                    output.push_str(&escape(desc));
+                    output.push(']');
+                }
+            }
+        }
+        Element::Timestamp(timestamp) => {
+            let mut writer = Vec::new();
+            if write_timestamp(&mut writer, timestamp).is_ok() {
+                output.push_str(&escape(&String::from_utf8_lossy(&writer)));
+            }
+        }
+        Element::Cookie(cookie) => output.push_str(&escape(&cookie.value)),
+        Element::InlineSrc(inline_src) => write_raw(output, &inline_src.body),
+        Element::Snippet(snippet) => {
+            if snippet.name.eq_ignore_ascii_case("typst") {
+                output.push_str(&snippet.value);
+            }
+        }
+        Element::ExportBlock(block) => {
+            if block.data.eq_ignore_ascii_case("typst") {
+                output.push_str(&block.contents);
+                output.push('\n');
+            }
+        }
+        Element::FnRef(fn_ref) => {
+            if let Some(definition) = &fn_ref.definition {
+                output.push_str("#footnote[");
+                output.push_str(&escape(definition));
+                output.push(']');
+            }
+        }
+        Element::SourceBlock(block) => write_raw_block(output, &block.language, &block.contents),
+        Element::ExampleBlock(block) => write_raw_block(output, "", &block.contents),
+        Element::FixedWidth(fixed_width) => {
+            write_raw_block(output, "", &fixed_width_contents(&fixed_width.value))
+        }
+        Element::Table(Table::Org { .. }) => write_table(org, node, output),
+        Element::Table(Table::TableEl { value, .. }) => write_raw_block(output, "", value),
+        Element::Rule(_) => output.push_str("#line(length: 100%)\n\n"),
+        Element::BabelCall(_)
+        | Element::Clock(_)
+        | Element::Comment(_)
+        | Element::CommentBlock(_)
+        | Element::Drawer(_)
+        | Element::FnDef(_)
+        | Element::InlineCall(_)
+        | Element::Keyword(_)
+        | Element::Macros(_)
+        | Element::Target(_)
+        | Element::TableRow(_)
+        | Element::TableCell(_) => (),
+    }
+}
+
+fn write_markup(org: &Org, node: NodeId, output: &mut String, start: &str, end: &str) {
+    output.push_str(start);
+    write_children(org, node, output);
+    output.push_str(end);
+}
+
+fn write_function(org: &Org, node: NodeId, output: &mut String, call: &str) {
+    output.push('#');
+    output.push_str(call);
+    output.push_str("[\n");
+    write_children(org, node, output);
+    output.truncate(output.trim_end().len());
+    output.push_str("\n]\n\n");
+}
+
+fn write_raw(output: &mut String, value: &str) {
+    if value.contains('`') {
+        output.push_str(&format!("#raw({})", string(value)));
+    } else {
+        output.push('`');
+        output.push_str(value);
+        output.push('`');
+    }
+}
+
+fn write_raw_block(output: &mut String, language: &str, contents: &str) {
+    output.push_str("```");
+    output.push_str(language);
+    output.push('\n');
+    output.push_str(contents);
+    if !contents.ends_with('\n') {
+        output.push('\n');
+    }
+    output.push_str("```\n\n");
+}
+
+fn write_table(org: &Org, node: NodeId, output: &mut String) {
+    let columns = table_columns(org, node);
+
+    output.push_str(&format!("#table(\n  columns: {},\n", columns));
+    for row in table_rows(org, node) {
+        output.push(' ');
+        for cell in row.children(&org.arena) {
+            let mut contents = String::new();
+            write_children(org, cell, &mut contents);
+            output.push_str(" [");
+            output.push_str(contents.trim());
+            output.push_str("],");
+        }
+        output.push('\n');
+    }
+    output.push_str(")\n\n");
+}
+
+// writes text, converting LaTeX math fragments into Typst math
+fn write_text(output: &mut String, text: &str) {
+    let mut rest = text;
+    while !rest.is_empty() {
+        let fragment = [("\\(", "\\)"), ("\\[", "\\]"), ("$", "$")]
+            .iter()
+            .filter_map(|(open, close)| {
+                let start = rest.find(open)?;
+                let contents = &rest[start + open.len()..];
+                let end = contents.find(close)?;
+                let math = &contents[..end];
+                if math.trim().is_empty() || (*open == "$" && !is_dollar_math(math)) {
+                    return None;
+                }
+                Some((
+                    start,
+                    start + open.len() + end + close.len(),
+                    math,
+                    *open == "\\[",
+                ))
+            })
+            .min_by_key(|(start, ..)| *start);
+
+        match fragment {
+            Some((start, end, math, display)) => {
+                output.push_str(&escape(&rest[..start]));
+                if display {
+                    output.push_str(&format!("$ {} $", math.trim()));
+                } else {
+                    output.push_str(&format!("${}$", math));
+                }
+                rest = &rest[end..];
+            }
+            None => {
+                output.push_str(&escape(rest));
+                break;
+            }
+        }
+    }
+}
+
+// `$` only delimits math if not followed or preceded by whitespace inside
+fn is_dollar_math(math: &str) -> bool {
+    !math.starts_with(char::is_whitespace) && !math.ends_with(char::is_whitespace)
+}
+
+fn escape(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(
+            c,
+            '\\' | '*' | '_' | '`' | '$' | '#' | '<' | '>' | '@' | '[' | ']' | '~'
+        ) {
+            output.push('\\');
+        }
+        output.push(c);
+    }
+    output
+}
+
+// writes a Typst string literal
+fn string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
@@ -0,0 +1,79 @@
+use std::path::Path;
+
+use rust_xlsxwriter::{ConditionalFormatFormula, Format, Workbook, XlsxError};
+
+use crate::export::csv::outline_rows;
+use crate::export::OutlineField;
+use crate::Org;
+
+impl Org<'_> {
+    /// Exports headlines of this document as an Excel workbook at `path`.
+    ///
+    /// The worksheet has the columns of [`Org::to_csv_outline`] with the
+    /// `Level`, `Title`, `Keyword`, `Priority`, `Tags`, `Deadline` and
+    /// `Scheduled` fields, and one row per headline. Titles are indented by
+    /// their level, column widths are fitted to their contents, and rows of
+    /// `DONE` headlines are struck through.
+    ///
+    /// [`Org::to_csv_outline`]: #method.to_csv_outline
+    ///
+    /// ```rust,no_run
+    /// use orgize::Org;
+    /// use std::path::Path;
+    ///
+    /// let org = Org::parse("* DONE Plan\n** TODO Steps\n");
+    /// org.to_excel(Path::new("outline.xlsx")).unwrap();
+    /// ```
+    pub fn to_excel(&self, path: &Path) -> Result<(), XlsxError> {
+        let fields = [
+            OutlineField::Level,
+            OutlineField::Title,
+            OutlineField::Keyword,
+            OutlineField::Priority,
+            OutlineField::Tags,
+            OutlineField::Deadline,
+            OutlineField::Scheduled,
+        ];
+        let rows = outline_rows(self, &fields);
+
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.add_worksheet();
+        let header = Format::new().set_bold();
+
+        for (i, row) in rows.iter().enumerate() {
+            let level: u8 = row[0].parse().unwrap_or(1);
+            for (j, value) in row.iter().enumerate() {
+                let (i, j) = (i as u32, j as u16);
+                if i == 0 {
+                    worksheet.write_string_with_format(i, j, value, &header)?;
+                } else if j == 0 {
+                    worksheet.write_number(i, j, level)?;
+                } else if j == 1 {
+                    let indent = Format::new().set_indent(level.saturating_sub(1));
+                    worksheet.write_string_with_format(i, j, value, &indent)?;
+                } else if !value.is_empty() {
+                    worksheet.write_string(i, j, value)?;
+                }
+            }
+        }
+
+        if rows.len() > 1 {
+            // the third column contains todo keywords
+            let done = ConditionalFormatFormula::new()
+                .set_rule("=$C2=\"DONE\"")
+                .set_format(Format::new().set_font_strikethrough());
+            worksheet.add_conditional_format(
+                1,
+                0,
+                rows.len() as u32 - 1,
+                fields.len() as u16 - 1,
+                &done,
+            )?;
+        }
+
+        worksheet.set_freeze_panes(1, 0)?;
+        worksheet.autofit();
+
+        workbook.save(path)
+    }
+}
@@ -0,0 +1,295 @@
+use indextree::{NodeEdge, NodeId};
+
+use crate::config::ParseConfig;
+use crate::elements::{Element, FnDef, FnRef};
+use crate::export::{DefaultOrgHandler, OrgHandler};
+use crate::parsers::{parse_container, Container, OwnedArena};
+use crate::Org;
+
+impl Org<'_> {
+    /// Renumbers labelled footnotes to 1, 2, 3, ..., in the order of their
+    /// first appearance, updating both references and definitions.
+    ///
+    /// Anonymous inline footnotes, like `[fn::definition]`, are not changed.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let mut org = Org::parse("A[fn:z] B[fn:3] C[fn::anonymous] D[fn:z]\n\n[fn:3] Three.\n\n[fn:z] Zed.\n");
+    /// org.renumber_footnotes();
+    ///
+    /// let mut writer = Vec::new();
+    /// org.write_org(&mut writer).unwrap();
+    /// assert_eq!(
+    ///     String::from_utf8(writer).unwrap(),
+    ///     "A[fn:1] B[fn:2] C[fn::anonymous] D[fn:1]\n\n[fn:2] Three.\n\n[fn:1] Zed.\n"
+    /// );
+    /// ```
+    pub fn renumber_footnotes(&mut self) {
+        let mut labels: Vec<String> = Vec::new();
+        let nodes: Vec<_> = self.root.descendants(&self.arena).collect();
+        for &node in &nodes {
+            let label = match &self[node] {
+                Element::FnRef(FnRef { label, .. }) | Element::FnDef(FnDef { label, .. }) => label,
+                _ => continue,
+            };
+            if !label.is_empty() && !labels.iter().any(|l| l == label) {
+                labels.push(label.to_string());
+            }
+        }
+
+        for node in nodes {
+            let label = match &mut self[node] {
+                Element::FnRef(FnRef { label, .. }) | Element::FnDef(FnDef { label, .. }) => label,
+                _ => continue,
+            };
+            if let Some(index) = labels.iter().position(|l| l == label) {
+                *label = (index + 1).to_string().into();
+            }
+        }
+    }
+
+    /// Converts labelled footnotes into inline footnotes, and removes their
+    /// definitions.
+    ///
+    /// A footnote referenced only once becomes an anonymous footnote, like
+    /// `[fn::definition]`. Otherwise, its first reference becomes
+    /// `[fn:label:definition]`, and the others are kept as they are.
+    /// Definitions without any reference are kept.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let mut org = Org::parse("A[fn:1] B[fn:2] C[fn:2]\n\n[fn:1] One, /with/ markup.\n\n[fn:2] Two.\n");
+    /// org.inline_footnotes();
+    ///
+    /// let mut writer = Vec::new();
+    /// org.write_org(&mut writer).unwrap();
+    /// assert_eq!(
+    ///     String::from_utf8(writer).unwrap(),
+    ///     "A[fn::One, /with/ markup.] B[fn:2:Two.] C[fn:2]\n\n"
+    /// );
+    /// ```
+    pub fn inline_footnotes(&mut self) {
+        let nodes: Vec<_> = self.root.descendants(&self.arena).collect();
+
+        for &def_n in &nodes {
+            let label = match &self[def_n] {
+                Element::FnDef(FnDef { label, .. }) => label.to_string(),
+                _ => continue,
+            };
+            let references: Vec<_> = nodes
+                .iter()
+                .copied()
+                .filter(|&node| {
+                    matches!(
+                        &self[node],
+                        Element::FnRef(FnRef { label: l, definition: None }) if *l == label
+                    )
+                })
+                .collect();
+            if references.is_empty() {
+                continue;
+            }
+
+            let definition = to_org_inline(self, def_n);
+            if let Element::FnRef(fn_ref) = &mut self[references[0]] {
+                if references.len() == 1 {
+                    fn_ref.label = "".into();
+                }
+                fn_ref.definition = Some(definition.into());
+            }
+            def_n.detach(&mut self.arena);
+        }
+    }
+
+    /// Converts inline footnotes into footnote references, and appends their
+    /// definitions to the end of the document.
+    ///
+    /// Anonymous footnotes, like `[fn::definition]`, are given sequential
+    /// numeric labels, skipping labels already in use. This is the inverse of
+    /// [`Org::inline_footnotes`].
+    ///
+    /// [`Org::inline_footnotes`]: #method.inline_footnotes
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let mut org = Org::parse("* A\nB[fn:1] C[fn::Two.]\n\n[fn:1] One.\n\n* D\nE[fn::Three.]\n");
+    /// org.externalize_footnotes();
+    ///
+    /// let mut writer = Vec::new();
+    /// org.write_org(&mut writer).unwrap();
+    /// assert_eq!(
+    ///     String::from_utf8(writer).unwrap(),
+    ///     "* A\nB[fn:1] C[fn:2]\n\n[fn:1] One.\n\n* D\nE[fn:3]\n\n[fn:2] Two.\n\n[fn:3] Three.\n"
+    /// );
+    /// ```
+    pub fn externalize_footnotes(&mut self) {
+        let nodes: Vec<_> = self.root.descendants(&self.arena).collect();
+        let mut labels: Vec<String> = nodes
+            .iter()
+            .filter_map(|&node| match &self[node] {
+                Element::FnRef(FnRef { label, .. }) | Element::FnDef(FnDef { label, .. }) => {
+                    Some(label.to_string())
+                }
+                _ => None,
+            })
+            .collect();
+
+        let mut definitions = String::new();
+        let mut number = 0;
+        for node in nodes {
+            let fn_ref = match &mut self[node] {
+                Element::FnRef(fn_ref) if fn_ref.definition.is_some() => fn_ref,
+                _ => continue,
+            };
+            if fn_ref.label.is_empty() {
+                let label = loop {
+                    number += 1;
+                    let label = number.to_string();
+                    if !labels.contains(&label) {
+                        break label;
+                    }
+                };
+                labels.push(label.clone());
+                fn_ref.label = label.into();
+            }
+            if !definitions.is_empty() {
+                definitions.push('\n');
+            }
+            definitions.push_str(&format!(
+                "[fn:{}] {}\n",
+                fn_ref.label,
+                fn_ref.definition.take().unwrap_or_default()
+            ));
+        }
+
+        if definitions.is_empty() {
+            return;
+        }
+
+        let sec_n = self.last_section();
+        // separates the definitions from previous element by a blank line
+        if let Some(post_blank) = sec_n
+            .last_child(&self.arena)
+            .and_then(|last| self[last].post_blank_mut())
+        {
+            *post_blank = (*post_blank).max(1);
+        }
+        parse_container(
+            &mut OwnedArena::new(&mut self.arena),
+            Container::Block {
+                node: sec_n,
+                content: &definitions,
+            },
+            &ParseConfig::default(),
+        );
+
+        self.debug_validate();
+    }
+
+    // returns the section at the end of the document, creating it if needed
+    fn last_section(&mut self) -> NodeId {
+        let mut parent = self.root;
+        loop {
+            match parent.last_child(&self.arena) {
+                Some(node) if matches!(self[node], Element::Section) => return node,
+                Some(node) if matches!(self[node], Element::Headline { .. }) => parent = node,
+                Some(node) => {
+                    // headline without section, `node` is its title
+                    let sec_n = self.arena.new_node(Element::Section);
+                    node.insert_after(sec_n, &mut self.arena);
+                    return sec_n;
+                }
+                None => {
+                    let sec_n = self.arena.new_node(Element::Section);
+                    parent.append(sec_n, &mut self.arena);
+                    return sec_n;
+                }
+            }
+        }
+    }
+}
+
+// writes the contents of `node` in org syntax, joined into a single line
+fn to_org_inline(org: &Org, node: NodeId) -> String {
+    let mut handler = DefaultOrgHandler;
+    let mut writer = Vec::new();
+    for edge in node.traverse(&org.arena).skip(1) {
+        let _ = match edge {
+            NodeEdge::Start(n) if n != node => handler.start(&mut writer, &org[n]),
+            NodeEdge::End(n) if n != node => handler.end(&mut writer, &org[n]),
+            _ => Ok(()),
+        };
+    }
+    String::from_utf8_lossy(&writer)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[test]
+fn renumber_footnotes() {
+    let renumber = |source: &str| {
+        let mut org = Org::parse(source);
+        org.renumber_footnotes();
+        let mut writer = Vec::new();
+        org.write_org(&mut writer).unwrap();
+        String::from_utf8(writer).unwrap()
+    };
+
+    assert_eq!(renumber(""), "");
+    // labels are swapped, and unreferenced definitions are numbered too
+    assert_eq!(
+        renumber("A[fn:2] B[fn:1]\n\n[fn:orphan] Orphan.\n\n[fn:1] One.\n"),
+        "A[fn:1] B[fn:2]\n\n[fn:3] Orphan.\n\n[fn:2] One.\n"
+    );
+    assert_eq!(renumber("A[fn:x:日本] B[fn:x]\n"), "A[fn:1:日本] B[fn:1]\n");
+    // non-ascii labels are not footnotes
+    assert_eq!(renumber("A[fn:été]\n"), "A[fn:été]\n");
+}
+
+#[test]
+fn inline_footnotes() {
+    let inline = |source: &str| {
+        let mut org = Org::parse(source);
+        org.inline_footnotes();
+        let mut writer = Vec::new();
+        org.write_org(&mut writer).unwrap();
+        String::from_utf8(writer).unwrap()
+    };
+
+    assert_eq!(inline(""), "");
+    // unreferenced definitions and inline footnotes are kept
+    assert_eq!(
+        inline("A[fn::été]\n\n[fn:1] Orphan.\n"),
+        "A[fn::été]\n\n[fn:1] Orphan.\n"
+    );
+    // whitespace of definitions is collapsed
+    assert_eq!(
+        inline("A[fn:1]\n\n[fn:1] 日本  語\t*bold*\n"),
+        "A[fn::日本 語 *bold*]\n\n"
+    );
+}
+
+#[test]
+fn externalize_footnotes() {
+    let externalize = |source: &str| {
+        let mut org = Org::parse(source);
+        org.externalize_footnotes();
+        let mut writer = Vec::new();
+        org.write_org(&mut writer).unwrap();
+        String::from_utf8(writer).unwrap()
+    };
+
+    assert_eq!(externalize(""), "");
+    assert_eq!(externalize("A[fn:1]\n"), "A[fn:1]\n");
+    // labels in use are skipped, and named inline footnotes keep their label
+    assert_eq!(
+        externalize("A[fn::日本] B[fn:1] C[fn:x:été]\n"),
+        "A[fn:2] B[fn:1] C[fn:x]\n\n[fn:2] 日本\n\n[fn:x] été\n"
+    );
+    // definitions go into a new section of the last headline
+    assert_eq!(externalize("* A[fn::one]\n"), "* A[fn:1]\n[fn:1] one\n");
+}
@@ -1,16 +1,24 @@
-use indextree::NodeId;
+use indextree::{NodeEdge, NodeId};
 use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
 use std::ops::RangeInclusive;
+use std::path::Path;
 use std::usize;
 
 use crate::{
     config::ParseConfig,
-    elements::{Element, Title},
+    elements::{Element, Timestamp, Title},
+    export::{DefaultOrgHandler, OrgHandler},
+    org::offset_in,
     parsers::{parse_container, Container, OwnedArena},
     validate::{ValidationError, ValidationResult},
     Org,
 };
 
+/// Maximum level of headlines, deeper headlines are treated as inline tasks
+/// by org-mode.
+pub const MAX_HEADLINE_LEVEL: usize = 14;
+
 /// Represents the document in `Org` struct.
 ///
 /// Each `Org` struct only has one `Document`.
@@ -415,6 +423,34 @@ impl Headline {
         self.lvl
     }
 
+    /// Returns `true` if this headline is a level-1 headline.
+    pub fn is_top_level(self) -> bool {
+        self.lvl == 1
+    }
+
+    /// Returns the number of levels between this headline and the top
+    /// level, which is `level - 1`.
+    pub fn depth_from_root(self) -> usize {
+        self.lvl.saturating_sub(1)
+    }
+
+    /// Returns `true` if this headline has no sub-headlines.
+    ///
+    /// ```rust
+    /// # use orgize::Org;
+    /// #
+    /// let org = Org::parse("* a\n** b\n");
+    /// let a = org.headlines().next().unwrap();
+    /// let b = org.headlines().nth(1).unwrap();
+    ///
+    /// assert!(a.is_top_level() && !a.is_leaf(&org));
+    /// assert!(!b.is_top_level() && b.is_leaf(&org));
+    /// assert_eq!(b.depth_from_root(), 1);
+    /// ```
+    pub fn is_leaf(self, org: &Org) -> bool {
+        self.first_child(org).is_none()
+    }
+
     /// Returns the ID of the headline element of this headline.
     pub fn headline_node(self) -> NodeId {
         self.hdl_n
@@ -438,6 +474,33 @@ impl Headline {
         }
     }
 
+    /// Returns the url this headline bookmarks, by convention.
+    ///
+    /// Same as [`Title::as_bookmark_url`], but falls back to the first link
+    /// to a url in the section of this headline.
+    ///
+    /// [`Title::as_bookmark_url`]: crate::elements::Title::as_bookmark_url
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse("* Article\nFrom [[file:a.org]] and [[https://example.com]].\n");
+    /// let headline = org.headlines().next().unwrap();
+    ///
+    /// assert_eq!(
+    ///     headline.bookmark_url(&org).unwrap(),
+    ///     "https://example.com"
+    /// );
+    /// ```
+    pub fn bookmark_url<'a: 'b, 'b>(self, org: &'b Org<'a>) -> Option<Cow<'b, str>> {
+        self.title(org).as_bookmark_url().or_else(|| {
+            org.extract_links(self.sec_n?)
+                .into_iter()
+                .find(|link| link.path.contains("://"))
+                .map(|link| Cow::Borrowed(&*link.path))
+        })
+    }
+
     /// Returns a mutual reference to the title element of this headline.
     ///
     /// Don't change the level and content of the `&mut Titile` directly.
@@ -517,18 +580,10 @@ impl Headline {
     /// ```
     pub fn set_level(&mut self, lvl: usize, org: &mut Org) -> ValidationResult<()> {
         if !self.is_detached(org) {
-            let min = self
-                .next(&org)
-                .or_else(|| self.parent(&org))
-                .map(|hdl| hdl.lvl)
-                .unwrap_or(1);
-            let max = self
-                .previous(&org)
-                .map(|hdl| hdl.lvl)
-                .unwrap_or(usize::max_value());
-            if !(min..=max).contains(&lvl) {
+            let range = self.level_range(org);
+            if !range.contains(&lvl) {
                 return Err(ValidationError::HeadlineLevelMismatch {
-                    range: min..=max,
+                    range,
                     at: self.hdl_n,
                 });
             }
@@ -541,6 +596,361 @@ impl Headline {
         Ok(())
     }
 
+    /// Changes the level of this headline, and adjusts the levels of all its
+    /// descendants by the same amount.
+    ///
+    /// Returns an error if the new level doesn't meet the requirements of
+    /// [`Headline::set_level`], or if any headline in this subtree would
+    /// exceed [`MAX_HEADLINE_LEVEL`].
+    ///
+    /// [`Headline::set_level`]: #method.set_level
+    /// [`MAX_HEADLINE_LEVEL`]: constant.MAX_HEADLINE_LEVEL.html
+    ///
+    /// ```rust
+    /// # use orgize::Org;
+    /// #
+    /// let mut org = Org::parse(
+    ///     r#"
+    /// * h1
+    /// ** h1_1
+    /// *** h1_1_1
+    /// **** h1_1_1_1
+    /// "#,
+    /// );
+    ///
+    /// let mut h1_1 = org.headlines().nth(1).unwrap();
+    ///
+    /// // h1_1_1_1 would be at level 15
+    /// assert!(h1_1.move_to_level(13, &mut org).is_err());
+    ///
+    /// h1_1.move_to_level(5, &mut org).unwrap();
+    ///
+    /// let mut writer = Vec::new();
+    /// org.write_org(&mut writer).unwrap();
+    /// assert_eq!(
+    ///     String::from_utf8(writer).unwrap(),
+    ///     r#"
+    /// * h1
+    /// ***** h1_1
+    /// ****** h1_1_1
+    /// ******* h1_1_1_1
+    /// "#,
+    /// );
+    /// ```
+    pub fn move_to_level(&mut self, lvl: usize, org: &mut Org) -> ValidationResult<()> {
+        let deepest = self
+            .hdl_n
+            .descendants(&org.arena)
+            .filter_map(|n| match org[n] {
+                Element::Headline { level } => Some(level),
+                _ => None,
+            })
+            .max()
+            .unwrap_or(self.lvl);
+        let max = MAX_HEADLINE_LEVEL.saturating_sub(deepest - self.lvl);
+
+        let range = if self.is_detached(org) {
+            1..=max
+        } else {
+            let range = self.level_range(org);
+            *range.start()..=max.min(*range.end())
+        };
+        if !range.contains(&lvl) {
+            return Err(ValidationError::HeadlineLevelMismatch {
+                range,
+                at: self.hdl_n,
+            });
+        }
+
+        let nodes: Vec<_> = self.hdl_n.descendants(&org.arena).collect();
+        for node in nodes {
+            if let Element::Headline { level } = org[node] {
+                let mut hdl = Headline::from_node(node, level, org);
+                hdl.lvl = level + lvl - self.lvl;
+                hdl.title_mut(org).level = hdl.lvl;
+                org[node] = Element::Headline { level: hdl.lvl };
+            }
+        }
+        self.lvl = lvl;
+        Ok(())
+    }
+
+    /// Merges the properties of `other` title into this headline.
+    ///
+    /// Keys only in `other` are always added. For keys present in both,
+    /// the value from `other` is used, unless `prefer_self` is `true`.
+    /// Keys are compared case-insensitively.
+    ///
+    /// ```rust
+    /// # use orgize::{elements::Title, Org};
+    /// #
+    /// let mut org = Org::parse("* h1\n:PROPERTIES:\n:ID: 1\n:OWNER: me\n:END:\n");
+    /// let other = Org::parse("* h1\n:PROPERTIES:\n:owner: you\n:EFFORT: 1:00\n:END:\n");
+    ///
+    /// let h1 = org.headlines().next().unwrap();
+    /// let other_title = other.headlines().next().unwrap().title(&other);
+    /// h1.merge_properties(other_title, false, &mut org);
+    ///
+    /// let properties: Vec<_> = h1
+    ///     .title(&org)
+    ///     .properties
+    ///     .iter()
+    ///     .map(|(k, v)| format!("{}={}", k, v))
+    ///     .collect();
+    /// assert_eq!(properties, vec!["ID=1", "OWNER=you", "EFFORT=1:00"]);
+    /// ```
+    pub fn merge_properties(self, other: &Title<'_>, prefer_self: bool, org: &mut Org) {
+        let properties = &mut self.title_mut(org).properties.pairs;
+        for (key, value) in other.properties.iter() {
+            match properties
+                .iter()
+                .position(|(k, _)| k.eq_ignore_ascii_case(key))
+            {
+                Some(_) if prefer_self => (),
+                Some(i) => properties[i].1 = value.to_string().into(),
+                None => properties.push((key.to_string().into(), value.to_string().into())),
+            }
+        }
+    }
+
+    /// Updates statistics cookies, `[/]` and `[%]`, in the title of this
+    /// headline.
+    ///
+    /// Counts checked checkboxes of the list items in this headline's own
+    /// section. Nested lists and sub-headlines are not counted.
+    ///
+    /// ```rust
+    /// # use orgize::Org;
+    /// #
+    /// let mut org = Org::parse("* Tasks [%] [/]\n- [X] one\n- [ ] two\n- three\n** Sub [/]\n");
+    ///
+    /// let tasks = org.headlines().next().unwrap();
+    /// tasks.update_statistics_cookie(&mut org);
+    ///
+    /// let mut writer = Vec::new();
+    /// org.write_org(&mut writer).unwrap();
+    /// assert_eq!(
+    ///     String::from_utf8(writer).unwrap(),
+    ///     "* Tasks [50%] [1/2]\n- [X] one\n- [ ] two\n- three\n** Sub [/]\n"
+    /// );
+    /// ```
+    pub fn update_statistics_cookie(self, org: &mut Org) {
+        let (mut checked, mut total) = (0, 0);
+        if let Some(sec_n) = self.sec_n {
+            for list in sec_n.children(&org.arena) {
+                if let Element::List(_) = org[list] {
+                    for item in list.children(&org.arena) {
+                        match checkbox(item, org) {
+                            Some(true) => {
+                                checked += 1;
+                                total += 1;
+                            }
+                            Some(false) => total += 1,
+                            None => (),
+                        }
+                    }
+                }
+            }
+        }
+
+        let cookies: Vec<_> = self
+            .ttl_n
+            .descendants(&org.arena)
+            .filter(|&n| matches!(org[n], Element::Cookie(_)))
+            .collect();
+        for cookie in cookies {
+            let (old, new) = match &mut org[cookie] {
+                Element::Cookie(cookie) => {
+                    let new = if cookie.value.ends_with("%]") {
+                        let percent = if total == 0 { 0 } else { checked * 100 / total };
+                        format!("[{}%]", percent)
+                    } else {
+                        format!("[{}/{}]", checked, total)
+                    };
+                    let old = std::mem::replace(&mut cookie.value, new.clone().into());
+                    (old.into_owned(), new)
+                }
+                _ => continue,
+            };
+            let title = self.title_mut(org);
+            title.raw = title.raw.replacen(&old, &new, 1).into();
+        }
+    }
+
+    /// Creates a checkbox list from direct child headlines with a todo
+    /// keyword.
+    ///
+    /// Each item contains the title of a child headline, and is checked if
+    /// its keyword is one of the done keywords in `config`. The list is
+    /// created as a detached `List` node in the arena of `org`, or `None` is
+    /// returned if there are no such children.
+    ///
+    /// ```rust
+    /// # use orgize::{Org, ParseConfig};
+    /// #
+    /// let config = ParseConfig {
+    ///     todo_keywords: (vec!["TODO".into()], vec!["DONE".into(), "CANCELED".into()]),
+    ///     ..Default::default()
+    /// };
+    /// let mut org = Org::parse_custom(
+    ///     "* Project\n** TODO one\n** DONE two\n** CANCELED three\n** four\n",
+    ///     &config,
+    /// );
+    ///
+    /// let mut project = org.headlines().next().unwrap();
+    /// let list = project
+    ///     .create_checkbox_list_from_children(&mut org, &config)
+    ///     .unwrap();
+    /// assert_eq!(org.node_to_org(list), "- [ ] one\n- [X] two\n- [X] three\n");
+    ///
+    /// project.set_section_content("Tasks:\n", &mut org);
+    /// let section = project.section_node().unwrap();
+    /// section.append(list, org.arena_mut());
+    ///
+    /// let mut writer = Vec::new();
+    /// org.write_org(&mut writer).unwrap();
+    /// assert_eq!(
+    ///     String::from_utf8(writer).unwrap(),
+    ///     "* Project\nTasks:\n- [ ] one\n- [X] two\n- [X] three\n\
+    ///      ** TODO one\n** DONE two\n** CANCELED three\n** four\n"
+    /// );
+    /// ```
+    pub fn create_checkbox_list_from_children(
+        self,
+        org: &mut Org,
+        config: &ParseConfig,
+    ) -> Option<NodeId> {
+        let mut content = String::new();
+        for child in self.children(org) {
+            let title = child.title(org);
+            if let Some(keyword) = &title.keyword {
+                let checked = config.todo_keywords.1.iter().any(|k| k == keyword);
+                content.push_str(if checked { "- [X] " } else { "- [ ] " });
+                content.push_str(&title.raw);
+                content.push('\n');
+            }
+        }
+
+        let parent = org.arena.new_node(Element::Section);
+        parse_container(
+            &mut OwnedArena::new(&mut org.arena),
+            Container::Block {
+                node: parent,
+                content: &content,
+            },
+            &ParseConfig::default(),
+        );
+        let list = org.arena[parent].first_child();
+        if let Some(list) = list {
+            list.detach(&mut org.arena);
+        }
+        parent.remove(&mut org.arena);
+        list
+    }
+
+    /// Converts list `list` in this headline's section, or a detached list
+    /// such as one returned by `create_checkbox_list_from_children`, into
+    /// sub-headlines, placed before existing sub-headlines.
+    ///
+    /// Each item becomes a headline titled with the first line of the item.
+    /// Checked items are marked with the first done keyword of `config`,
+    /// unchecked ones with its first todo keyword, and the rest of the item
+    /// becomes the section of the new headline.
+    ///
+    /// Returns an error if `list` is neither a list in this headline's
+    /// section nor a detached list.
+    ///
+    /// ```rust
+    /// # use orgize::{Org, ParseConfig};
+    /// #
+    /// let mut org = Org::parse("* Project\nIntro\n- [X] one\n- [ ] two\n  details\n** three\n");
+    ///
+    /// let mut project = org.headlines().next().unwrap();
+    /// let section = project.section_node().unwrap();
+    /// let list = org.arena()[section].last_child().unwrap();
+    /// project
+    ///     .promote_checkbox_list_to_subheadings(list, &mut org, &ParseConfig::default())
+    ///     .unwrap();
+    ///
+    /// let mut writer = Vec::new();
+    /// org.write_org(&mut writer).unwrap();
+    /// assert_eq!(
+    ///     String::from_utf8(writer).unwrap(),
+    ///     "* Project\nIntro\n** DONE one\n** TODO two\ndetails\n** three\n"
+    /// );
+    /// ```
+    pub fn promote_checkbox_list_to_subheadings(
+        &mut self,
+        list: NodeId,
+        org: &mut Org,
+        config: &ParseConfig,
+    ) -> ValidationResult<()> {
+        let parent = org.arena[list].parent();
+        if !matches!(org[list], Element::List(_)) || (parent.is_some() && parent != self.sec_n) {
+            return Err(ValidationError::UnexpectedElement {
+                expected: "List in headline's section or detached List",
+                at: list,
+            });
+        }
+
+        let items: Vec<_> = list.children(&org.arena).collect();
+        let mut headlines = Vec::with_capacity(items.len());
+        for item in items {
+            let checked = checkbox(item, org);
+            let keyword = match checked {
+                Some(true) => config.todo_keywords.1.first(),
+                Some(false) => config.todo_keywords.0.first(),
+                None => None,
+            }
+            .map(|keyword| keyword.clone().into());
+
+            let mut writer = Vec::new();
+            let mut handler = DefaultOrgHandler;
+            for edge in item.traverse(&org.arena).skip(1) {
+                let _ = match edge {
+                    NodeEdge::Start(n) if n != item => handler.start(&mut writer, &org[n]),
+                    NodeEdge::End(n) if n != item => handler.end(&mut writer, &org[n]),
+                    _ => Ok(()),
+                };
+            }
+            let content = String::from_utf8_lossy(&writer);
+            let mut lines = content.lines();
+            let first = lines.next().unwrap_or_default();
+            let title = match checked {
+                Some(_) => first.get(3..).unwrap_or_default(),
+                None => first,
+            };
+
+            let mut headline = Headline::new(
+                Title {
+                    level: self.lvl + 1,
+                    keyword,
+                    raw: title.trim().to_string().into(),
+                    ..Default::default()
+                },
+                org,
+            );
+            let body = dedent(lines);
+            if !body.trim().is_empty() {
+                headline.set_section_content(body, org);
+            }
+            headlines.push(headline);
+        }
+
+        list.detach(&mut org.arena);
+        if let Some(sec_n) = parent {
+            if org.arena[sec_n].first_child().is_none() {
+                sec_n.detach(&mut org.arena);
+                self.sec_n = None;
+            }
+        }
+        for headline in headlines.into_iter().rev() {
+            self.prepend(headline, org)?;
+        }
+
+        Ok(())
+    }
+
     /// Changes the title content of this headline.
     ///
     /// ```rust
@@ -1188,6 +1598,20 @@ impl Headline {
         }
     }
 
+    // range of levels allowed for an attached headline
+    fn level_range(self, org: &Org) -> RangeInclusive<usize> {
+        let min = self
+            .next(org)
+            .or_else(|| self.parent(org))
+            .map(|hdl| hdl.lvl)
+            .unwrap_or(1);
+        let max = self
+            .previous(org)
+            .map(|hdl| hdl.lvl)
+            .unwrap_or(usize::max_value());
+        min..=max
+    }
+
     fn check_level(self, range: RangeInclusive<usize>) -> ValidationResult<()> {
         if !range.contains(&self.lvl) {
             Err(ValidationError::HeadlineLevelMismatch {
@@ -1216,4 +1640,691 @@ impl Org<'_> {
                 _ => None,
             })
     }
+
+    /// Returns the deepest headline level in this document, or `0` if it
+    /// has no headlines.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// assert_eq!(Org::parse("* a\n*** b\n** c\n").count_nested_levels(), 3);
+    /// assert_eq!(Org::parse("no headlines\n").count_nested_levels(), 0);
+    /// ```
+    pub fn count_nested_levels(&self) -> usize {
+        self.headlines()
+            .map(|headline| headline.level())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Returns an `org-protocol://` url which opens the given headline in
+    /// Emacs.
+    ///
+    /// Headlines with an `ID` property are linked by `roam-node`, others by
+    /// `open` with a `file:` search link, which requires an absolute source
+    /// path of this document. Returns `None` if neither is available.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let mut org = Org::parse("* Plan & Do\n* Notes\n:PROPERTIES:\n:ID: 2b5a\n:END:\n");
+    /// let plan = org.headlines().next().unwrap();
+    /// let notes = org.headlines().nth(1).unwrap();
+    ///
+    /// assert_eq!(
+    ///     org.to_org_protocol_url(notes).as_deref(),
+    ///     Some("org-protocol://roam-node?node_id=2b5a")
+    /// );
+    /// assert_eq!(org.to_org_protocol_url(plan), None);
+    ///
+    /// org.set_source_path("/home/user/notes.org");
+    /// assert_eq!(
+    ///     org.to_org_protocol_url(plan).as_deref(),
+    ///     Some("org-protocol://open?url=file:///home/user/notes.org::*Plan%20%26%20Do")
+    /// );
+    /// ```
+    pub fn to_org_protocol_url(&self, headline: Headline) -> Option<String> {
+        org_protocol_url(headline.title(self), self.source_path())
+    }
+
+    /// Returns headlines starting at a byte offset within `start..end` of
+    /// `source`, the text this document was parsed from.
+    ///
+    /// Offsets are found from the titles borrowed from `source`, so
+    /// headlines whose title was modified or which were added after parsing
+    /// are never returned.
+    ///
+    /// ```rust
+    /// # use orgize::Org;
+    /// #
+    /// let source = "* a\ntext\n** b\n* c\n";
+    /// let org = Org::parse(source);
+    ///
+    /// let titles = |start, end| -> Vec<_> {
+    ///     org.headlines_in_range(source, start, end)
+    ///         .into_iter()
+    ///         .map(|h| h.title(&org).raw.to_string())
+    ///         .collect()
+    /// };
+    ///
+    /// assert_eq!(titles(0, 9), vec!["a"]);
+    /// assert_eq!(titles(1, 14), vec!["b"]);
+    /// assert_eq!(titles(0, 100), vec!["a", "b", "c"]);
+    /// ```
+    pub fn headlines_in_range(&self, source: &str, start: usize, end: usize) -> Vec<Headline> {
+        self.headlines()
+            .filter(|headline| {
+                let line_start = offset_in(source, &headline.title(self).raw)
+                    .map(|offset| source[..offset].rfind('\n').map_or(0, |i| i + 1));
+                matches!(line_start, Some(offset) if (start..end).contains(&offset))
+            })
+            .collect()
+    }
+
+    /// Applies `mutate` to every headline passing `predicate`.
+    ///
+    /// Headlines are collected before updating, so `mutate` can safely
+    /// change the structure of document. Headlines removed from the
+    /// document by previous calls of `mutate` are skipped.
+    ///
+    /// ```rust
+    /// # use orgize::{Org, ParseConfig};
+    /// #
+    /// let mut org = Org::parse_custom(
+    ///     "* WAIT h1\n* TODO h2\n** WAIT h2_1\n",
+    ///     &ParseConfig {
+    ///         todo_keywords: (vec!["TODO".into(), "WAIT".into()], vec![]),
+    ///         ..Default::default()
+    ///     },
+    /// );
+    ///
+    /// org.batch_update(
+    ///     |headline, org| headline.title(org).keyword.as_deref() == Some("WAIT"),
+    ///     |headline, org| {
+    ///         let title = headline.title_mut(org);
+    ///         title.keyword = Some("TODO".into());
+    ///         title.tags.push("review".into());
+    ///     },
+    /// );
+    ///
+    /// let mut writer = Vec::new();
+    /// org.write_org(&mut writer).unwrap();
+    /// assert_eq!(
+    ///     String::from_utf8(writer).unwrap(),
+    ///     "* TODO h1 :review:\n* TODO h2\n** TODO h2_1 :review:\n"
+    /// );
+    /// ```
+    pub fn batch_update<P, F>(&mut self, predicate: P, mut mutate: F)
+    where
+        P: Fn(Headline, &Org) -> bool,
+        F: FnMut(&mut Headline, &mut Org),
+    {
+        let headlines: Vec<_> = self.headlines().collect();
+
+        for mut headline in headlines {
+            let root = self.root;
+            let attached = headline
+                .hdl_n
+                .ancestors(&self.arena)
+                .any(|node| node == root);
+
+            if attached && predicate(headline, self) {
+                mutate(&mut headline, self);
+            }
+        }
+    }
+
+    /// Returns a copy of this `Org` struct, without headlines duplicating
+    /// the title of one of their siblings.
+    ///
+    /// Among sibling headlines with identical raw titles, the one with the
+    /// most recent `CREATED` property is kept, or the first one if none of
+    /// them has a `CREATED` property. Removed headlines are dropped together
+    /// with their sections and sub-headlines.
+    ///
+    /// ```rust
+    /// # use orgize::{Org, ParseConfig};
+    /// #
+    /// let org = Org::parse(
+    ///     r#"* a
+    /// * b
+    /// ** c
+    /// ** c
+    /// * a
+    /// ** d
+    /// "#,
+    /// );
+    ///
+    /// let mut writer = Vec::new();
+    /// org.deduplicate_headlines_by_title()
+    ///     .write_org(&mut writer)
+    ///     .unwrap();
+    /// assert_eq!(String::from_utf8(writer).unwrap(), "* a\n* b\n** c\n");
+    ///
+    /// let org = Org::parse(
+    ///     r#"* a
+    /// :PROPERTIES:
+    /// :CREATED: [2019-04-08 Mon]
+    /// :END:
+    /// * a
+    /// :PROPERTIES:
+    /// :created: [2019-04-09 Tue]
+    /// :END:
+    /// "#,
+    /// );
+    ///
+    /// let org = org.deduplicate_headlines_by_title();
+    /// assert_eq!(org.headlines().count(), 1);
+    /// let title = org.headlines().next().unwrap().title(&org);
+    /// assert_eq!(title.properties.iter().next().unwrap().1, "[2019-04-09 Tue]");
+    ///
+    /// let config = ParseConfig {
+    ///     todo_keywords: (vec!["OPEN".into()], vec![]),
+    ///     ..Default::default()
+    /// };
+    /// let org = Org::parse_custom("* OPEN a\n* OPEN a\n", &config);
+    ///
+    /// let org = org.deduplicate_headlines_by_title();
+    /// assert_eq!(org.headlines().count(), 1);
+    /// let title = org.headlines().next().unwrap().title(&org);
+    /// assert_eq!(title.keyword.as_deref(), Some("OPEN"));
+    /// ```
+    pub fn deduplicate_headlines_by_title(&self) -> Org<'static> {
+        let mut duplicates = HashSet::new();
+        for node in std::iter::once(self.root).chain(self.headlines().map(|h| h.hdl_n)) {
+            // kept headline of each title among the children of `node`
+            let mut kept: HashMap<&str, (NodeId, _)> = HashMap::new();
+            for child in node.children(&self.arena) {
+                let title = match self.arena[child].first_child().map(|n| &self[n]) {
+                    Some(Element::Title(title)) => title,
+                    _ => continue,
+                };
+                let created = created(title);
+                match kept.get_mut(&*title.raw) {
+                    Some((kept, kept_created)) if created > *kept_created => {
+                        duplicates.insert(std::mem::replace(kept, child));
+                        *kept_created = created;
+                    }
+                    Some(_) => {
+                        duplicates.insert(child);
+                    }
+                    None => {
+                        kept.insert(&title.raw, (child, created));
+                    }
+                }
+            }
+        }
+
+        self.copy_without(|node| duplicates.contains(&node))
+    }
+
+    /// Updates statistics cookies, `[/]` and `[%]`, in the titles of all
+    /// headlines to reflect the checkboxes in their sections.
+    ///
+    /// Only checkboxes of top-level list items in a headline's own section
+    /// are counted, just like `org-update-statistics-cookies` does.
+    ///
+    /// ```rust
+    /// # use orgize::Org;
+    /// #
+    /// let mut org = Org::parse(
+    ///     r#"* Tasks [/]
+    /// - [X] one
+    /// - [ ] two
+    ///   - [X] nested
+    /// - [-] three
+    /// ** Subtasks [%]
+    /// - [x] four
+    /// "#,
+    /// );
+    ///
+    /// org.compute_statistics_cookie_values();
+    ///
+    /// let titles: Vec<_> = org.headlines().map(|h| h.title(&org).raw.clone()).collect();
+    /// assert_eq!(titles, vec!["Tasks [1/3]", "Subtasks [100%]"]);
+    /// ```
+    pub fn compute_statistics_cookie_values(&mut self) {
+        let headlines: Vec<_> = self.headlines().collect();
+        for headline in headlines {
+            headline.update_statistics_cookie(self);
+        }
+    }
+
+    /// Moves the section of each level-1 headline without sub-headlines
+    /// into a new level-2 headline titled `intro_keyword`, like `Overview`.
+    ///
+    /// ```rust
+    /// # use orgize::Org;
+    /// #
+    /// let mut org = Org::parse("* Rust\nA language.\n* Go\n* C\nA language.\n** History\n");
+    ///
+    /// org.auto_section_headers("Overview");
+    ///
+    /// let mut writer = Vec::new();
+    /// org.write_org(&mut writer).unwrap();
+    /// assert_eq!(
+    ///     String::from_utf8(writer).unwrap(),
+    ///     "* Rust\n** Overview\nA language.\n* Go\n* C\nA language.\n** History\n"
+    /// );
+    /// ```
+    pub fn auto_section_headers(&mut self, intro_keyword: &str) {
+        let headlines: Vec<_> = self.document().children(self).collect();
+
+        for headline in headlines {
+            let section = match headline.section_node() {
+                Some(section) if headline.first_child(self).is_none() => section,
+                _ => continue,
+            };
+
+            let intro = Headline::new(
+                Title {
+                    level: headline.level() + 1,
+                    raw: intro_keyword.to_string().into(),
+                    ..Default::default()
+                },
+                self,
+            );
+            section.detach(&mut self.arena);
+            intro.hdl_n.append(section, &mut self.arena);
+            headline.hdl_n.append(intro.hdl_n, &mut self.arena);
+        }
+
+        self.debug_validate();
+    }
+}
+
+// returns `Some(true)` if list item starts with a checked checkbox, or
+// `Some(false)` if it starts with an unchecked or partially checked one
+fn checkbox(item: NodeId, org: &Org) -> Option<bool> {
+    let paragraph = org.arena[item].first_child()?;
+    let text = org.arena[paragraph].first_child()?;
+    match &org[text] {
+        Element::Text { value } if value.starts_with("[X]") || value.starts_with("[x]") => {
+            Some(true)
+        }
+        Element::Text { value } if value.starts_with("[ ]") || value.starts_with("[-]") => {
+            Some(false)
+        }
+        _ => None,
+    }
+}
+
+// returns the start of `CREATED` property as a comparable tuple
+fn created(title: &Title) -> Option<(u16, u8, u8, u8, u8)> {
+    let (_, value) = title
+        .properties
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case("CREATED"))?;
+    let (_, timestamp) = Timestamp::parse_inactive(value.trim())
+        .or_else(|| Timestamp::parse_active(value.trim()))?;
+    match timestamp {
+        Timestamp::Active { start, .. }
+        | Timestamp::Inactive { start, .. }
+        | Timestamp::ActiveRange { start, .. }
+        | Timestamp::InactiveRange { start, .. } => Some((
+            start.year,
+            start.month,
+            start.day,
+            start.hour.unwrap_or_default(),
+            start.minute.unwrap_or_default(),
+        )),
+        Timestamp::Diary { .. } => None,
+    }
+}
+
+// joins lines, removing their common indentation
+fn dedent<'a>(lines: impl Iterator<Item = &'a str> + Clone) -> String {
+    let common = lines
+        .clone()
+        .filter(|line| !line.trim().is_empty())
+        // only ascii indentation is removed, so `common` is a char boundary of
+        // all non-blank lines
+        .map(|line| line.len() - line.trim_start_matches([' ', '\t']).len())
+        .min()
+        .unwrap_or_default();
+
+    let mut output = String::new();
+    for line in lines {
+        output.push_str(line.get(common..).unwrap_or_default());
+        output.push('\n');
+    }
+    output
+}
+
+/// Returns an `org-protocol://` url of the headline with `title`, see
+/// `Org::to_org_protocol_url`.
+pub(crate) fn org_protocol_url(title: &Title, source_path: Option<&Path>) -> Option<String> {
+    if let Some((_, id)) = title
+        .properties
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case("ID"))
+    {
+        return Some(format!(
+            "org-protocol://roam-node?node_id={}",
+            url_encode(id.trim())
+        ));
+    }
+
+    // relative paths would be read as the host of the file url
+    let path = source_path.filter(|path| path.is_absolute())?;
+    let path: Vec<_> = path.to_string_lossy().split('/').map(url_encode).collect();
+    let search = title.raw.split_whitespace().collect::<Vec<_>>().join(" ");
+    Some(format!(
+        "org-protocol://open?url=file://{}::*{}",
+        path.join("/"),
+        url_encode(&search)
+    ))
+}
+
+// percent-encodes all characters except unreserved ones
+pub(crate) fn url_encode(value: &str) -> String {
+    let mut output = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                output.push(byte as char)
+            }
+            _ => output.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    output
+}
+
+#[test]
+fn headlines_in_range() {
+    let source = "\r\n* a  \r\n  :PROPERTIES:\r\n  :ID: 1\r\n  :END:\r\n| x |\r\n\r\n\r\n*   b :t:\r\n** TODO\r\n";
+    let org = Org::parse(source);
+    let titles = |start, end| -> Vec<_> {
+        org.headlines_in_range(source, start, end)
+            .into_iter()
+            .map(|headline| headline.title(&org).raw.to_string())
+            .collect()
+    };
+
+    let b = source.find("*   b").unwrap();
+    assert_eq!(titles(0, b), ["a"]);
+    assert_eq!(titles(b, b + 1), ["b"]);
+    assert_eq!(titles(3, source.len()), ["b", ""]);
+    assert!(titles(0, 2).is_empty());
+
+    // offsets can't be found in other texts, or for modified headlines
+    assert!(org.headlines_in_range("* a\n", 0, 10).is_empty());
+    let copy = Org::parse_string(source.to_string());
+    assert!(copy.headlines_in_range(source, 0, source.len()).is_empty());
+}
+
+#[test]
+fn create_checkbox_list_from_children() {
+    let config = ParseConfig::default();
+    let mut org = Org::parse("* P\n** TODO *one*\n** two\n*** DONE three\n");
+    let project = org.headlines().next().unwrap();
+    let list = project
+        .create_checkbox_list_from_children(&mut org, &config)
+        .unwrap();
+    assert!(org.arena[list].parent().is_none());
+    assert_eq!(org.node_to_org(list), "- [ ] *one*\n");
+    assert!(org
+        .root
+        .descendants(&org.arena)
+        .all(|node| !matches!(org[node], Element::List(_))));
+
+    // no children with a todo keyword
+    let mut org = Org::parse("* P\n** one\n");
+    let project = org.headlines().next().unwrap();
+    let nodes = org.arena.count();
+    assert!(project
+        .create_checkbox_list_from_children(&mut org, &config)
+        .is_none());
+    assert_eq!(
+        org.arena.iter().filter(|node| !node.is_removed()).count(),
+        nodes
+    );
+}
+
+#[test]
+fn promote_checkbox_list_to_subheadings() {
+    let config = ParseConfig {
+        todo_keywords: (vec!["OPEN".into()], vec!["CLOSED".into()]),
+        ..Default::default()
+    };
+    let mut org = Org::parse_custom(
+        "* OPEN P\n- [X] one\n- [ ] two\n  details\n \u{a0}\u{a0}more\n- three\n",
+        &config,
+    );
+
+    let mut project = org.headlines().next().unwrap();
+    let list = org.arena[project.section_node().unwrap()]
+        .first_child()
+        .unwrap();
+    project
+        .promote_checkbox_list_to_subheadings(list, &mut org, &config)
+        .unwrap();
+
+    let mut writer = Vec::new();
+    org.write_org(&mut writer).unwrap();
+    assert_eq!(
+        String::from_utf8(writer).unwrap(),
+        "* OPEN P\n** CLOSED one\n** OPEN two\n details\n\u{a0}\u{a0}more\n** three\n"
+    );
+
+    // no keywords are configured
+    let config = ParseConfig {
+        todo_keywords: (vec![], vec![]),
+        ..Default::default()
+    };
+    let mut org = Org::parse("* P\n- [ ] one\n");
+    let mut project = org.headlines().next().unwrap();
+    let list = org.arena[project.section_node().unwrap()]
+        .first_child()
+        .unwrap();
+    project
+        .promote_checkbox_list_to_subheadings(list, &mut org, &config)
+        .unwrap();
+    assert_eq!(org.headlines().nth(1).unwrap().title(&org).raw, "one");
+    assert!(org
+        .headlines()
+        .nth(1)
+        .unwrap()
+        .title(&org)
+        .keyword
+        .is_none());
+
+    // lists created from children are promoted back
+    let config = ParseConfig::default();
+    let mut org = Org::parse("* A\n** TODO one\n** DONE two\n* B\n");
+    let a = org.headlines().next().unwrap();
+    let mut b = org.headlines().nth(3).unwrap();
+    let list = a
+        .create_checkbox_list_from_children(&mut org, &config)
+        .unwrap();
+    b.promote_checkbox_list_to_subheadings(list, &mut org, &config)
+        .unwrap();
+    let mut writer = Vec::new();
+    org.write_org(&mut writer).unwrap();
+    assert_eq!(
+        String::from_utf8(writer).unwrap(),
+        "* A\n** TODO one\n** DONE two\n* B\n** TODO one\n** DONE two\n"
+    );
+
+    // not a list in the section
+    let mut org = Org::parse("* P\ntext\n");
+    let mut project = org.headlines().next().unwrap();
+    let paragraph = org.arena[project.section_node().unwrap()]
+        .first_child()
+        .unwrap();
+    assert!(project
+        .promote_checkbox_list_to_subheadings(paragraph, &mut org, &config)
+        .is_err());
+}
+
+#[test]
+fn to_org_protocol_url() {
+    let mut org =
+        Org::parse("* Plan & Do\n* Été à Paris\n* Notes\n:PROPERTIES:\n:ID: 2b 5a\n:END:\n");
+    let headlines: Vec<_> = org.headlines().collect();
+
+    assert_eq!(
+        org.to_org_protocol_url(headlines[2]).as_deref(),
+        Some("org-protocol://roam-node?node_id=2b%205a")
+    );
+    assert_eq!(org.to_org_protocol_url(headlines[0]), None);
+
+    // relative paths can't be opened
+    org.set_source_path("notes/a.org");
+    assert_eq!(org.to_org_protocol_url(headlines[0]), None);
+
+    org.set_source_path("/home/user/my notes/é.org");
+    assert_eq!(
+        org.to_org_protocol_url(headlines[0]).as_deref(),
+        Some("org-protocol://open?url=file:///home/user/my%20notes/%C3%A9.org::*Plan%20%26%20Do")
+    );
+    assert_eq!(
+        org.to_org_protocol_url(headlines[1]).as_deref(),
+        Some("org-protocol://open?url=file:///home/user/my%20notes/%C3%A9.org::*%C3%89t%C3%A9%20%C3%A0%20Paris")
+    );
+    // the id is used even if the path is set
+    assert_eq!(
+        org.to_org_protocol_url(headlines[2]).as_deref(),
+        Some("org-protocol://roam-node?node_id=2b%205a")
+    );
+}
+
+#[test]
+fn batch_update() {
+    let mut org = Org::parse("");
+    org.batch_update(|_, _| true, |_, _| panic!("no headlines"));
+
+    let config = ParseConfig {
+        todo_keywords: (vec!["ÀFAIRE".into()], vec!["FINI".into()]),
+        ..Default::default()
+    };
+    let mut org = Org::parse_custom("* ÀFAIRE a\n** ÀFAIRE a1\n* ÀFAIRE b\n", &config);
+
+    // detaching a headline skips its children
+    let mut updated = Vec::new();
+    org.batch_update(
+        |headline, org| headline.title(org).keyword.as_deref() == Some("ÀFAIRE"),
+        |headline, org| {
+            updated.push(headline.title(org).raw.to_string());
+            if headline.title(org).raw == "a" {
+                headline.detach(org);
+            } else {
+                headline.title_mut(org).keyword = Some("FINI".into());
+            }
+        },
+    );
+    assert_eq!(updated, ["a", "b"]);
+
+    let mut writer = Vec::new();
+    org.write_org(&mut writer).unwrap();
+    assert_eq!(String::from_utf8(writer).unwrap(), "* FINI b\n");
+}
+
+#[test]
+fn move_to_level() {
+    let mut org = Org::parse("* été\n** 日本\n*** c\n");
+    let mut a = org.headlines().next().unwrap();
+
+    assert!(a.move_to_level(0, &mut org).is_err());
+    assert!(a.move_to_level(MAX_HEADLINE_LEVEL - 1, &mut org).is_err());
+    a.move_to_level(MAX_HEADLINE_LEVEL - 2, &mut org).unwrap();
+    let levels: Vec<_> = org
+        .headlines()
+        .map(|headline| (headline.level(), headline.title(&org).level))
+        .collect();
+    assert_eq!(levels, [(12, 12), (13, 13), (14, 14)]);
+
+    // detached headlines can be moved to the top level
+    let mut b = org.headlines().nth(1).unwrap();
+    b.detach(&mut org);
+    b.move_to_level(1, &mut org).unwrap();
+    let c = b.first_child(&org).unwrap();
+    assert_eq!((c.level(), c.title(&org).level), (2, 2));
+    assert_eq!(b.title(&org).level, 1);
+}
+
+#[test]
+fn deduplicate_headlines_by_title() {
+    let dedup = |source: &str| {
+        let mut writer = Vec::new();
+        Org::parse(source)
+            .deduplicate_headlines_by_title()
+            .write_org(&mut writer)
+            .unwrap();
+        String::from_utf8(writer).unwrap()
+    };
+
+    assert_eq!(dedup(""), "");
+    // titles are compared exactly, and only between siblings
+    assert_eq!(dedup("* été\n* Été\n** été\n"), "* été\n* Été\n** été\n");
+    // duplicates are removed with their subtrees, the first one is kept
+    assert_eq!(
+        dedup("* 日本\nfirst\n* 日本\nsecond\n** child\n"),
+        "* 日本\nfirst\n"
+    );
+}
+
+#[test]
+fn merge_properties() {
+    let mut org = Org::parse("* a\n:PROPERTIES:\n:Été: 1\n:END:\n* b\n");
+    let other = Org::parse("* o\n:PROPERTIES:\n:été: 2\n:ÉTÉ: 3\n:id: x\n:END:\n");
+    let other = other.headlines().next().unwrap().title(&other).clone();
+    let properties = |headline: Headline, org: &Org| -> Vec<String> {
+        headline
+            .title(org)
+            .properties
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, v))
+            .collect()
+    };
+
+    // only ascii letters of keys are compared case-insensitively
+    let a = org.headlines().next().unwrap();
+    a.merge_properties(&other, true, &mut org);
+    assert_eq!(properties(a, &org), ["Été=1", "été=2", "ÉTÉ=3", "id=x"]);
+
+    // a headline without properties takes all of them
+    let b = org.headlines().nth(1).unwrap();
+    b.merge_properties(&other, false, &mut org);
+    assert_eq!(properties(b, &org), ["été=2", "ÉTÉ=3", "id=x"]);
+
+    // merging an empty property set changes nothing
+    b.merge_properties(&Title::default(), false, &mut org);
+    assert_eq!(properties(b, &org), ["été=2", "ÉTÉ=3", "id=x"]);
+}
+
+#[test]
+fn compute_statistics_cookie_values() {
+    let mut org = Org::parse("");
+    org.compute_statistics_cookie_values();
+
+    let mut org = Org::parse(
+        "* TODO Été [3/5] :tag:\n- [X] 日本\n** Vide [%] [/]\ntext\n** Plain\n- [ ] one\n",
+    );
+    org.compute_statistics_cookie_values();
+
+    let mut writer = Vec::new();
+    org.write_org(&mut writer).unwrap();
+    assert_eq!(
+        String::from_utf8(writer).unwrap(),
+        "* TODO Été [1/1] :tag:\n- [X] 日本\n** Vide [0%] [0/0]\ntext\n** Plain\n- [ ] one\n"
+    );
+}
+
+#[test]
+fn auto_section_headers() {
+    let mut org = Org::parse("");
+    org.auto_section_headers("Overview");
+    assert_eq!(org.headlines().count(), 0);
+
+    // headlines without sections, nested headlines and preambles are skipped
+    let mut org = Org::parse("intro\n* Vide\n* Été\ntexte\n** Sub\nbody\n* 日本\n語\n");
+    org.auto_section_headers("Aperçu");
+
+    let mut writer = Vec::new();
+    org.write_org(&mut writer).unwrap();
+    assert_eq!(
+        String::from_utf8(writer).unwrap(),
+        "intro\n* Vide\n* Été\ntexte\n** Sub\nbody\n* 日本\n** Aperçu\n語\n"
+    );
 }
@@ -0,0 +1,299 @@
+use crate::config::{ParseConfig, DEFAULT_CONFIG};
+use crate::elements::Timestamp;
+use crate::import::{
+    parse_iso8601, push_properties, push_timestamp, tag_name, ImportError, ImportResult,
+};
+use crate::Org;
+
+/// CSV import configuration
+///
+/// Columns are chosen by their header, which is matched case-insensitively.
+#[derive(Clone, Debug)]
+pub struct CsvImportConfig {
+    /// Column of headline titles, default is `"Title"`
+    pub title: String,
+    /// Column of todo keywords
+    pub keyword: Option<String>,
+    /// Column of deadlines, in `2019-04-08` or `2019-04-08 10:00` format
+    pub deadline: Option<String>,
+    /// Columns of tags, which are separated by whitespaces, `,` or `:`
+    pub tags: Vec<String>,
+    /// Field delimiter, default is `,`
+    pub delimiter: char,
+}
+
+impl Default for CsvImportConfig {
+    fn default() -> Self {
+        CsvImportConfig {
+            title: String::from("Title"),
+            keyword: None,
+            deadline: None,
+            tags: Vec::new(),
+            delimiter: ',',
+        }
+    }
+}
+
+impl Org<'_> {
+    /// Converts CSV data into `Org` struct.
+    ///
+    /// The first row contains column headers. Each following row becomes a
+    /// headline, whose title, todo keyword, deadline and tags are taken from
+    /// the columns named in `config`. Other non-empty columns are stored as
+    /// properties, named after their headers.
+    ///
+    /// Keywords other than the default `TODO` and `DONE` are parsed as todo
+    /// keywords.
+    ///
+    /// ```rust
+    /// use orgize::import::CsvImportConfig;
+    /// use orgize::Org;
+    ///
+    /// let org = Org::from_csv(
+    ///     "Task,Status,Due,Labels,Owner\n\
+    ///      Write docs,TODO,2019-04-10,\"docs, web\",Alice\n\
+    ///      Release,DONE,,,\n",
+    ///     &CsvImportConfig {
+    ///         title: String::from("task"),
+    ///         keyword: Some(String::from("status")),
+    ///         deadline: Some(String::from("due")),
+    ///         tags: vec![String::from("labels")],
+    ///         ..Default::default()
+    ///     },
+    /// )
+    /// .unwrap();
+    ///
+    /// let mut writer = Vec::new();
+    /// org.write_org(&mut writer).unwrap();
+    /// assert_eq!(
+    ///     String::from_utf8(writer).unwrap(),
+    ///     "* TODO Write docs :docs:web:\nDEADLINE: <2019-04-10 Wed>\n\
+    ///      :PROPERTIES:\n:Owner: Alice\n:END:\n\
+    ///      * DONE Release\n"
+    /// );
+    /// ```
+    pub fn from_csv(input: &str, config: &CsvImportConfig) -> ImportResult<Org<'static>> {
+        let (text, parse_config) = csv_to_org(input, config)?;
+        Ok(Org::parse_string_custom(text, &parse_config))
+    }
+}
+
+fn csv_to_org(input: &str, config: &CsvImportConfig) -> ImportResult<(String, ParseConfig)> {
+    rows_to_org(
+        parse_records(input, config.delimiter)?,
+        &config.title,
+        config.keyword.as_deref(),
+        config.deadline.as_deref(),
+        &config.tags,
+    )
+}
+
+// converts rows into headlines, the first row contains column headers, and
+// returns a parse config containing every keyword used
+pub(crate) fn rows_to_org(
+    rows: Vec<Vec<String>>,
+    title: &str,
+    keyword: Option<&str>,
+    deadline: Option<&str>,
+    tags: &[String],
+) -> ImportResult<(String, ParseConfig)> {
+    let mut rows = rows.into_iter();
+
+    let headers = rows.next().ok_or_else(|| ImportError::InvalidInput {
+        reason: String::from("expected a header row"),
+    })?;
+    let column = |name: &str| {
+        headers
+            .iter()
+            .position(|header| header.trim().eq_ignore_ascii_case(name.trim()))
+            .ok_or_else(|| ImportError::InvalidInput {
+                reason: format!("column {:?} not found", name),
+            })
+    };
+
+    let title = column(title)?;
+    let keyword = keyword.map(column).transpose()?;
+    let deadline = deadline.map(column).transpose()?;
+    let tag_columns = tags
+        .iter()
+        .map(|name| column(name))
+        .collect::<ImportResult<Vec<_>>>()?;
+
+    let mut output = String::new();
+    let mut keywords = DEFAULT_CONFIG.todo_keywords.clone();
+
+    for (i, row) in rows.enumerate() {
+        let cell = |column: usize| single_line(row.get(column).map(String::as_str).unwrap_or(""));
+
+        output.push('*');
+        if let Some(keyword) = keyword.map(cell).filter(|keyword| !keyword.is_empty()) {
+            output.push(' ');
+            output.push_str(&keyword);
+            // keywords other than the default ones are treated as todo keywords
+            if !keywords.0.iter().chain(&keywords.1).any(|k| *k == keyword) {
+                keywords.0.push(keyword);
+            }
+        }
+        output.push(' ');
+        output.push_str(&cell(title));
+
+        let tags: Vec<_> = tag_columns
+            .iter()
+            .flat_map(|column| {
+                cell(*column)
+                    .split(|c: char| c.is_whitespace() || c == ',' || c == ':')
+                    .filter(|tag| !tag.is_empty())
+                    .map(tag_name)
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        if !tags.is_empty() {
+            output.push_str(" :");
+            for tag in tags {
+                output.push_str(&tag);
+                output.push(':');
+            }
+        }
+        output.push('\n');
+
+        if let Some(value) = deadline.map(cell).filter(|value| !value.is_empty()) {
+            let due = parse_iso8601(&value).ok_or_else(|| ImportError::InvalidInput {
+                // the header is the first line
+                reason: format!("invalid deadline {:?} in row {}", value, i + 2),
+            })?;
+            output.push_str("DEADLINE: ");
+            push_timestamp(
+                &mut output,
+                &Timestamp::Active {
+                    start: due,
+                    repeater: None,
+                    delay: None,
+                },
+            );
+            output.push('\n');
+        }
+
+        let properties: Vec<_> = headers
+            .iter()
+            .enumerate()
+            .filter(|(column, _)| {
+                *column != title
+                    && Some(*column) != keyword
+                    && Some(*column) != deadline
+                    && !tag_columns.contains(column)
+            })
+            .map(|(column, header)| (property_name(header), cell(column)))
+            .filter(|(name, value)| !name.is_empty() && !value.is_empty())
+            .collect();
+        let properties: Vec<_> = properties
+            .iter()
+            .map(|(name, value)| (name.as_str(), value))
+            .collect();
+        push_properties(&mut output, &properties);
+    }
+
+    let parse_config = ParseConfig {
+        todo_keywords: keywords,
+        ..Default::default()
+    };
+
+    Ok((output, parse_config))
+}
+
+// splits `input` into records, following RFC 4180
+fn parse_records(input: &str, delimiter: char) -> ImportResult<Vec<Vec<String>>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = input.trim_start_matches('\u{feff}').chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted => {
+                if chars.peek() == Some(&'"') {
+                    chars.next();
+                    field.push('"');
+                } else {
+                    quoted = false;
+                }
+            }
+            '"' if field.is_empty() => quoted = true,
+            c if quoted => field.push(c),
+            c if c == delimiter => record.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => (),
+            '\n' => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            c => field.push(c),
+        }
+    }
+
+    if quoted {
+        return Err(ImportError::InvalidInput {
+            reason: String::from("unclosed quoted field"),
+        });
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+
+    // blank lines are skipped
+    records.retain(|record| record.iter().any(|field| !field.trim().is_empty()));
+
+    Ok(records)
+}
+
+fn single_line(value: &str) -> String {
+    value.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn property_name(header: &str) -> String {
+    header.split_whitespace().collect::<Vec<_>>().join("_")
+}
+
+#[test]
+fn records() {
+    assert_eq!(
+        parse_records("a,\"b,\"\"c\"\"\"\r\n\n\"d\ne\",\n", ',').unwrap(),
+        vec![
+            vec![String::from("a"), String::from("b,\"c\"")],
+            vec![String::from("d\ne"), String::new()],
+        ]
+    );
+    assert_eq!(
+        parse_records("a;b", ';').unwrap(),
+        vec![vec![String::from("a"), String::from("b")]]
+    );
+    assert!(parse_records("\"a", ',').is_err());
+    assert!(csv_to_org("Name\nx\n", &CsvImportConfig::default()).is_err());
+}
+
+#[test]
+fn custom_keywords() {
+    let org = Org::from_csv(
+        "Title,Status\nOne,WAITING\nTwo,DONE\nThree,\n",
+        &CsvImportConfig {
+            keyword: Some(String::from("Status")),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    let titles: Vec<_> = org
+        .headlines()
+        .map(|headline| {
+            let title = headline.title(&org);
+            (title.keyword.clone(), title.raw.to_string())
+        })
+        .collect();
+    assert_eq!(
+        titles,
+        [
+            (Some("WAITING".into()), String::from("One")),
+            (Some("DONE".into()), String::from("Two")),
+            (None, String::from("Three")),
+        ]
+    );
+}
@@ -0,0 +1,540 @@
+use serde_json::{Map, Value};
+
+use crate::import::{ImportError, ImportResult};
+use crate::Org;
+
+impl Org<'_> {
+    /// Converts the json output of Emacs's `org-element-parse-buffer` into `Org` struct.
+    ///
+    /// The input is expected to be produced by `ox-json` or any exporter that
+    /// follows the same layout: every node is an object with a `type`, a
+    /// `properties` object and a `contents` array, and plain text is
+    /// represented as json strings.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::from_json_emacs(
+    ///     r#"{
+    ///     "$$data_type": "org-document",
+    ///     "contents": [{
+    ///         "$$data_type": "org-node",
+    ///         "type": "headline",
+    ///         "properties": { "level": 1, "raw-value": "Title", "todo-keyword": "TODO" },
+    ///         "contents": []
+    ///     }]
+    /// }"#,
+    /// )
+    /// .unwrap();
+    ///
+    /// let mut writer = Vec::new();
+    /// org.write_org(&mut writer).unwrap();
+    /// assert_eq!(String::from_utf8(writer).unwrap(), "* TODO Title\n");
+    /// ```
+    pub fn from_json_emacs(json: &str) -> ImportResult<Org<'static>> {
+        let value: Value = serde_json::from_str(json)?;
+        let mut output = String::new();
+        write_value(&mut output, &value)?;
+        Ok(Org::parse_string(output))
+    }
+}
+
+struct Node<'a>(&'a Map<String, Value>);
+
+impl<'a> Node<'a> {
+    fn ty(&self) -> &'a str {
+        self.0
+            .get("type")
+            .or_else(|| self.0.get("$$data_type"))
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+    }
+
+    fn property(&self, key: &str) -> Option<&'a Value> {
+        self.0
+            .get("properties")
+            .and_then(Value::as_object)
+            .and_then(|properties| properties.get(key))
+            .filter(|value| !value.is_null())
+    }
+
+    fn str(&self, key: &str) -> Option<&'a str> {
+        self.property(key).and_then(Value::as_str)
+    }
+
+    fn usize(&self, key: &str) -> usize {
+        self.property(key)
+            .and_then(Value::as_u64)
+            .unwrap_or_default() as usize
+    }
+
+    fn contents(&self) -> impl Iterator<Item = &'a Value> {
+        self.0
+            .get("contents")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+    }
+}
+
+fn write_value(output: &mut String, value: &Value) -> ImportResult<()> {
+    match value {
+        Value::String(text) => output.push_str(text),
+        Value::Object(map) => write_node(output, Node(map))?,
+        Value::Null => (),
+        _ => {
+            return Err(ImportError::InvalidInput {
+                reason: format!("expected an org-element node, found {}", value),
+            })
+        }
+    }
+    Ok(())
+}
+
+fn write_contents(output: &mut String, node: &Node) -> ImportResult<()> {
+    for child in node.contents() {
+        write_value(output, child)?;
+    }
+    Ok(())
+}
+
+fn contents_to_string(node: &Node) -> ImportResult<String> {
+    let mut output = String::new();
+    write_contents(&mut output, node)?;
+    Ok(output)
+}
+
+fn write_node(output: &mut String, node: Node) -> ImportResult<()> {
+    let ty = node.ty();
+
+    macro_rules! block {
+        ($begin:expr, $end:expr) => {{
+            output.push_str($begin);
+            output.push('\n');
+            write_contents(output, &node)?;
+            output.push_str($end);
+            output.push('\n');
+        }};
+    }
+
+    macro_rules! raw_block {
+        ($begin:expr, $end:expr) => {{
+            output.push_str($begin);
+            output.push('\n');
+            let value = node.str("value").unwrap_or_default();
+            output.push_str(value);
+            if !value.is_empty() && !value.ends_with('\n') {
+                output.push('\n');
+            }
+            output.push_str($end);
+            output.push('\n');
+        }};
+    }
+
+    macro_rules! emphasis {
+        ($marker:expr) => {{
+            output.push_str($marker);
+            write_contents(output, &node)?;
+            output.push_str($marker);
+        }};
+    }
+
+    match ty {
+        "org-data" | "org-document" | "section" | "plain-list" => write_contents(output, &node)?,
+        "headline" => {
+            for _ in 0..node.usize("level").max(1) {
+                output.push('*');
+            }
+            if let Some(keyword) = node.str("todo-keyword") {
+                output.push(' ');
+                output.push_str(keyword);
+            }
+            let priority = node.property("priority").and_then(|priority| {
+                priority
+                    .as_u64()
+                    .and_then(|code| std::char::from_u32(code as u32))
+                    .or_else(|| priority.as_str().and_then(|s| s.chars().next()))
+            });
+            if let Some(priority) = priority {
+                output.push_str(" [#");
+                output.push(priority);
+                output.push(']');
+            }
+            output.push(' ');
+            if let Some(raw) = node.str("raw-value") {
+                output.push_str(raw);
+            } else if let Some(Value::Array(title)) = node.property("title") {
+                for object in title {
+                    write_value(output, object)?;
+                }
+            }
+            let tags: Vec<_> = node
+                .property("tags")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_str)
+                .collect();
+            if !tags.is_empty() {
+                output.push_str(" :");
+                for tag in tags {
+                    output.push_str(tag);
+                    output.push(':');
+                }
+            }
+            output.push('\n');
+            for _ in 0..node.usize("pre-blank") {
+                output.push('\n');
+            }
+            write_contents(output, &node)?;
+        }
+        "planning" => {
+            let mut first = true;
+            for (key, keyword) in &[
+                ("scheduled", "SCHEDULED: "),
+                ("deadline", "DEADLINE: "),
+                ("closed", "CLOSED: "),
+            ] {
+                if let Some(timestamp) = node.property(key) {
+                    if !first {
+                        output.push(' ');
+                    }
+                    first = false;
+                    output.push_str(keyword);
+                    write_value(output, timestamp)?;
+                }
+            }
+            output.push('\n');
+        }
+        "property-drawer" => block!(":PROPERTIES:", ":END:"),
+        "node-property" => {
+            output.push(':');
+            output.push_str(node.str("key").unwrap_or_default());
+            output.push_str(": ");
+            output.push_str(node.str("value").unwrap_or_default());
+            output.push('\n');
+        }
+        "drawer" => block!(
+            &format!(":{}:", node.str("drawer-name").unwrap_or("DRAWER")),
+            ":END:"
+        ),
+        "paragraph" => {
+            write_contents(output, &node)?;
+            if !output.ends_with('\n') {
+                output.push('\n');
+            }
+        }
+        "item" => {
+            let bullet = node.str("bullet").unwrap_or("- ");
+            output.push_str(bullet);
+            if !bullet.ends_with(' ') {
+                output.push(' ');
+            }
+            match node.str("checkbox") {
+                Some("on") => output.push_str("[X] "),
+                Some("off") => output.push_str("[ ] "),
+                Some("trans") => output.push_str("[-] "),
+                _ => (),
+            }
+            let contents = contents_to_string(&node)?;
+            let indent = " ".repeat(bullet.trim_end().len() + 1);
+            for (i, line) in contents.lines().enumerate() {
+                if i > 0 && !line.is_empty() {
+                    output.push_str(&indent);
+                }
+                output.push_str(line);
+                output.push('\n');
+            }
+        }
+        "src-block" => {
+            let mut begin = String::from("#+BEGIN_SRC");
+            for key in &["language", "switches", "parameters"] {
+                if let Some(value) = node.str(key) {
+                    begin.push(' ');
+                    begin.push_str(value);
+                }
+            }
+            raw_block!(&begin, "#+END_SRC")
+        }
+        "example-block" => raw_block!("#+BEGIN_EXAMPLE", "#+END_EXAMPLE"),
+        "comment-block" => raw_block!("#+BEGIN_COMMENT", "#+END_COMMENT"),
+        "export-block" => raw_block!(
+            &format!("#+BEGIN_EXPORT {}", node.str("type").unwrap_or_default()),
+            "#+END_EXPORT"
+        ),
+        "quote-block" => block!("#+BEGIN_QUOTE", "#+END_QUOTE"),
+        "center-block" => block!("#+BEGIN_CENTER", "#+END_CENTER"),
+        "verse-block" => block!("#+BEGIN_VERSE", "#+END_VERSE"),
+        "special-block" => {
+            let name = node.str("type").unwrap_or_default();
+            block!(&format!("#+BEGIN_{}", name), &format!("#+END_{}", name))
+        }
+        "dynamic-block" => {
+            let mut begin = format!("#+BEGIN: {}", node.str("block-name").unwrap_or_default());
+            if let Some(arguments) = node.str("arguments") {
+                begin.push(' ');
+                begin.push_str(arguments);
+            }
+            block!(&begin, "#+END:")
+        }
+        "keyword" => {
+            output.push_str("#+");
+            output.push_str(node.str("key").unwrap_or_default());
+            output.push_str(": ");
+            output.push_str(node.str("value").unwrap_or_default());
+            output.push('\n');
+        }
+        "babel-call" => {
+            output.push_str("#+CALL: ");
+            output.push_str(node.str("value").unwrap_or_default());
+            output.push('\n');
+        }
+        "horizontal-rule" => output.push_str("-----\n"),
+        "fixed-width" | "comment" => {
+            let prefix = if ty == "comment" { "#" } else { ":" };
+            for line in node.str("value").unwrap_or_default().lines() {
+                output.push_str(prefix);
+                if !line.is_empty() {
+                    output.push(' ');
+                    output.push_str(line);
+                }
+                output.push('\n');
+            }
+        }
+        "table" => {
+            if node.str("type") == Some("table.el") {
+                output.push_str(node.str("value").unwrap_or_default());
+            } else {
+                write_contents(output, &node)?;
+                if let Some(tblfm) = node.str("tblfm") {
+                    output.push_str("#+TBLFM: ");
+                    output.push_str(tblfm);
+                    output.push('\n');
+                }
+            }
+        }
+        "table-row" => {
+            if node.str("type") == Some("rule") {
+                output.push_str("|-|\n");
+            } else {
+                output.push('|');
+                write_contents(output, &node)?;
+                output.push('\n');
+            }
+        }
+        "table-cell" => {
+            output.push(' ');
+            write_contents(output, &node)?;
+            output.push_str(" |");
+        }
+        "clock" => {
+            output.push_str("CLOCK: ");
+            if let Some(value) = node.property("value") {
+                write_value(output, value)?;
+            }
+            if let Some(duration) = node.str("duration") {
+                output.push_str(" => ");
+                output.push_str(duration);
+            }
+            output.push('\n');
+        }
+        "footnote-definition" => {
+            output.push_str("[fn:");
+            output.push_str(node.str("label").unwrap_or_default());
+            output.push_str("] ");
+            write_contents(output, &node)?;
+        }
+        "bold" => emphasis!("*"),
+        "italic" => emphasis!("/"),
+        "underline" => emphasis!("_"),
+        "strike-through" => emphasis!("+"),
+        "code" | "verbatim" => {
+            let marker = if ty == "code" { "~" } else { "=" };
+            output.push_str(marker);
+            output.push_str(node.str("value").unwrap_or_default());
+            output.push_str(marker);
+        }
+        "link" => {
+            let path = match (node.str("raw-link"), node.str("type"), node.str("path")) {
+                (Some(raw), _, _) => raw.to_string(),
+                (None, Some("fuzzy"), Some(path)) | (None, None, Some(path)) => path.to_string(),
+                (None, Some(ty), Some(path)) => format!("{}:{}", ty, path),
+                _ => String::new(),
+            };
+            let desc = contents_to_string(&node)?;
+            output.push_str("[[");
+            output.push_str(&path);
+            if !desc.is_empty() {
+                output.push_str("][");
+                output.push_str(&desc);
+            }
+            output.push_str("]]");
+        }
+        "footnote-reference" => {
+            output.push_str("[fn:");
+            output.push_str(node.str("label").unwrap_or_default());
+            if node.contents().next().is_some() {
+                output.push(':');
+                write_contents(output, &node)?;
+            }
+            output.push(']');
+        }
+        "inline-src-block" => {
+            output.push_str("src_");
+            output.push_str(node.str("language").unwrap_or_default());
+            if let Some(parameters) = node.str("parameters") {
+                output.push('[');
+                output.push_str(parameters);
+                output.push(']');
+            }
+            output.push('{');
+            output.push_str(node.str("value").unwrap_or_default());
+            output.push('}');
+        }
+        "export-snippet" => {
+            output.push_str("@@");
+            output.push_str(node.str("back-end").unwrap_or_default());
+            output.push(':');
+            output.push_str(node.str("value").unwrap_or_default());
+            output.push_str("@@");
+        }
+        "macro" => {
+            output.push_str("{{{");
+            output.push_str(node.str("key").unwrap_or_default());
+            let args: Vec<_> = node
+                .property("args")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_str)
+                .collect();
+            if !args.is_empty() {
+                output.push('(');
+                output.push_str(&args.join(","));
+                output.push(')');
+            }
+            output.push_str("}}}");
+        }
+        "target" => {
+            output.push_str("<<");
+            output.push_str(node.str("value").unwrap_or_default());
+            output.push_str(">>");
+        }
+        "radio-target" => {
+            output.push_str("<<<");
+            write_contents(output, &node)?;
+            output.push_str(">>>");
+        }
+        "entity" => {
+            output.push('\\');
+            output.push_str(node.str("name").unwrap_or_default());
+        }
+        "line-break" => output.push_str("\\\\\n"),
+        "subscript" | "superscript" => {
+            output.push_str(if ty == "subscript" { "_{" } else { "^{" });
+            write_contents(output, &node)?;
+            output.push('}');
+        }
+        "timestamp" | "statistics-cookie" | "latex-fragment" | "latex-environment" => {
+            output.push_str(
+                node.str("raw-value")
+                    .or_else(|| node.str("value"))
+                    .unwrap_or_default(),
+            );
+        }
+        _ => {
+            if let Some(value) = node.str("value") {
+                output.push_str(value);
+            } else {
+                write_contents(output, &node)?;
+            }
+        }
+    }
+
+    // post-blank means trailing whitespaces for objects, and blank lines for elements
+    let post_blank = node.usize("post-blank");
+    if is_object(ty) {
+        for _ in 0..post_blank {
+            output.push(' ');
+        }
+    } else {
+        for _ in 0..post_blank {
+            output.push('\n');
+        }
+    }
+
+    Ok(())
+}
+
+fn is_object(ty: &str) -> bool {
+    matches!(
+        ty,
+        "bold"
+            | "code"
+            | "entity"
+            | "export-snippet"
+            | "footnote-reference"
+            | "inline-babel-call"
+            | "inline-src-block"
+            | "italic"
+            | "line-break"
+            | "latex-fragment"
+            | "link"
+            | "macro"
+            | "radio-target"
+            | "statistics-cookie"
+            | "strike-through"
+            | "subscript"
+            | "superscript"
+            | "table-cell"
+            | "target"
+            | "timestamp"
+            | "underline"
+            | "verbatim"
+    )
+}
+
+#[test]
+fn convert() {
+    let value: Value = serde_json::from_str(
+        r#"{
+        "$$data_type": "org-document",
+        "contents": [{
+            "type": "headline",
+            "properties": { "level": 2, "raw-value": "Title", "priority": 65, "tags": ["a", "b"] },
+            "contents": [{
+                "type": "section",
+                "contents": [{
+                    "type": "planning",
+                    "properties": {
+                        "scheduled": { "type": "timestamp", "properties": { "raw-value": "<2019-04-08 Mon>" } }
+                    }
+                }, {
+                    "type": "paragraph",
+                    "properties": { "post-blank": 1 },
+                    "contents": [
+                        "Some ",
+                        { "type": "bold", "properties": { "post-blank": 1 }, "contents": ["bold"] },
+                        "text."
+                    ]
+                }, {
+                    "type": "plain-list",
+                    "contents": [{
+                        "type": "item",
+                        "properties": { "bullet": "- ", "checkbox": "on" },
+                        "contents": [{ "type": "paragraph", "contents": ["done"] }]
+                    }]
+                }]
+            }]
+        }]
+    }"#,
+    )
+    .unwrap();
+
+    let mut output = String::new();
+    write_value(&mut output, &value).unwrap();
+    assert_eq!(
+        output,
+        "** [#A] Title :a:b:\nSCHEDULED: <2019-04-08 Mon>\nSome *bold* text.\n\n- [X] done\n"
+    );
+}
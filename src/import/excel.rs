@@ -0,0 +1,215 @@
+use std::path::Path;
+
+use calamine::{open_workbook_auto, Data, Reader, Sheets};
+
+use crate::config::ParseConfig;
+use crate::import::{csv::rows_to_org, ImportError, ImportResult};
+use crate::Org;
+
+/// Excel import configuration
+///
+/// Columns are chosen by their header, which is matched case-insensitively.
+#[derive(Clone, Debug)]
+pub struct ExcelImportConfig {
+    /// Column of headline titles, default is `"Title"`
+    pub title: String,
+    /// Column of todo keywords
+    pub keyword: Option<String>,
+    /// Column of deadlines, containing dates or text in `2019-04-08` format
+    pub deadline: Option<String>,
+    /// Columns of tags, which are separated by whitespaces, `,` or `:`
+    pub tags: Vec<String>,
+}
+
+impl Default for ExcelImportConfig {
+    fn default() -> Self {
+        ExcelImportConfig {
+            title: String::from("Title"),
+            keyword: None,
+            deadline: None,
+            tags: Vec::new(),
+        }
+    }
+}
+
+impl Org<'_> {
+    /// Converts the first sheet of an Excel or OpenDocument spreadsheet
+    /// into `Org` struct.
+    ///
+    /// Works like [`Org::from_csv`]: the first row contains column headers,
+    /// and each following row becomes a headline. Merged cells repeat their
+    /// value in every cell they span, and date cells are written as
+    /// `2019-04-08` or `2019-04-08 10:00`. Keywords other than the default
+    /// `TODO` and `DONE` are parsed as todo keywords.
+    ///
+    /// [`Org::from_csv`]: #method.from_csv
+    ///
+    /// ```rust,no_run
+    /// use orgize::import::ExcelImportConfig;
+    /// use orgize::Org;
+    /// use std::path::Path;
+    ///
+    /// let org = Org::from_excel(
+    ///     Path::new("tasks.xlsx"),
+    ///     &ExcelImportConfig {
+    ///         keyword: Some(String::from("Status")),
+    ///         deadline: Some(String::from("Due")),
+    ///         ..Default::default()
+    ///     },
+    /// )
+    /// .unwrap();
+    /// ```
+    pub fn from_excel(path: &Path, config: &ExcelImportConfig) -> ImportResult<Org<'static>> {
+        let (text, parse_config) = excel_to_org(path, config)?;
+        Ok(Org::parse_string_custom(text, &parse_config))
+    }
+}
+
+fn excel_to_org(path: &Path, config: &ExcelImportConfig) -> ImportResult<(String, ParseConfig)> {
+    let mut workbook = open_workbook_auto(path)?;
+
+    let name =
+        workbook
+            .sheet_names()
+            .first()
+            .cloned()
+            .ok_or_else(|| ImportError::InvalidInput {
+                reason: String::from("expected at least one sheet"),
+            })?;
+    let range = workbook.worksheet_range(&name)?;
+    let (top, left) = range.start().unwrap_or_default();
+
+    let mut rows: Vec<Vec<String>> = range
+        .rows()
+        .map(|row| row.iter().map(cell_text).collect())
+        .collect();
+
+    // only xlsx files keep track of merged cells
+    if let Sheets::Xlsx(xlsx) = &mut workbook {
+        if let Some(merged) = xlsx.worksheet_merge_cells(&name) {
+            for dimensions in merged? {
+                let (start_row, start_column) = dimensions.start;
+                let (end_row, end_column) = dimensions.end;
+                let cell = |row: u32, column: u32| {
+                    let row = rows.get(row.checked_sub(top)? as usize)?;
+                    row.get(column.checked_sub(left)? as usize).cloned()
+                };
+                let value = match cell(start_row, start_column) {
+                    Some(value) => value,
+                    None => continue,
+                };
+                for row in start_row..=end_row {
+                    for column in start_column..=end_column {
+                        if let Some(cell) = row
+                            .checked_sub(top)
+                            .and_then(|row| rows.get_mut(row as usize))
+                            .zip(column.checked_sub(left))
+                            .and_then(|(row, column)| row.get_mut(column as usize))
+                        {
+                            *cell = value.clone();
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    rows_to_org(
+        rows,
+        &config.title,
+        config.keyword.as_deref(),
+        config.deadline.as_deref(),
+        &config.tags,
+    )
+}
+
+fn cell_text(cell: &Data) -> String {
+    match cell {
+        Data::Empty | Data::Error(_) => String::new(),
+        Data::String(value) => value.clone(),
+        Data::Int(value) => value.to_string(),
+        Data::Float(value) if value.fract() == 0.0 => format!("{:.0}", value),
+        Data::Float(value) => value.to_string(),
+        Data::Bool(value) => value.to_string(),
+        Data::DateTime(value) if value.is_duration() => value.as_f64().to_string(),
+        Data::DateTime(value) => serial_date(value.as_f64()),
+        Data::DateTimeIso(value) | Data::DurationIso(value) => value.replace('T', " "),
+    }
+}
+
+// formats a date serial number, which counts days since 1899-12-30
+fn serial_date(serial: f64) -> String {
+    let days = serial.floor() as i64;
+    let minutes = ((serial - serial.floor()) * 24.0 * 60.0).round() as i64;
+    let (days, minutes) = if minutes == 24 * 60 {
+        (days + 1, 0)
+    } else {
+        (days, minutes)
+    };
+
+    // days since 1970-01-01, converted into a civil date
+    let days = days - 25569 + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month + 2) / 5 + 1;
+    let month = if month < 10 { month + 3 } else { month - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    if minutes == 0 {
+        format!("{:04}-{:02}-{:02}", year, month, day)
+    } else {
+        format!(
+            "{:04}-{:02}-{:02} {:02}:{:02}",
+            year,
+            month,
+            day,
+            minutes / 60,
+            minutes % 60
+        )
+    }
+}
+
+#[test]
+fn serial_date_() {
+    assert_eq!(serial_date(43563.0), "2019-04-08");
+    assert_eq!(serial_date(43563.5), "2019-04-08 12:00");
+    assert_eq!(serial_date(36585.0), "2000-02-29");
+    assert_eq!(serial_date(25569.0), "1970-01-01");
+}
+
+#[test]
+#[cfg(feature = "rust_xlsxwriter")]
+fn custom_keywords() {
+    use rust_xlsxwriter::Workbook;
+
+    let path = crate::test_dir("from-excel").join("tasks.xlsx");
+    let mut workbook = Workbook::new();
+    let sheet = workbook.add_worksheet();
+    for (row, values) in [["Title", "Status"], ["One", "WAITING"], ["Two", "DONE"]]
+        .iter()
+        .enumerate()
+    {
+        for (column, value) in values.iter().enumerate() {
+            sheet.write(row as u32, column as u16, *value).unwrap();
+        }
+    }
+    workbook.save(&path).unwrap();
+
+    let org = Org::from_excel(
+        &path,
+        &ExcelImportConfig {
+            keyword: Some(String::from("Status")),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    let keywords: Vec<_> = org
+        .headlines()
+        .map(|headline| headline.title(&org).keyword.clone())
+        .collect();
+    assert_eq!(keywords, [Some("WAITING".into()), Some("DONE".into())]);
+}
@@ -0,0 +1,278 @@
+use serde_json::Value;
+
+use crate::config::ParseConfig;
+use crate::elements::Timestamp;
+use crate::import::{
+    markdown::markdown_to_org, parse_iso8601, push_properties, push_timestamp, tag_name,
+    ImportError, ImportResult,
+};
+use crate::Org;
+
+/// GitHub issues import configuration
+#[derive(Clone, Debug)]
+pub struct IssueImportConfig {
+    /// Level of imported headlines, default is `1`
+    pub level: usize,
+    /// Todo keywords of open and closed issues, default is `("TODO", "DONE")`
+    pub todo_keywords: (String, String),
+    /// Imports pull requests as well, default is `false`
+    pub pull_requests: bool,
+    /// Uses issue labels as headline tags, default is `true`
+    pub labels: bool,
+    /// Converts issue body into headline section, default is `true`
+    pub body: bool,
+    /// Adds an `ASSIGNEES` property, default is `true`
+    pub assignees: bool,
+    /// Adds a `CREATED` property, default is `true`
+    pub created_at: bool,
+    /// Adds an `URL` property, default is `true`
+    pub url: bool,
+}
+
+impl Default for IssueImportConfig {
+    fn default() -> Self {
+        IssueImportConfig {
+            level: 1,
+            todo_keywords: (String::from("TODO"), String::from("DONE")),
+            pull_requests: false,
+            labels: true,
+            body: true,
+            assignees: true,
+            created_at: true,
+            url: true,
+        }
+    }
+}
+
+impl Org<'_> {
+    /// Converts the json response of GitHub's issues API into `Org` struct.
+    ///
+    /// Each issue becomes a headline, whose todo keyword depends on the issue
+    /// state. The issue number and other fields enabled in `config` are
+    /// stored in the properties drawer, and the markdown body is converted
+    /// into its section.
+    ///
+    /// The result is parsed with `config.todo_keywords` as its only todo
+    /// keywords.
+    ///
+    /// ```rust
+    /// use orgize::{import::IssueImportConfig, Org};
+    ///
+    /// let org = Org::from_github_issues(
+    ///     r#"[{
+    ///         "number": 1,
+    ///         "title": "Crash on startup",
+    ///         "state": "open",
+    ///         "labels": [{ "name": "bug" }],
+    ///         "body": "It crashes with **panic**."
+    ///     }]"#,
+    ///     &IssueImportConfig::default(),
+    /// )
+    /// .unwrap();
+    ///
+    /// let mut writer = Vec::new();
+    /// org.write_org(&mut writer).unwrap();
+    /// assert_eq!(
+    ///     String::from_utf8(writer).unwrap(),
+    ///     "* TODO Crash on startup :bug:\n:PROPERTIES:\n:ISSUE: 1\n:END:\nIt crashes with *panic*.\n\n"
+    /// );
+    /// ```
+    pub fn from_github_issues(
+        json: &str,
+        config: &IssueImportConfig,
+    ) -> ImportResult<Org<'static>> {
+        let parse_config = ParseConfig {
+            todo_keywords: (
+                vec![config.todo_keywords.0.clone()],
+                vec![config.todo_keywords.1.clone()],
+            ),
+            ..Default::default()
+        };
+        Ok(Org::parse_string_custom(
+            issues_to_org(json, config)?,
+            &parse_config,
+        ))
+    }
+}
+
+fn issues_to_org(json: &str, config: &IssueImportConfig) -> ImportResult<String> {
+    let value: Value = serde_json::from_str(json)?;
+
+    let issues = match &value {
+        Value::Array(issues) => issues.iter().collect(),
+        Value::Object(_) => vec![&value],
+        _ => {
+            return Err(ImportError::InvalidInput {
+                reason: String::from("expected an issue object or an array of issues"),
+            })
+        }
+    };
+
+    let mut output = String::new();
+
+    for issue in issues {
+        if !config.pull_requests && issue.get("pull_request").is_some() {
+            continue;
+        }
+
+        let title = issue.get("title").and_then(Value::as_str).ok_or_else(|| {
+            ImportError::InvalidInput {
+                reason: String::from("expected issue title"),
+            }
+        })?;
+        let closed = issue.get("state").and_then(Value::as_str) == Some("closed");
+
+        for _ in 0..config.level.max(1) {
+            output.push('*');
+        }
+        output.push(' ');
+        output.push_str(if closed {
+            &config.todo_keywords.1
+        } else {
+            &config.todo_keywords.0
+        });
+        output.push(' ');
+        output.push_str(&title.split_whitespace().collect::<Vec<_>>().join(" "));
+
+        let labels = strings(issue.get("labels"), "name");
+        if config.labels && !labels.is_empty() {
+            output.push_str(" :");
+            for label in labels {
+                output.push_str(&tag_name(label));
+                output.push(':');
+            }
+        }
+        output.push('\n');
+
+        let mut properties = Vec::new();
+        if let Some(number) = issue.get("number").and_then(Value::as_u64) {
+            properties.push(("ISSUE", number.to_string()));
+        }
+        if let Some(url) = issue.get("html_url").and_then(Value::as_str) {
+            if config.url {
+                properties.push(("URL", url.to_string()));
+            }
+        }
+        let assignees = strings(issue.get("assignees"), "login");
+        if config.assignees && !assignees.is_empty() {
+            properties.push(("ASSIGNEES", assignees.join(", ")));
+        }
+        if config.created_at {
+            let created_at = issue.get("created_at").and_then(Value::as_str);
+            if let Some(created_at) = created_at.and_then(parse_iso8601) {
+                let mut value = String::new();
+                push_timestamp(
+                    &mut value,
+                    &Timestamp::Inactive {
+                        start: created_at,
+                        repeater: None,
+                        delay: None,
+                    },
+                );
+                properties.push(("CREATED", value));
+            }
+        }
+        push_properties(&mut output, &properties);
+
+        if let Some(body) = issue.get("body").and_then(Value::as_str) {
+            let body = body.trim();
+            if config.body && !body.is_empty() {
+                output.push_str(&markdown_to_org(body, config.level.max(1)));
+                output.push('\n');
+            }
+        }
+    }
+
+    Ok(output)
+}
+
+// collects strings from an array of strings or objects
+fn strings<'a>(value: Option<&'a Value>, key: &str) -> Vec<&'a str> {
+    value
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|item| item.as_str().or_else(|| item.get(key)?.as_str()))
+        .collect()
+}
+
+#[test]
+fn convert() {
+    let json = r##"[{
+        "number": 12,
+        "html_url": "https://github.com/PoiScript/orgize/issues/12",
+        "title": "Support   markdown",
+        "state": "closed",
+        "labels": [{ "name": "help wanted" }, { "name": "enhancement" }],
+        "assignees": [{ "login": "alice" }, { "login": "bob" }],
+        "created_at": "2019-04-08T10:00:00Z",
+        "body": "# Details\nUse `pulldown-cmark`."
+    }, {
+        "number": 13,
+        "title": "Pull request",
+        "state": "open",
+        "pull_request": {}
+    }]"##;
+
+    assert_eq!(
+        issues_to_org(json, &IssueImportConfig::default()).unwrap(),
+        "* DONE Support markdown :help_wanted:enhancement:\n\
+         :PROPERTIES:\n\
+         :ISSUE: 12\n\
+         :URL: https://github.com/PoiScript/orgize/issues/12\n\
+         :ASSIGNEES: alice, bob\n\
+         :CREATED: [2019-04-08 Mon 10:00]\n\
+         :END:\n\
+         ** Details\n\
+         Use ~pulldown-cmark~.\n\n"
+    );
+
+    assert_eq!(
+        issues_to_org(
+            json,
+            &IssueImportConfig {
+                level: 2,
+                pull_requests: true,
+                labels: false,
+                body: false,
+                assignees: false,
+                created_at: false,
+                url: false,
+                ..Default::default()
+            }
+        )
+        .unwrap(),
+        "** DONE Support markdown\n\
+         :PROPERTIES:\n\
+         :ISSUE: 12\n\
+         :END:\n\
+         ** TODO Pull request\n\
+         :PROPERTIES:\n\
+         :ISSUE: 13\n\
+         :END:\n"
+    );
+
+    assert!(issues_to_org("42", &IssueImportConfig::default()).is_err());
+}
+
+#[test]
+fn custom_keywords() {
+    let org = Org::from_github_issues(
+        r#"[{ "title": "Crash", "state": "closed" }, { "title": "Typo", "state": "open" }]"#,
+        &IssueImportConfig {
+            todo_keywords: (String::from("OPEN"), String::from("CLOSED")),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    let keywords: Vec<_> = org
+        .headlines()
+        .map(|headline| headline.title(&org).keyword.as_deref().map(String::from))
+        .collect();
+    assert_eq!(
+        keywords,
+        [Some(String::from("CLOSED")), Some(String::from("OPEN"))]
+    );
+    assert_eq!(org.headlines().next().unwrap().title(&org).raw, "Crash");
+}
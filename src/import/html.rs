@@ -0,0 +1,380 @@
+use scraper::{ElementRef, Html, Node};
+
+use crate::import::ImportResult;
+use crate::Org;
+
+impl Org<'_> {
+    /// Converts a html string into `Org` struct.
+    ///
+    /// Headings become headlines, and paragraphs, `<pre>` blocks, lists,
+    /// tables and block quotes are converted to their org-mode counterparts.
+    /// Unknown elements are unwrapped and only their contents are kept.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::from_html("<h1>Title</h1><p>Some <b>bold</b> text.</p>").unwrap();
+    ///
+    /// let mut writer = Vec::new();
+    /// org.write_org(&mut writer).unwrap();
+    /// assert_eq!(
+    ///     String::from_utf8(writer).unwrap(),
+    ///     "* Title\nSome *bold* text.\n\n"
+    /// );
+    /// ```
+    pub fn from_html(html: &str) -> ImportResult<Org<'static>> {
+        Ok(Org::parse_string(html_to_org(html)))
+    }
+}
+
+fn html_to_org(html: &str) -> String {
+    let html = Html::parse_fragment(html);
+    let mut converter = HtmlConverter::default();
+    converter.blocks(html.root_element());
+    converter.output
+}
+
+#[derive(Default)]
+struct HtmlConverter {
+    output: String,
+    // inline contents waiting to be written as a paragraph
+    paragraph: String,
+}
+
+impl HtmlConverter {
+    fn blocks(&mut self, parent: ElementRef) {
+        for child in parent.children() {
+            match child.value() {
+                Node::Text(text) => push_text(&mut self.paragraph, text),
+                Node::Element(_) => {
+                    if let Some(element) = ElementRef::wrap(child) {
+                        self.block(element);
+                    }
+                }
+                _ => (),
+            }
+        }
+        self.flush_paragraph();
+    }
+
+    fn block(&mut self, element: ElementRef) {
+        let name = element.value().name();
+        match name {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                self.flush_paragraph();
+                let level = name[1..].parse().unwrap_or(1);
+                let mut title = String::new();
+                inlines(&mut title, element);
+                for _ in 0..level {
+                    self.output.push('*');
+                }
+                self.output.push(' ');
+                self.output.push_str(&single_line(&title));
+                self.output.push('\n');
+            }
+            "p" => {
+                self.flush_paragraph();
+                inlines(&mut self.paragraph, element);
+                self.flush_paragraph();
+            }
+            "pre" => {
+                self.flush_paragraph();
+                self.pre(element);
+            }
+            "ul" | "ol" => {
+                self.flush_paragraph();
+                self.list(element, 0);
+                self.output.push('\n');
+            }
+            "table" => {
+                self.flush_paragraph();
+                self.table(element);
+                self.output.push('\n');
+            }
+            "blockquote" => {
+                self.flush_paragraph();
+                self.output.push_str("#+BEGIN_QUOTE\n");
+                self.blocks(element);
+                self.output.push_str("#+END_QUOTE\n\n");
+            }
+            "hr" => {
+                self.flush_paragraph();
+                self.output.push_str("-----\n\n");
+            }
+            "head" | "script" | "style" | "template" => (),
+            "html" | "body" | "main" | "article" | "section" | "header" | "footer" | "nav"
+            | "aside" | "div" | "figure" => {
+                self.flush_paragraph();
+                self.blocks(element);
+            }
+            _ => inline(&mut self.paragraph, element),
+        }
+    }
+
+    fn flush_paragraph(&mut self) {
+        let paragraph = self.paragraph.trim();
+        if !paragraph.is_empty() {
+            self.output.push_str(paragraph);
+            self.output.push_str("\n\n");
+        }
+        self.paragraph.clear();
+    }
+
+    fn pre(&mut self, element: ElementRef) {
+        let code = element
+            .children()
+            .filter_map(ElementRef::wrap)
+            .find(|child| child.value().name() == "code");
+
+        let text: String = element.text().collect();
+        let mut contents = text.lines().map(escape_line).collect::<Vec<_>>().join("\n");
+        contents.push('\n');
+
+        if let Some(code) = code {
+            let language = code
+                .value()
+                .attr("class")
+                .and_then(|class| {
+                    class.split_whitespace().find_map(|class| {
+                        class
+                            .strip_prefix("language-")
+                            .or_else(|| class.strip_prefix("lang-"))
+                    })
+                })
+                .unwrap_or_default();
+            self.output.push_str("#+BEGIN_SRC ");
+            self.output.push_str(language);
+            self.output.push('\n');
+            self.output.push_str(&contents);
+            self.output.push_str("#+END_SRC\n\n");
+        } else {
+            self.output.push_str("#+BEGIN_EXAMPLE\n");
+            self.output.push_str(&contents);
+            self.output.push_str("#+END_EXAMPLE\n\n");
+        }
+    }
+
+    fn list(&mut self, element: ElementRef, indent: usize) {
+        let ordered = element.value().name() == "ol";
+
+        let items = element
+            .children()
+            .filter_map(ElementRef::wrap)
+            .filter(|child| child.value().name() == "li");
+
+        for (index, item) in items.enumerate() {
+            let bullet = if ordered {
+                format!("{}. ", index + 1)
+            } else {
+                String::from("- ")
+            };
+
+            let mut contents = String::new();
+            let mut sublists = Vec::new();
+            for child in item.children() {
+                match child.value() {
+                    Node::Text(text) => push_text(&mut contents, text),
+                    Node::Element(_) => {
+                        if let Some(child) = ElementRef::wrap(child) {
+                            match child.value().name() {
+                                "ul" | "ol" => sublists.push(child),
+                                _ => inline(&mut contents, child),
+                            }
+                        }
+                    }
+                    _ => (),
+                }
+            }
+
+            for _ in 0..indent {
+                self.output.push(' ');
+            }
+            self.output.push_str(&bullet);
+            self.output.push_str(&single_line(&contents));
+            self.output.push('\n');
+
+            for sublist in sublists {
+                self.list(sublist, indent + bullet.len());
+            }
+        }
+    }
+
+    fn table(&mut self, element: ElementRef) {
+        let rows = element
+            .descendants()
+            .filter_map(ElementRef::wrap)
+            .filter(|child| child.value().name() == "tr");
+
+        for (index, row) in rows.enumerate() {
+            let cells: Vec<_> = row
+                .children()
+                .filter_map(ElementRef::wrap)
+                .filter(|child| matches!(child.value().name(), "td" | "th"))
+                .collect();
+
+            self.output.push('|');
+            for cell in &cells {
+                let mut contents = String::new();
+                inlines(&mut contents, *cell);
+                self.output.push(' ');
+                self.output
+                    .push_str(&single_line(&contents).replace('|', "\\vert{}"));
+                self.output.push_str(" |");
+            }
+            self.output.push('\n');
+
+            // only the first row can be a header row
+            if index == 0 && cells.iter().all(|cell| cell.value().name() == "th") {
+                self.output.push('|');
+                for (i, _) in cells.iter().enumerate() {
+                    if i > 0 {
+                        self.output.push('+');
+                    }
+                    self.output.push_str("---");
+                }
+                self.output.push_str("|\n");
+            }
+        }
+    }
+}
+
+fn inlines(output: &mut String, element: ElementRef) {
+    for child in element.children() {
+        match child.value() {
+            Node::Text(text) => push_text(output, text),
+            Node::Element(_) => {
+                if let Some(child) = ElementRef::wrap(child) {
+                    inline(output, child);
+                }
+            }
+            _ => (),
+        }
+    }
+}
+
+fn inline(output: &mut String, element: ElementRef) {
+    let marker = match element.value().name() {
+        "strong" | "b" => "*",
+        "em" | "i" => "/",
+        "u" | "ins" => "_",
+        "s" | "del" | "strike" => "+",
+        "code" | "kbd" | "samp" => {
+            let code: String = element.text().collect();
+            output.push('~');
+            output.push_str(&code);
+            output.push('~');
+            return;
+        }
+        "a" => {
+            let mut desc = String::new();
+            inlines(&mut desc, element);
+            let desc = desc.trim();
+            match element.value().attr("href") {
+                Some(href) if desc.is_empty() || desc == href => {
+                    output.push_str("[[");
+                    output.push_str(href);
+                    output.push_str("]]");
+                }
+                Some(href) => {
+                    output.push_str("[[");
+                    output.push_str(href);
+                    output.push_str("][");
+                    output.push_str(desc);
+                    output.push_str("]]");
+                }
+                None => output.push_str(desc),
+            }
+            return;
+        }
+        "img" => {
+            if let Some(src) = element.value().attr("src") {
+                output.push_str("[[");
+                output.push_str(src);
+                output.push_str("]]");
+            }
+            return;
+        }
+        "br" => {
+            output.push_str("\\\\\n");
+            return;
+        }
+        "script" | "style" => return,
+        _ => "",
+    };
+
+    let mut contents = String::new();
+    inlines(&mut contents, element);
+
+    // emphasis markers must be adjacent to non-whitespace characters
+    let trimmed = contents.trim();
+    if trimmed.is_empty() {
+        output.push_str(&contents);
+        return;
+    }
+    if contents.starts_with(char::is_whitespace) && !output.ends_with(' ') {
+        output.push(' ');
+    }
+    output.push_str(marker);
+    output.push_str(trimmed);
+    output.push_str(marker);
+    if contents.ends_with(char::is_whitespace) {
+        output.push(' ');
+    }
+}
+
+// collapses consecutive whitespaces into one space, just like browsers do
+fn push_text(output: &mut String, text: &str) {
+    let mut last_is_space = output.is_empty() || output.ends_with(char::is_whitespace);
+    for ch in text.chars() {
+        if ch.is_whitespace() {
+            if !last_is_space {
+                output.push(' ');
+            }
+            last_is_space = true;
+        } else {
+            output.push(ch);
+            last_is_space = false;
+        }
+    }
+}
+
+fn single_line(text: &str) -> String {
+    text.replace("\\\\\n", " ")
+        .replace('\n', " ")
+        .trim()
+        .to_string()
+}
+
+// lines starting with `*` or `#+` must be escaped with a comma inside blocks
+fn escape_line(line: &str) -> String {
+    let trimmed = line.trim_start();
+    if trimmed.starts_with('*') || trimmed.starts_with("#+") {
+        format!(",{}", line)
+    } else {
+        line.to_string()
+    }
+}
+
+#[test]
+fn convert() {
+    assert_eq!(
+        html_to_org("<h2>Title</h2><p>Some <strong>bold </strong>and <a href=\"https://example.com\">link</a>.</p>"),
+        "** Title\nSome *bold* and [[https://example.com][link]].\n\n"
+    );
+    assert_eq!(
+        html_to_org("<ul><li>one<ol><li>two</li></ol></li><li><em>three</em></li></ul>"),
+        "- one\n  1. two\n- /three/\n\n"
+    );
+    assert_eq!(
+        html_to_org("<pre><code class=\"language-rust\">fn main() {}\n</code></pre>"),
+        "#+BEGIN_SRC rust\nfn main() {}\n#+END_SRC\n\n"
+    );
+    assert_eq!(
+        html_to_org("<table><tr><th>a</th><th>b</th></tr><tr><td>1</td><td>2</td></tr></table>"),
+        "| a | b |\n|---+---|\n| 1 | 2 |\n\n"
+    );
+    assert_eq!(
+        html_to_org("<blockquote><p>quote</p></blockquote>"),
+        "#+BEGIN_QUOTE\nquote\n\n#+END_QUOTE\n\n"
+    );
+}
@@ -0,0 +1,445 @@
+use std::borrow::Cow;
+
+use crate::elements::{Datetime, Timestamp};
+use crate::import::{datetime, push_section, push_timestamp, tag_name, ImportError, ImportResult};
+use crate::Org;
+
+impl Org<'_> {
+    /// Converts iCalendar data into `Org` struct.
+    ///
+    /// Each `VEVENT` and `VTODO` component becomes a headline: `SUMMARY` is
+    /// used as the headline title, `CATEGORIES` as tags and `DESCRIPTION` as
+    /// its section. `DTSTART` becomes the scheduled timestamp, which spans to
+    /// `DTEND` if given, like `<2019-04-08 Mon 10:00-11:00>` on the same day
+    /// or `<2019-04-08 Mon>--<2019-04-10 Wed>` across days. `DUE` becomes the
+    /// deadline. `RRULE` is converted into a repeater.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::from_ics(
+    ///     "BEGIN:VCALENDAR\r\n\
+    ///      BEGIN:VEVENT\r\n\
+    ///      SUMMARY:Meeting\r\n\
+    ///      DTSTART:20190408T100000\r\n\
+    ///      RRULE:FREQ=WEEKLY\r\n\
+    ///      CATEGORIES:work\r\n\
+    ///      END:VEVENT\r\n\
+    ///      END:VCALENDAR\r\n",
+    /// )
+    /// .unwrap();
+    ///
+    /// let mut writer = Vec::new();
+    /// org.write_org(&mut writer).unwrap();
+    /// assert_eq!(
+    ///     String::from_utf8(writer).unwrap(),
+    ///     "* Meeting :work:\nSCHEDULED: <2019-04-08 Mon 10:00 +1w>\n"
+    /// );
+    /// ```
+    pub fn from_ics(ics: &str) -> ImportResult<Org<'static>> {
+        Ok(Org::parse_string(ics_to_org(ics)?))
+    }
+}
+
+#[derive(Default)]
+struct Component {
+    todo: bool,
+    summary: String,
+    description: String,
+    categories: Vec<String>,
+    status: Option<String>,
+    start: Option<Datetime<'static>>,
+    end: Option<Datetime<'static>>,
+    due: Option<Datetime<'static>>,
+    repeater: Option<String>,
+}
+
+impl Component {
+    fn property(&mut self, name: &str, value: &str) -> ImportResult<()> {
+        match name {
+            "SUMMARY" => self.summary = unescape(value),
+            "DESCRIPTION" => self.description = unescape(value),
+            "CATEGORIES" => self.categories.extend(
+                value
+                    .split(',')
+                    .map(unescape)
+                    .filter(|category| !category.trim().is_empty()),
+            ),
+            "STATUS" => self.status = Some(value.to_ascii_uppercase()),
+            "DTSTART" => self.start = Some(parse_date(value)?),
+            "DTEND" => self.end = Some(parse_date(value)?),
+            "DUE" => self.due = Some(parse_date(value)?),
+            "RRULE" => self.repeater = parse_rrule(value),
+            _ => (),
+        }
+        Ok(())
+    }
+
+    fn write(self, output: &mut String) {
+        let repeater: Option<Cow<'static, str>> = self.repeater.map(Into::into);
+
+        output.push('*');
+        if self.todo {
+            match self.status.as_deref() {
+                Some("COMPLETED") | Some("CANCELLED") => output.push_str(" DONE"),
+                _ => output.push_str(" TODO"),
+            }
+        }
+        output.push(' ');
+        output.push_str(
+            &self
+                .summary
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" "),
+        );
+        if !self.categories.is_empty() {
+            output.push_str(" :");
+            for category in &self.categories {
+                output.push_str(&tag_name(category));
+                output.push(':');
+            }
+        }
+        output.push('\n');
+
+        let mut planning = String::new();
+        if let Some(start) = self.start {
+            // the end date of all-day events is exclusive
+            let end = self
+                .end
+                .map(|end| match (start.hour, end.hour) {
+                    (None, None) => previous_day(end),
+                    _ => end,
+                })
+                .filter(|end| sort_key(end) > sort_key(&start));
+
+            planning.push_str("SCHEDULED: ");
+            match end {
+                Some(end)
+                    if (start.year, start.month, start.day) == (end.year, end.month, end.day) =>
+                {
+                    match (start.hour, start.minute, end.hour, end.minute) {
+                        (
+                            Some(start_hour),
+                            Some(start_minute),
+                            Some(end_hour),
+                            Some(end_minute),
+                        ) => {
+                            planning.push_str(&format!(
+                                "<{}-{:02}-{:02} {} {:02}:{:02}-{:02}:{:02}",
+                                start.year,
+                                start.month,
+                                start.day,
+                                start.dayname,
+                                start_hour,
+                                start_minute,
+                                end_hour,
+                                end_minute
+                            ));
+                            if let Some(repeater) = &repeater {
+                                planning.push(' ');
+                                planning.push_str(repeater);
+                            }
+                            planning.push('>');
+                        }
+                        _ => push_timestamp(
+                            &mut planning,
+                            &Timestamp::Active {
+                                start,
+                                repeater: repeater.clone(),
+                                delay: None,
+                            },
+                        ),
+                    }
+                }
+                Some(end) => push_timestamp(
+                    &mut planning,
+                    &Timestamp::ActiveRange {
+                        start,
+                        end,
+                        repeater: repeater.clone(),
+                        delay: None,
+                    },
+                ),
+                None => push_timestamp(
+                    &mut planning,
+                    &Timestamp::Active {
+                        start,
+                        repeater: repeater.clone(),
+                        delay: None,
+                    },
+                ),
+            }
+        }
+        if let Some(deadline) = self.due {
+            if !planning.is_empty() {
+                planning.push(' ');
+            }
+            planning.push_str("DEADLINE: ");
+            push_timestamp(
+                &mut planning,
+                &Timestamp::Active {
+                    start: deadline,
+                    repeater: None,
+                    delay: None,
+                },
+            );
+        }
+        if !planning.is_empty() {
+            output.push_str(&planning);
+            output.push('\n');
+        }
+
+        push_section(output, &self.description);
+    }
+}
+
+fn ics_to_org(ics: &str) -> ImportResult<String> {
+    // unfold lines, a line beginning with a whitespace continues the previous one
+    let mut lines: Vec<String> = Vec::new();
+    for line in ics.lines() {
+        if line.starts_with(' ') || line.starts_with('\t') {
+            if let Some(last) = lines.last_mut() {
+                last.push_str(&line[1..]);
+                continue;
+            }
+        }
+        if !line.trim().is_empty() {
+            lines.push(line.to_string());
+        }
+    }
+
+    if !lines
+        .first()
+        .map(|line| line.trim().eq_ignore_ascii_case("BEGIN:VCALENDAR"))
+        .unwrap_or_default()
+    {
+        return Err(ImportError::InvalidInput {
+            reason: String::from("expected BEGIN:VCALENDAR"),
+        });
+    }
+
+    let mut output = String::new();
+    let mut component: Option<Component> = None;
+    // depth of nested components, e.g. VALARM, inside current component
+    let mut nested = 0;
+
+    for line in &lines {
+        let (name, value) = split_content_line(line)?;
+        let name = name.to_ascii_uppercase();
+        let is_component = matches!(value.to_ascii_uppercase().as_str(), "VEVENT" | "VTODO");
+
+        match (name.as_str(), &mut component) {
+            ("BEGIN", None) if is_component => {
+                component = Some(Component {
+                    todo: value.eq_ignore_ascii_case("VTODO"),
+                    ..Default::default()
+                });
+            }
+            ("BEGIN", Some(_)) => nested += 1,
+            ("END", Some(_)) if nested > 0 => nested -= 1,
+            ("END", Some(_)) if is_component => {
+                if let Some(component) = component.take() {
+                    component.write(&mut output);
+                }
+            }
+            (_, Some(component)) if nested == 0 => component.property(&name, value)?,
+            _ => (),
+        }
+    }
+
+    Ok(output)
+}
+
+// splits a content line into its name, without parameters, and value
+fn split_content_line(line: &str) -> ImportResult<(&str, &str)> {
+    let mut quoted = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            ':' if !quoted => {
+                let name = line[..i].split(';').next().unwrap_or_default();
+                return Ok((name, &line[i + 1..]));
+            }
+            _ => (),
+        }
+    }
+    Err(ImportError::InvalidInput {
+        reason: format!("expected a content line, found {:?}", line),
+    })
+}
+
+// parses `20190408` or `20190408T100000(Z)`, time zone is ignored
+fn parse_date(value: &str) -> ImportResult<Datetime<'static>> {
+    let value = value.trim();
+    let number = |start: usize, end: usize| {
+        value
+            .get(start..end)
+            .filter(|s| s.chars().all(|c| c.is_ascii_digit()))
+            .and_then(|s| s.parse::<u16>().ok())
+    };
+
+    let date = (number(0, 4), number(4, 6), number(6, 8));
+    let time = if value.as_bytes().get(8) == Some(&b'T') {
+        Some((number(9, 11), number(11, 13)))
+    } else {
+        None
+    };
+
+    match (date, time) {
+        ((Some(year), Some(month), Some(day)), None) => {
+            Ok(datetime(year, month as u8, day as u8, None))
+        }
+        ((Some(year), Some(month), Some(day)), Some((Some(hour), Some(minute)))) => Ok(datetime(
+            year,
+            month as u8,
+            day as u8,
+            Some((hour as u8, minute as u8)),
+        )),
+        _ => Err(ImportError::InvalidInput {
+            reason: format!("expected a date or date-time, found {:?}", value),
+        }),
+    }
+}
+
+fn sort_key(datetime: &Datetime) -> (u16, u8, u8, u8, u8) {
+    (
+        datetime.year,
+        datetime.month,
+        datetime.day,
+        datetime.hour.unwrap_or_default(),
+        datetime.minute.unwrap_or_default(),
+    )
+}
+
+// returns the date before `date`, used for the exclusive end of all-day events
+fn previous_day(date: Datetime<'static>) -> Datetime<'static> {
+    let (year, month, day) = match (date.year, date.month, date.day) {
+        (year, month, day) if day > 1 => (year, month, day - 1),
+        (year, month, _) if month > 1 => (year, month - 1, days_in_month(year, month - 1)),
+        (year, _, _) => (year.saturating_sub(1), 12, 31),
+    };
+    datetime(year, month, day, None)
+}
+
+fn days_in_month(year: u16, month: u8) -> u8 {
+    let leap = matches!(
+        (year % 4, year % 100, year % 400),
+        (_, _, 0) | (0, 1..=99, _)
+    );
+    match month {
+        2 if leap => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// converts `FREQ=WEEKLY;INTERVAL=2` to `+2w`
+fn parse_rrule(value: &str) -> Option<String> {
+    let mut unit = None;
+    let mut interval = 1;
+    for part in value.split(';') {
+        let mut pair = part.splitn(2, '=');
+        match (pair.next(), pair.next()) {
+            (Some("FREQ"), Some(freq)) => {
+                unit = match freq {
+                    "HOURLY" => Some('h'),
+                    "DAILY" => Some('d'),
+                    "WEEKLY" => Some('w'),
+                    "MONTHLY" => Some('m'),
+                    "YEARLY" => Some('y'),
+                    _ => None,
+                }
+            }
+            (Some("INTERVAL"), Some(value)) => interval = value.parse().unwrap_or(1),
+            _ => (),
+        }
+    }
+    unit.map(|unit| format!("+{}{}", interval, unit))
+}
+
+fn unescape(value: &str) -> String {
+    let mut output = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('n') | Some('N') => output.push('\n'),
+                Some(c) => output.push(c),
+                None => (),
+            }
+        } else {
+            output.push(c);
+        }
+    }
+    output
+}
+
+#[test]
+fn convert() {
+    assert_eq!(
+        ics_to_org(
+            "BEGIN:VCALENDAR\n\
+             VERSION:2.0\n\
+             BEGIN:VEVENT\n\
+             SUMMARY:Team\n  meeting\n\
+             DTSTART;TZID=Europe/Berlin:20190408T100000\n\
+             DTEND;TZID=Europe/Berlin:20190408T110000\n\
+             RRULE:FREQ=WEEKLY;INTERVAL=2\n\
+             DESCRIPTION:Line 1\\nLine 2\\, with comma\n\
+             CATEGORIES:work,team meeting\n\
+             BEGIN:VALARM\n\
+             DESCRIPTION:Reminder\n\
+             END:VALARM\n\
+             END:VEVENT\n\
+             BEGIN:VTODO\n\
+             SUMMARY:Report\n\
+             DUE;VALUE=DATE:20190410\n\
+             STATUS:COMPLETED\n\
+             END:VTODO\n\
+             END:VCALENDAR\n"
+        )
+        .unwrap(),
+        "* Team meeting :work:team_meeting:\n\
+         SCHEDULED: <2019-04-08 Mon 10:00-11:00 +2w>\n\
+         Line 1\n\
+         Line 2, with comma\n\n\
+         * DONE Report\n\
+         DEADLINE: <2019-04-10 Wed>\n"
+    );
+
+    assert_eq!(
+        ics_to_org(
+            "BEGIN:VCALENDAR\n\
+             BEGIN:VEVENT\n\
+             SUMMARY:Trip\n\
+             DTSTART:20190408T100000\n\
+             DTEND:20190410T180000\n\
+             END:VEVENT\n\
+             BEGIN:VEVENT\n\
+             SUMMARY:Holiday\n\
+             DTSTART;VALUE=DATE:20190301\n\
+             DTEND;VALUE=DATE:20190302\n\
+             END:VEVENT\n\
+             BEGIN:VEVENT\n\
+             SUMMARY:Conference\n\
+             DTSTART;VALUE=DATE:20190228\n\
+             DTEND;VALUE=DATE:20190302\n\
+             END:VEVENT\n\
+             END:VCALENDAR\n"
+        )
+        .unwrap(),
+        "* Trip\n\
+         SCHEDULED: <2019-04-08 Mon 10:00>--<2019-04-10 Wed 18:00>\n\
+         * Holiday\n\
+         SCHEDULED: <2019-03-01 Fri>\n\
+         * Conference\n\
+         SCHEDULED: <2019-02-28 Thu>--<2019-03-01 Fri>\n"
+    );
+    assert!(ics_to_org("BEGIN:VCALENDAR\nBEGIN:VEVENT\nDTSTART:00000101\nEND:VEVENT\n").is_ok());
+
+    assert!(ics_to_org("BEGIN:VEVENT\nEND:VEVENT\n").is_err());
+    assert!(ics_to_org("BEGIN:VCALENDAR\nBEGIN:VEVENT\nDTSTART:1234567é\nEND:VEVENT\n").is_err());
+    assert!(ics_to_org("BEGIN:VCALENDAR\nBEGIN:VEVENT\nDTSTART:2019\nEND:VEVENT\n").is_err());
+}
@@ -0,0 +1,350 @@
+use serde_json::Value;
+
+use crate::config::ParseConfig;
+use crate::elements::Timestamp;
+use crate::import::{
+    jira_wiki::jira_wiki_to_org, parse_iso8601, push_properties, push_timestamp, tag_name,
+    ImportError, ImportResult,
+};
+use crate::Org;
+
+/// Jira import configuration
+#[derive(Clone, Debug)]
+pub struct JiraImportConfig {
+    /// Todo keywords of specific Jira statuses, e.g. `("In Progress", "NEXT")`
+    ///
+    /// Status names are matched case-insensitively.
+    pub statuses: Vec<(String, String)>,
+    /// Todo keywords of other statuses, chosen by their status category,
+    /// default is `("TODO", "DONE")`
+    pub todo_keywords: (String, String),
+    /// Field containing story points, default is `"customfield_10016"`
+    pub story_points_field: String,
+}
+
+impl Default for JiraImportConfig {
+    fn default() -> Self {
+        JiraImportConfig {
+            statuses: Vec::new(),
+            todo_keywords: (String::from("TODO"), String::from("DONE")),
+            story_points_field: String::from("customfield_10016"),
+        }
+    }
+}
+
+impl Org<'_> {
+    /// Converts Jira issues into `Org` struct.
+    ///
+    /// Accepts the json returned by Jira's search API, an array of issues or
+    /// a single issue. Each issue becomes a headline: its status is mapped
+    /// to a todo keyword, components become tags, story points are stored in
+    /// the `EFFORT` property, and the description in Jira wiki markup is
+    /// converted into its section.
+    ///
+    /// Keywords of issues in the `done` status category are parsed as done
+    /// keywords, and the others as todo keywords.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::from_jira_json(
+    ///     r#"{ "issues": [{
+    ///         "key": "PROJ-1",
+    ///         "fields": {
+    ///             "summary": "Crash on startup",
+    ///             "status": { "name": "Done", "statusCategory": { "key": "done" } },
+    ///             "components": [{ "name": "core" }],
+    ///             "customfield_10016": 3.0,
+    ///             "description": "It crashes with *panic*."
+    ///         }
+    ///     }] }"#,
+    /// )
+    /// .unwrap();
+    ///
+    /// let mut writer = Vec::new();
+    /// org.write_org(&mut writer).unwrap();
+    /// assert_eq!(
+    ///     String::from_utf8(writer).unwrap(),
+    ///     "* DONE Crash on startup :core:\n:PROPERTIES:\n:JIRA: PROJ-1\n:EFFORT: 3\n:END:\nIt crashes with *panic*.\n\n"
+    /// );
+    /// ```
+    pub fn from_jira_json(json: &str) -> ImportResult<Org<'static>> {
+        Org::from_jira_json_custom(json, &JiraImportConfig::default())
+    }
+
+    /// Likes `from_jira_json`, but with custom `JiraImportConfig`.
+    pub fn from_jira_json_custom(
+        json: &str,
+        config: &JiraImportConfig,
+    ) -> ImportResult<Org<'static>> {
+        let (text, parse_config) = jira_to_org(json, config)?;
+        Ok(Org::parse_string_custom(text, &parse_config))
+    }
+}
+
+// returns the org text, and a parse config containing every keyword used
+fn jira_to_org(json: &str, config: &JiraImportConfig) -> ImportResult<(String, ParseConfig)> {
+    let value: Value = serde_json::from_str(json)?;
+
+    let issues = match &value {
+        Value::Array(issues) => issues.iter().collect(),
+        Value::Object(object) => match object.get("issues") {
+            Some(Value::Array(issues)) => issues.iter().collect(),
+            _ => vec![&value],
+        },
+        _ => {
+            return Err(ImportError::InvalidInput {
+                reason: String::from("expected an issue object or an array of issues"),
+            })
+        }
+    };
+
+    let mut output = String::new();
+    let mut keywords = (
+        vec![config.todo_keywords.0.clone()],
+        vec![config.todo_keywords.1.clone()],
+    );
+
+    for issue in issues {
+        // issues from REST API put everything under `fields`
+        let fields = issue.get("fields").unwrap_or(issue);
+
+        let summary = fields
+            .get("summary")
+            .and_then(Value::as_str)
+            .ok_or_else(|| ImportError::InvalidInput {
+                reason: String::from("expected issue summary"),
+            })?;
+
+        output.push('*');
+        if let Some((keyword, done)) = fields
+            .get("status")
+            .and_then(|status| status_keyword(status, config))
+        {
+            output.push(' ');
+            output.push_str(keyword);
+            if !keywords.0.iter().chain(&keywords.1).any(|k| k == keyword) {
+                let list = if done {
+                    &mut keywords.1
+                } else {
+                    &mut keywords.0
+                };
+                list.push(keyword.to_string());
+            }
+        }
+        output.push(' ');
+        output.push_str(&summary.split_whitespace().collect::<Vec<_>>().join(" "));
+
+        let components: Vec<_> = fields
+            .get("components")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|component| {
+                component
+                    .as_str()
+                    .or_else(|| component.get("name")?.as_str())
+            })
+            .collect();
+        if !components.is_empty() {
+            output.push_str(" :");
+            for component in components {
+                output.push_str(&tag_name(component));
+                output.push(':');
+            }
+        }
+        output.push('\n');
+
+        let due = fields.get("duedate").and_then(Value::as_str);
+        if let Some(due) = due.and_then(parse_iso8601) {
+            output.push_str("DEADLINE: ");
+            push_timestamp(
+                &mut output,
+                &Timestamp::Active {
+                    start: due,
+                    repeater: None,
+                    delay: None,
+                },
+            );
+            output.push('\n');
+        }
+
+        let mut properties = Vec::new();
+        if let Some(key) = issue.get("key").and_then(Value::as_str) {
+            properties.push(("JIRA", key.to_string()));
+        }
+        if let Some(points) = fields
+            .get(&config.story_points_field)
+            .and_then(Value::as_f64)
+        {
+            properties.push(("EFFORT", points.to_string()));
+        }
+        push_properties(&mut output, &properties);
+
+        let description = match fields.get("description") {
+            Some(Value::String(description)) => jira_wiki_to_org(description.trim(), 1),
+            // atlassian document format, used by REST API v3
+            Some(description @ Value::Object(_)) => {
+                let mut text = String::new();
+                adf_text(description, &mut text);
+                jira_wiki_to_org(text.trim(), 1)
+            }
+            _ => String::new(),
+        };
+        if !description.is_empty() {
+            output.push_str(&description);
+            output.push('\n');
+        }
+    }
+
+    let parse_config = ParseConfig {
+        todo_keywords: keywords,
+        ..Default::default()
+    };
+
+    Ok((output, parse_config))
+}
+
+// returns the keyword of this status, and whether it's in the done category
+fn status_keyword<'a>(status: &Value, config: &'a JiraImportConfig) -> Option<(&'a str, bool)> {
+    let name = status.get("name").and_then(Value::as_str)?;
+
+    let category = status
+        .get("statusCategory")
+        .and_then(|category| category.get("key"))
+        .and_then(Value::as_str);
+    let done = category == Some("done");
+
+    if let Some((_, keyword)) = config
+        .statuses
+        .iter()
+        .find(|(status, _)| status.eq_ignore_ascii_case(name))
+    {
+        return Some((keyword, done));
+    }
+
+    if done {
+        Some((&config.todo_keywords.1, done))
+    } else {
+        Some((&config.todo_keywords.0, done))
+    }
+}
+
+// collects plain text from atlassian document format, one line per block
+fn adf_text(node: &Value, output: &mut String) {
+    if let Some(text) = node.get("text").and_then(Value::as_str) {
+        output.push_str(text);
+    }
+    for child in node
+        .get("content")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        adf_text(child, output);
+    }
+    match node.get("type").and_then(Value::as_str) {
+        Some("paragraph") | Some("heading") | Some("codeBlock") => output.push_str("\n\n"),
+        Some("hardBreak") => output.push('\n'),
+        _ => (),
+    }
+}
+
+#[test]
+fn convert() {
+    let json = r#"[{
+        "key": "PROJ-1",
+        "fields": {
+            "summary": "Review   code",
+            "status": { "name": "In Review", "statusCategory": { "key": "indeterminate" } },
+            "components": [{ "name": "web ui" }],
+            "duedate": "2019-04-10",
+            "story_points": 2.5,
+            "description": "h3. Notes\n* check {{lib.rs}}"
+        }
+    }, {
+        "key": "PROJ-2",
+        "fields": {
+            "summary": "Deploy",
+            "status": { "name": "Closed", "statusCategory": { "key": "done" } },
+            "description": {
+                "type": "doc",
+                "content": [{
+                    "type": "paragraph",
+                    "content": [{ "type": "text", "text": "Ship it" }]
+                }]
+            }
+        }
+    }]"#;
+
+    assert_eq!(
+        jira_to_org(
+            json,
+            &JiraImportConfig {
+                statuses: vec![(String::from("in review"), String::from("REVIEW"))],
+                story_points_field: String::from("story_points"),
+                ..Default::default()
+            }
+        )
+        .unwrap()
+        .0,
+        "* REVIEW Review code :web_ui:\n\
+         DEADLINE: <2019-04-10 Wed>\n\
+         :PROPERTIES:\n\
+         :JIRA: PROJ-1\n\
+         :EFFORT: 2.5\n\
+         :END:\n\
+         **** Notes\n\
+         - check ~lib.rs~\n\n\
+         * DONE Deploy\n\
+         :PROPERTIES:\n\
+         :JIRA: PROJ-2\n\
+         :END:\n\
+         Ship it\n\n"
+    );
+
+    assert!(jira_to_org("42", &JiraImportConfig::default()).is_err());
+}
+
+#[test]
+fn custom_statuses() {
+    let org = Org::from_jira_json_custom(
+        r#"[{
+            "fields": {
+                "summary": "Review code",
+                "status": { "name": "In Review", "statusCategory": { "key": "indeterminate" } }
+            }
+        }, {
+            "fields": {
+                "summary": "Drop support",
+                "status": { "name": "Won't Do", "statusCategory": { "key": "done" } }
+            }
+        }]"#,
+        &JiraImportConfig {
+            statuses: vec![
+                (String::from("In Review"), String::from("REVIEW")),
+                (String::from("Won't Do"), String::from("CANCELED")),
+            ],
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    let titles: Vec<_> = org
+        .headlines()
+        .map(|headline| {
+            let title = headline.title(&org);
+            (
+                title.keyword.as_deref().map(String::from),
+                title.raw.to_string(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        titles,
+        [
+            (Some(String::from("REVIEW")), String::from("Review code")),
+            (Some(String::from("CANCELED")), String::from("Drop support")),
+        ]
+    );
+}
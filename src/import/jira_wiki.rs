@@ -0,0 +1,267 @@
+//! A small Jira wiki markup to org-mode converter.
+
+/// Converts Jira wiki markup into org-mode text.
+///
+/// Jira headings are converted into headlines, nested under `level`.
+pub(crate) fn jira_wiki_to_org(input: &str, level: usize) -> String {
+    let mut output = String::new();
+    let mut lines = input.lines();
+
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim();
+
+        if let Some((name, parameters, end, rest)) = block_start(trimmed) {
+            output.push_str("#+BEGIN_");
+            output.push_str(name);
+            if !parameters.is_empty() {
+                output.push(' ');
+                output.push_str(parameters);
+            }
+            output.push('\n');
+            let mut contents = Vec::new();
+            if let Some(index) = rest.find(end) {
+                contents.push(&rest[..index]);
+            } else {
+                contents.push(rest);
+                for line in lines.by_ref() {
+                    if let Some(index) = line.find(end) {
+                        contents.push(&line[..index]);
+                        break;
+                    }
+                    contents.push(line);
+                }
+            }
+            // the opening and closing tags may share lines with contents
+            if matches!(contents.first(), Some(line) if line.trim().is_empty()) {
+                contents.remove(0);
+            }
+            if matches!(contents.last(), Some(line) if line.trim().is_empty()) {
+                contents.pop();
+            }
+            for line in contents {
+                if name == "QUOTE" {
+                    push_inline(&mut output, line.trim());
+                } else {
+                    if line.starts_with('*') || line.trim_start().starts_with("#+") {
+                        output.push(',');
+                    }
+                    output.push_str(line);
+                }
+                output.push('\n');
+            }
+            output.push_str("#+END_");
+            output.push_str(name);
+            output.push('\n');
+        } else if let Some((depth, title)) = heading(trimmed) {
+            for _ in 0..level + depth {
+                output.push('*');
+            }
+            output.push(' ');
+            push_inline(&mut output, title);
+            output.push('\n');
+        } else if let Some(quote) = trimmed.strip_prefix("bq. ") {
+            output.push_str("#+BEGIN_QUOTE\n");
+            push_inline(&mut output, quote.trim());
+            output.push_str("\n#+END_QUOTE\n");
+        } else if trimmed == "----" {
+            output.push_str("-----\n");
+        } else if let Some((bullets, contents)) = list_item(trimmed) {
+            let depth = bullets.len();
+            for _ in 1..depth {
+                output.push_str("  ");
+            }
+            output.push_str(if bullets.ends_with('#') { "1. " } else { "- " });
+            push_inline(&mut output, contents);
+            output.push('\n');
+        } else if trimmed.starts_with("||") {
+            let cells: Vec<_> = trimmed.trim_matches('|').split("||").collect();
+            push_row(&mut output, &cells);
+            output.push('|');
+            for (i, _) in cells.iter().enumerate() {
+                if i > 0 {
+                    output.push('+');
+                }
+                output.push_str("---");
+            }
+            output.push_str("|\n");
+        } else if trimmed.starts_with('|') {
+            let cells: Vec<_> = trimmed.trim_matches('|').split('|').collect();
+            push_row(&mut output, &cells);
+        } else {
+            // a line starting with stars would be parsed as headline
+            if trimmed.starts_with('*') {
+                output.push(' ');
+            }
+            push_inline(&mut output, trimmed);
+            output.push('\n');
+        }
+    }
+
+    output
+}
+
+// returns the name and parameters of org block, the closing tag and the rest of line
+fn block_start(line: &str) -> Option<(&'static str, &str, &'static str, &str)> {
+    let (name, end) = if line.starts_with("{code") {
+        ("code", "{code}")
+    } else if line.starts_with("{noformat") {
+        ("noformat", "{noformat}")
+    } else if line.starts_with("{quote}") {
+        ("quote", "{quote}")
+    } else {
+        return None;
+    };
+
+    let close = line.find('}')?;
+    let parameters = line[1 + name.len()..close].trim_start_matches(':');
+    let rest = &line[close + 1..];
+
+    match name {
+        // parameters look like `java` or `title=Foo.java|borderStyle=solid`
+        "code" => {
+            let language = parameters
+                .split('|')
+                .find(|parameter| !parameter.contains('='))
+                .unwrap_or_default();
+            Some(("SRC", language, end, rest))
+        }
+        "noformat" => Some(("EXAMPLE", "", end, rest)),
+        _ => Some(("QUOTE", "", end, rest)),
+    }
+}
+
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let mut chars = line.chars();
+    match (chars.next(), chars.next(), chars.next()) {
+        (Some('h'), Some(depth @ '1'..='6'), Some('.')) => {
+            Some((depth as usize - '0' as usize, line[3..].trim()))
+        }
+        _ => None,
+    }
+}
+
+fn list_item(line: &str) -> Option<(&str, &str)> {
+    let len = line
+        .chars()
+        .take_while(|&c| c == '*' || c == '#' || c == '-')
+        .count();
+    if len == 0 || !line[len..].starts_with(' ') {
+        return None;
+    }
+    let bullets = &line[..len];
+    // `-` is only a bullet on its own, and `----` is a rule
+    if bullets.contains('-') && bullets != "-" {
+        return None;
+    }
+    Some((bullets, line[len..].trim()))
+}
+
+fn push_row(output: &mut String, cells: &[&str]) {
+    output.push('|');
+    for cell in cells {
+        output.push(' ');
+        push_inline(output, cell.trim());
+        output.push_str(" |");
+    }
+    output.push('\n');
+}
+
+fn push_inline(output: &mut String, text: &str) {
+    let mut rest = text;
+    let mut prev = ' ';
+
+    while let Some(c) = rest.chars().next() {
+        match convert_inline(rest, prev) {
+            Some((converted, len)) => {
+                output.push_str(&converted);
+                rest = &rest[len..];
+                prev = converted.chars().last().unwrap_or(' ');
+            }
+            None => {
+                output.push(c);
+                rest = &rest[c.len_utf8()..];
+                prev = c;
+            }
+        }
+    }
+}
+
+// tries to convert the markup at the beginning of text, returns the
+// converted string and the length of consumed text
+fn convert_inline(text: &str, prev: char) -> Option<(String, usize)> {
+    if let Some(rest) = text.strip_prefix("{{") {
+        let end = rest.find("}}")?;
+        return Some((format!("~{}~", &rest[..end]), end + 4));
+    }
+
+    if let Some(rest) = text.strip_prefix('[') {
+        let end = rest.find(']')?;
+        let inner = &rest[..end];
+        // attachments and user mentions
+        if inner.is_empty() || inner.starts_with('^') || inner.starts_with('~') {
+            return None;
+        }
+        let converted = match inner.find('|') {
+            Some(bar) => format!("[[{}][{}]]", inner[bar + 1..].trim(), inner[..bar].trim()),
+            None => format!("[[{}]]", inner.trim()),
+        };
+        return Some((converted, end + 2));
+    }
+
+    // emphasis markers must not be inside a word
+    if prev.is_alphanumeric() {
+        return None;
+    }
+
+    for (marker, org) in &[('*', '*'), ('_', '/'), ('-', '+'), ('+', '_')] {
+        if let Some(rest) = text.strip_prefix(*marker) {
+            if rest.starts_with(char::is_whitespace) {
+                return None;
+            }
+            let end = rest.match_indices(*marker).map(|(i, _)| i).find(|&i| {
+                i > 0
+                    && !rest[..i].ends_with(char::is_whitespace)
+                    && !rest[i + 1..].starts_with(char::is_alphanumeric)
+            })?;
+            let mut contents = String::new();
+            push_inline(&mut contents, &rest[..end]);
+            return Some((format!("{0}{1}{0}", org, contents), end + 2));
+        }
+    }
+
+    None
+}
+
+#[test]
+fn convert() {
+    assert_eq!(
+        jira_wiki_to_org(
+            "h2. Steps\n\
+             # Run {{cargo test}}\n\
+             ## See *error* in [log|https://example.com]\n\
+             * a -deleted- well-known _item_\n\
+             ||a||b||\n\
+             |1|2|\n\
+             {code:rust}\n\
+             fn main() {}\n\
+             {code}\n\
+             bq. quoted\n\
+             ----",
+            1
+        ),
+        "*** Steps\n\
+         1. Run ~cargo test~\n  \
+         1. See *error* in [[https://example.com][log]]\n\
+         - a +deleted+ well-known /item/\n\
+         | a | b |\n\
+         |---+---|\n\
+         | 1 | 2 |\n\
+         #+BEGIN_SRC rust\n\
+         fn main() {}\n\
+         #+END_SRC\n\
+         #+BEGIN_QUOTE\n\
+         quoted\n\
+         #+END_QUOTE\n\
+         -----\n"
+    );
+}
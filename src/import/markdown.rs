@@ -0,0 +1,223 @@
+//! A small markdown to org-mode converter, covering the subset of markdown
+//! commonly found in issue trackers.
+
+/// Converts markdown text into org-mode text.
+///
+/// Markdown headings are converted into headlines, nested under `level`.
+// only the github importer uses it for now
+#[cfg_attr(not(feature = "serde_json"), allow(dead_code))]
+pub(crate) fn markdown_to_org(input: &str, level: usize) -> String {
+    let mut output = String::new();
+    let mut lines = input.lines();
+    let mut in_quote = false;
+
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim_start();
+
+        if in_quote && !trimmed.starts_with('>') {
+            output.push_str("#+END_QUOTE\n");
+            in_quote = false;
+        }
+
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            let fence = &trimmed[..3];
+            let language = trimmed[3..].trim();
+            output.push_str("#+BEGIN_SRC");
+            if !language.is_empty() {
+                output.push(' ');
+                output.push_str(language);
+            }
+            output.push('\n');
+            for line in lines.by_ref() {
+                if line.trim_start().starts_with(fence) {
+                    break;
+                }
+                if line.starts_with('*') || line.trim_start().starts_with("#+") {
+                    output.push(',');
+                }
+                output.push_str(line);
+                output.push('\n');
+            }
+            output.push_str("#+END_SRC\n");
+        } else if let Some(quote) = trimmed.strip_prefix('>') {
+            if !in_quote {
+                output.push_str("#+BEGIN_QUOTE\n");
+                in_quote = true;
+            }
+            push_inline(&mut output, quote.trim_start());
+            output.push('\n');
+        } else if trimmed.starts_with('#') {
+            let depth = trimmed.chars().take_while(|&c| c == '#').count();
+            let title = trimmed[depth..].trim().trim_end_matches('#').trim();
+            if depth <= 6 && (title.is_empty() || trimmed[depth..].starts_with(' ')) {
+                for _ in 0..level + depth {
+                    output.push('*');
+                }
+                output.push(' ');
+                push_inline(&mut output, title);
+                output.push('\n');
+            } else {
+                push_inline(&mut output, line);
+                output.push('\n');
+            }
+        } else if is_rule(trimmed) {
+            output.push_str("-----\n");
+        } else if let Some(item) = list_item(trimmed) {
+            let indent = line.len() - trimmed.len();
+            for _ in 0..indent {
+                output.push(' ');
+            }
+            let (bullet, contents) = item;
+            output.push_str(bullet);
+            push_inline(&mut output, contents);
+            output.push('\n');
+        } else {
+            // a line starting with stars would be parsed as headline
+            if line.starts_with('*') {
+                output.push(' ');
+            }
+            push_inline(&mut output, line);
+            output.push('\n');
+        }
+    }
+
+    if in_quote {
+        output.push_str("#+END_QUOTE\n");
+    }
+
+    output
+}
+
+fn is_rule(line: &str) -> bool {
+    let line = line.trim_end();
+    line.len() >= 3
+        && ['-', '*', '_'].iter().any(|&c| {
+            line.chars().all(|ch| ch == c || ch == ' ')
+                && line.chars().filter(|&ch| ch == c).count() >= 3
+        })
+}
+
+fn list_item(line: &str) -> Option<(&str, &str)> {
+    for bullet in &["- ", "* ", "+ "] {
+        if let Some(contents) = line.strip_prefix(bullet) {
+            let contents = contents
+                .strip_prefix("[ ] ")
+                .map(|c| ("- [ ] ", c))
+                .or_else(|| contents.strip_prefix("[x] ").map(|c| ("- [X] ", c)))
+                .or_else(|| contents.strip_prefix("[X] ").map(|c| ("- [X] ", c)));
+            return Some(contents.unwrap_or(("- ", &line[2..])));
+        }
+    }
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    if digits > 0 && line[digits..].starts_with(". ") {
+        return Some((&line[..digits + 2], &line[digits + 2..]));
+    }
+    None
+}
+
+fn push_inline(output: &mut String, text: &str) {
+    let mut rest = text;
+    let mut prev = ' ';
+
+    while let Some(c) = rest.chars().next() {
+        // emphasis markers inside a word, e.g. `snake_case`, are not markup
+        let in_word = prev.is_alphanumeric() && (c == '_' || c == '*');
+
+        match convert_inline(rest).filter(|_| !in_word) {
+            Some((converted, len)) => {
+                output.push_str(&converted);
+                rest = &rest[len..];
+                prev = converted.chars().last().unwrap_or(' ');
+            }
+            None => {
+                output.push(c);
+                rest = &rest[c.len_utf8()..];
+                prev = c;
+            }
+        }
+    }
+}
+
+// tries to convert the markup at the beginning of text, returns the
+// converted string and the length of consumed text
+fn convert_inline(text: &str) -> Option<(String, usize)> {
+    if let Some(rest) = text.strip_prefix('`') {
+        let end = rest.find('`')?;
+        return Some((format!("~{}~", &rest[..end]), end + 2));
+    }
+
+    if text.starts_with("![") || text.starts_with('[') {
+        let start = if text.starts_with('!') { 2 } else { 1 };
+        let close = text.find("](")?;
+        let end = close + text[close..].find(')')?;
+        let desc = &text[start..close];
+        let url = &text[close + 2..end];
+        if desc.contains(']') || url.contains(char::is_whitespace) {
+            return None;
+        }
+        let converted = if start == 2 || desc.is_empty() {
+            format!("[[{}]]", url)
+        } else {
+            let mut desc_ = String::new();
+            push_inline(&mut desc_, desc);
+            format!("[[{}][{}]]", url, desc_)
+        };
+        return Some((converted, end + 1));
+    }
+
+    for (marker, org) in &[
+        ("**", "*"),
+        ("__", "*"),
+        ("~~", "+"),
+        ("*", "/"),
+        ("_", "/"),
+    ] {
+        if let Some(rest) = text.strip_prefix(marker) {
+            if rest.starts_with(char::is_whitespace) {
+                return None;
+            }
+            let end = rest.find(marker)?;
+            if end == 0 || rest[..end].ends_with(char::is_whitespace) {
+                return None;
+            }
+            let mut contents = String::new();
+            push_inline(&mut contents, &rest[..end]);
+            return Some((format!("{0}{1}{0}", org, contents), end + marker.len() * 2));
+        }
+    }
+
+    None
+}
+
+#[test]
+fn convert() {
+    assert_eq!(
+        markdown_to_org(
+            "## Steps\n\
+             1. Run `cargo test`\n\
+             2. See **error** in [log](https://example.com)\n\
+             \n\
+             - [x] done _item_ in snake_case\n  \
+             * nested\n\
+             > quoted\n\
+             ```rust\n\
+             fn main() {}\n\
+             ```\n\
+             ---",
+            1
+        ),
+        "*** Steps\n\
+         1. Run ~cargo test~\n\
+         2. See *error* in [[https://example.com][log]]\n\
+         \n\
+         - [X] done /item/ in snake_case\n  \
+         - nested\n\
+         #+BEGIN_QUOTE\n\
+         quoted\n\
+         #+END_QUOTE\n\
+         #+BEGIN_SRC rust\n\
+         fn main() {}\n\
+         #+END_SRC\n\
+         -----\n"
+    );
+}
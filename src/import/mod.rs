@@ -0,0 +1,205 @@
+//! Import various formats into `Org` struct.
+
+mod csv;
+#[cfg(feature = "serde_json")]
+mod emacs;
+#[cfg(feature = "calamine")]
+mod excel;
+#[cfg(feature = "serde_json")]
+mod github;
+#[cfg(feature = "scraper")]
+mod html;
+mod ics;
+#[cfg(feature = "serde_json")]
+mod jira;
+#[cfg(feature = "serde_json")]
+mod jira_wiki;
+mod markdown;
+#[cfg(feature = "quick-xml")]
+mod opml;
+#[cfg(feature = "quick-xml")]
+mod rss;
+
+pub use csv::CsvImportConfig;
+#[cfg(feature = "calamine")]
+pub use excel::ExcelImportConfig;
+#[cfg(feature = "serde_json")]
+pub use github::IssueImportConfig;
+#[cfg(feature = "serde_json")]
+pub use jira::JiraImportConfig;
+
+use std::error::Error;
+use std::fmt;
+
+use crate::elements::{Datetime, Timestamp};
+use crate::export::write_timestamp;
+
+/// Import Error
+///
+/// Some variants only exist when the corresponding feature is enabled.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ImportError {
+    /// Input is not valid for the given format
+    InvalidInput { reason: String },
+    /// Input is not a valid json
+    #[cfg(feature = "serde_json")]
+    Json(serde_json::Error),
+    /// Input is not a valid xml
+    #[cfg(feature = "quick-xml")]
+    Xml(quick_xml::Error),
+    /// Input is not a valid spreadsheet
+    #[cfg(feature = "calamine")]
+    Excel(calamine::Error),
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ImportError::InvalidInput { reason } => write!(f, "invalid input: {}", reason),
+            #[cfg(feature = "serde_json")]
+            ImportError::Json(err) => write!(f, "invalid json: {}", err),
+            #[cfg(feature = "quick-xml")]
+            ImportError::Xml(err) => write!(f, "invalid xml: {}", err),
+            #[cfg(feature = "calamine")]
+            ImportError::Excel(err) => write!(f, "invalid spreadsheet: {}", err),
+        }
+    }
+}
+
+impl Error for ImportError {}
+
+#[cfg(feature = "serde_json")]
+impl From<serde_json::Error> for ImportError {
+    fn from(err: serde_json::Error) -> Self {
+        ImportError::Json(err)
+    }
+}
+
+#[cfg(feature = "quick-xml")]
+impl From<quick_xml::Error> for ImportError {
+    fn from(err: quick_xml::Error) -> Self {
+        ImportError::Xml(err)
+    }
+}
+
+#[cfg(feature = "calamine")]
+impl From<calamine::Error> for ImportError {
+    fn from(err: calamine::Error) -> Self {
+        ImportError::Excel(err)
+    }
+}
+
+#[cfg(feature = "calamine")]
+impl From<calamine::XlsxError> for ImportError {
+    fn from(err: calamine::XlsxError) -> Self {
+        ImportError::Excel(err.into())
+    }
+}
+
+pub type ImportResult<T> = Result<T, ImportError>;
+
+/// Returns the abbreviated day name of the given date.
+pub(crate) fn day_name(year: u16, month: u8, day: u8) -> &'static str {
+    // Sakamoto's method
+    const OFFSETS: [i32; 12] = [0, 3, 2, 5, 0, 3, 5, 1, 4, 6, 2, 4];
+    // signed, since january and february of year 0 belong to year -1
+    let year = i32::from(year) - if month < 3 { 1 } else { 0 };
+    let weekday = (year + year.div_euclid(4) - year.div_euclid(100)
+        + year.div_euclid(400)
+        + OFFSETS[(month as usize + 11) % 12]
+        + i32::from(day))
+    .rem_euclid(7);
+    ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"][weekday as usize]
+}
+
+/// Constructs a `Datetime` with the day name filled.
+pub(crate) fn datetime(year: u16, month: u8, day: u8, time: Option<(u8, u8)>) -> Datetime<'static> {
+    Datetime {
+        year,
+        month,
+        day,
+        dayname: day_name(year, month, day).into(),
+        hour: time.map(|(hour, _)| hour),
+        minute: time.map(|(_, minute)| minute),
+    }
+}
+
+/// Writes a timestamp in org syntax.
+pub(crate) fn push_timestamp(output: &mut String, timestamp: &Timestamp) {
+    let mut writer = Vec::new();
+    if write_timestamp(&mut writer, timestamp).is_ok() {
+        output.push_str(&String::from_utf8_lossy(&writer));
+    }
+}
+
+/// Parses `2019-04-08` or `2019-04-08T10:00:00Z`, time zone is ignored.
+pub(crate) fn parse_iso8601(value: &str) -> Option<Datetime<'static>> {
+    let number = |start: usize, end: usize| value.get(start..end)?.parse::<u16>().ok();
+    let time = number(11, 13)
+        .zip(number(14, 16))
+        .map(|(hour, minute)| (hour as u8, minute as u8));
+
+    Some(datetime(
+        number(0, 4)?,
+        number(5, 7)? as u8,
+        number(8, 10)? as u8,
+        time,
+    ))
+}
+
+/// Writes a properties drawer, unless `properties` is empty.
+pub(crate) fn push_properties<V: AsRef<str>>(output: &mut String, properties: &[(&str, V)]) {
+    if properties.is_empty() {
+        return;
+    }
+    output.push_str(":PROPERTIES:\n");
+    for (key, value) in properties {
+        output.push(':');
+        output.push_str(key);
+        output.push_str(": ");
+        output.push_str(value.as_ref());
+        output.push('\n');
+    }
+    output.push_str(":END:\n");
+}
+
+/// Converts an arbitrary string into a valid headline tag.
+pub(crate) fn tag_name(name: &str) -> String {
+    name.trim()
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '_' || c == '@' || c == '#' || c == '%' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Writes plain text into a section, followed by a blank line.
+///
+/// Lines starting with a star are indented, so they won't be parsed as headlines.
+pub(crate) fn push_section(output: &mut String, text: &str) {
+    let text = text.trim();
+    if text.is_empty() {
+        return;
+    }
+    for line in text.lines() {
+        if line.starts_with('*') {
+            output.push(' ');
+        }
+        output.push_str(line);
+        output.push('\n');
+    }
+    output.push('\n');
+}
+
+#[test]
+fn day_name_() {
+    assert_eq!(day_name(2019, 4, 8), "Mon");
+    assert_eq!(day_name(2000, 2, 29), "Tue");
+    assert_eq!(day_name(2024, 12, 31), "Tue");
+    assert_eq!(day_name(0, 1, 1), "Sat");
+}
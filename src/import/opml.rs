@@ -0,0 +1,238 @@
+use quick_xml::{
+    events::{BytesStart, Event},
+    Reader,
+};
+
+use crate::config::DEFAULT_CONFIG;
+use crate::elements::title::is_tag_line;
+use crate::import::ImportResult;
+use crate::Org;
+
+impl Org<'_> {
+    /// Converts an OPML outline into `Org` struct.
+    ///
+    /// Each `<outline>` becomes a headline, whose level is its depth in the
+    /// outline: `text` is used as the headline title, `xmlUrl` is written
+    /// into its section as a link, and `type="rss"` becomes the `rss` tag.
+    /// `<title>` of the outline is used as `#+TITLE`.
+    ///
+    /// Titles looking like todo keywords, priorities or tags, and brackets
+    /// ending a link, are separated by zero width spaces, so they are kept
+    /// as text.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::from_opml(
+    ///     r#"<opml version="2.0"><head><title>Feeds</title></head><body>
+    ///     <outline text="Podcasts">
+    ///         <outline text="Show" type="rss" xmlUrl="https://example.com/feed.xml"/>
+    ///     </outline>
+    /// </body></opml>"#,
+    /// )
+    /// .unwrap();
+    ///
+    /// let mut writer = Vec::new();
+    /// org.write_org(&mut writer).unwrap();
+    /// assert_eq!(
+    ///     String::from_utf8(writer).unwrap(),
+    ///     "#+TITLE: Feeds\n* Podcasts\n** Show :rss:\n[[https://example.com/feed.xml][Show]]\n"
+    /// );
+    /// ```
+    pub fn from_opml(xml: &str) -> ImportResult<Org<'static>> {
+        Ok(Org::parse_string(opml_to_org(xml)?))
+    }
+}
+
+fn opml_to_org(xml: &str) -> ImportResult<String> {
+    let mut reader = Reader::from_str(xml);
+    reader.trim_text(true);
+
+    let mut output = String::new();
+    let mut buf = Vec::new();
+    // names of all opening elements
+    let mut path: Vec<Vec<u8>> = Vec::new();
+    let mut title: Option<String> = None;
+    // number of enclosing outline elements
+    let mut depth = 0;
+
+    loop {
+        match reader.read_event(&mut buf)? {
+            Event::Start(element) => {
+                let name = element.local_name().to_vec();
+                if name == b"outline" {
+                    depth += 1;
+                    push_outline(&mut output, &reader, &element, depth)?;
+                }
+                path.push(name);
+            }
+            Event::Empty(element) if element.local_name() == b"outline" => {
+                push_outline(&mut output, &reader, &element, depth + 1)?;
+            }
+            Event::End(_) => {
+                let name = path.pop();
+                if name.as_deref() == Some(&b"outline"[..]) {
+                    depth -= 1;
+                }
+            }
+            Event::Text(text)
+                if path.last().map(Vec::as_slice) == Some(b"title") && title.is_none() =>
+            {
+                title = Some(text.unescape_and_decode(&reader)?);
+            }
+            Event::Eof => break,
+            _ => (),
+        }
+        buf.clear();
+    }
+
+    if let Some(title) = title {
+        output.insert_str(0, &format!("#+TITLE: {}\n", title.trim()));
+    }
+
+    Ok(output)
+}
+
+fn push_outline(
+    output: &mut String,
+    reader: &Reader<&[u8]>,
+    element: &BytesStart,
+    level: usize,
+) -> ImportResult<()> {
+    let mut text = String::new();
+    let mut xml_url = String::new();
+    let mut rss = false;
+    for attribute in element.attributes() {
+        let attribute = attribute?;
+        let value = attribute.unescape_and_decode_value(reader)?;
+        match attribute.key {
+            b"text" => text = value,
+            b"title" if text.is_empty() => text = value,
+            b"xmlUrl" => xml_url = value,
+            b"type" => rss = value.eq_ignore_ascii_case("rss"),
+            _ => (),
+        }
+    }
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    output.push_str(&"*".repeat(level));
+    output.push(' ');
+    output.push_str(&escape_title(&text));
+    if rss {
+        output.push_str(" :rss:");
+    }
+    output.push('\n');
+    if !xml_url.is_empty() {
+        output.push_str("[[");
+        output.push_str(&xml_url.trim().replace('[', "%5B").replace(']', "%5D"));
+        output.push(']');
+        if !text.is_empty() {
+            output.push('[');
+            output.push_str(&escape_description(&text));
+            output.push(']');
+        }
+        output.push_str("]\n");
+    }
+
+    Ok(())
+}
+
+// inserts zero width spaces, so todo keywords, priorities, `COMMENT` and tags
+// in `text` are not parsed from the headline
+fn escape_title(text: &str) -> String {
+    let first = text.split(' ').next().unwrap_or_default();
+    let last = text.rsplit(' ').next().unwrap_or_default();
+
+    let mut output = String::new();
+    if first == "COMMENT"
+        || first.starts_with("[#")
+        || DEFAULT_CONFIG.todo_keywords.0.iter().any(|k| k == first)
+        || DEFAULT_CONFIG.todo_keywords.1.iter().any(|k| k == first)
+    {
+        output.push('\u{200B}');
+    }
+    output.push_str(text);
+    if is_tag_line(last) {
+        output.push('\u{200B}');
+    }
+    output
+}
+
+// separates brackets which would end the link description
+fn escape_description(text: &str) -> String {
+    let mut output = String::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        output.push(c);
+        if c == ']' && matches!(chars.peek(), None | Some(']')) {
+            output.push('\u{200B}');
+        }
+    }
+    output
+}
+
+#[test]
+fn convert() {
+    assert_eq!(
+        opml_to_org(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<opml version="1.0">
+<head><title>Outline</title></head>
+<body>
+    <outline text="A">
+        <outline title="B &amp; C"/>
+        <outline text="D">
+            <outline text="E" type="rss" xmlUrl="https://example.com/e.xml"/>
+        </outline>
+    </outline>
+    <outline text="F"/>
+</body>
+</opml>"#
+        )
+        .unwrap(),
+        "#+TITLE: Outline\n\
+         * A\n\
+         ** B & C\n\
+         ** D\n\
+         *** E :rss:\n\
+         [[https://example.com/e.xml][E]]\n\
+         * F\n"
+    );
+}
+
+#[test]
+fn escape() {
+    let org = Org::from_opml(
+        r#"<opml><body>
+    <outline text="TODO x :a:" xmlUrl="https://example.com/[1]"/>
+    <outline text="[#A] a ]] b"/>
+    <outline text="COMMENT c"/>
+</body></opml>"#,
+    )
+    .unwrap();
+
+    let titles: Vec<_> = org.headlines().map(|h| h.title(&org).clone()).collect();
+    assert_eq!(titles.len(), 3);
+    for title in &titles {
+        assert!(title.keyword.is_none());
+        assert!(title.priority.is_none());
+        assert!(title.tags.is_empty());
+        assert!(!title.is_commented());
+    }
+
+    let link = org
+        .iter()
+        .find_map(|event| match event {
+            crate::Event::Start(crate::Element::Link(link)) => Some(link.clone()),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(link.path, "https://example.com/%5B1%5D");
+    assert_eq!(link.desc.as_deref(), Some("TODO x :a:"));
+
+    let org = opml_to_org(r#"<opml><body><outline text="a ]]] [b]" xmlUrl="u"/></body></opml>"#);
+    assert_eq!(
+        org.unwrap(),
+        "* a ]]] [b]\n[[u][a ]\u{200B}]\u{200B}] [b]\u{200B}]]\n"
+    );
+}
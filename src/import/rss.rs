@@ -0,0 +1,208 @@
+use quick_xml::{events::Event, Reader};
+
+use crate::elements::Timestamp;
+use crate::import::{datetime, push_section, push_timestamp, tag_name, ImportResult};
+use crate::Org;
+
+impl Org<'_> {
+    /// Converts a rss feed into `Org` struct.
+    ///
+    /// Each `<item>` becomes a headline: `<title>` is used as the headline
+    /// title, `<category>`s as tags, `<pubDate>` as the scheduled timestamp,
+    /// and `<link>` and `<description>` are written into its section.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::from_rss(
+    ///     r#"<rss version="2.0"><channel><title>Feed</title>
+    ///     <item>
+    ///         <title>Post</title>
+    ///         <link>https://example.com/post</link>
+    ///         <pubDate>Mon, 08 Apr 2019 10:00:00 +0000</pubDate>
+    ///         <category>news</category>
+    ///     </item>
+    /// </channel></rss>"#,
+    /// )
+    /// .unwrap();
+    ///
+    /// let mut writer = Vec::new();
+    /// org.write_org(&mut writer).unwrap();
+    /// assert_eq!(
+    ///     String::from_utf8(writer).unwrap(),
+    ///     "#+TITLE: Feed\n* Post :news:\nSCHEDULED: <2019-04-08 Mon 10:00>\n[[https://example.com/post]]\n\n"
+    /// );
+    /// ```
+    pub fn from_rss(xml: &str) -> ImportResult<Org<'static>> {
+        Ok(Org::parse_string(rss_to_org(xml)?))
+    }
+}
+
+#[derive(Default)]
+struct Item {
+    title: String,
+    link: String,
+    description: String,
+    pub_date: String,
+    categories: Vec<String>,
+}
+
+#[derive(Default)]
+struct Feed {
+    title: Option<String>,
+    items: Vec<Item>,
+    // item being parsed
+    item: Option<Item>,
+    // names of all opening elements
+    path: Vec<Vec<u8>>,
+}
+
+impl Feed {
+    fn text(&mut self, text: &str) {
+        let name = self.path.last().map(Vec::as_slice);
+        if let Some(item) = &mut self.item {
+            match name {
+                Some(b"title") => item.title.push_str(text),
+                Some(b"link") => item.link.push_str(text),
+                Some(b"description") => item.description.push_str(text),
+                Some(b"pubDate") => item.pub_date.push_str(text),
+                Some(b"category") => item.categories.push(text.to_string()),
+                _ => (),
+            }
+        } else if name == Some(b"title") && self.title.is_none() {
+            self.title = Some(text.to_string());
+        }
+    }
+}
+
+fn rss_to_org(xml: &str) -> ImportResult<String> {
+    let mut reader = Reader::from_str(xml);
+    reader.trim_text(true);
+
+    let mut feed = Feed::default();
+    let mut buf = Vec::new();
+
+    loop {
+        match reader.read_event(&mut buf)? {
+            Event::Start(element) => {
+                let name = element.local_name().to_vec();
+                if name == b"item" {
+                    feed.item = Some(Item::default());
+                }
+                feed.path.push(name);
+            }
+            Event::End(_) => {
+                let name = feed.path.pop();
+                if name.as_deref() == Some(&b"item"[..]) {
+                    feed.items.extend(feed.item.take());
+                }
+            }
+            Event::Text(text) => {
+                let text = text.unescape_and_decode(&reader)?;
+                feed.text(&text);
+            }
+            Event::CData(text) => {
+                let text = reader.decode(&text)?.to_string();
+                feed.text(&text);
+            }
+            Event::Eof => break,
+            _ => (),
+        }
+        buf.clear();
+    }
+
+    let mut output = String::new();
+
+    if let Some(title) = &feed.title {
+        output.push_str("#+TITLE: ");
+        output.push_str(title.trim());
+        output.push('\n');
+    }
+
+    for item in &feed.items {
+        output.push_str("* ");
+        output.push_str(&item.title.split_whitespace().collect::<Vec<_>>().join(" "));
+        if !item.categories.is_empty() {
+            output.push_str(" :");
+            for category in &item.categories {
+                output.push_str(&tag_name(category));
+                output.push(':');
+            }
+        }
+        output.push('\n');
+        if let Some(scheduled) = parse_rfc2822(&item.pub_date) {
+            output.push_str("SCHEDULED: ");
+            push_timestamp(&mut output, &scheduled);
+            output.push('\n');
+        }
+        if !item.link.is_empty() {
+            output.push_str("[[");
+            output.push_str(item.link.trim());
+            output.push_str("]]\n\n");
+        }
+        push_section(&mut output, &item.description);
+    }
+
+    Ok(output)
+}
+
+// parses dates like `Mon, 08 Apr 2019 10:00:00 +0000`, time zone is ignored
+fn parse_rfc2822(date: &str) -> Option<Timestamp<'static>> {
+    let date = date.trim();
+    let date = date.find(',').map(|i| &date[i + 1..]).unwrap_or(date);
+    let mut parts = date.split_whitespace();
+
+    let day = parts.next()?.parse().ok()?;
+    let month = parts.next()?;
+    let month = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ]
+    .iter()
+    .position(|name| month.eq_ignore_ascii_case(name))? as u8
+        + 1;
+    let year = parts.next()?.parse().ok()?;
+    let time = parts.next().and_then(|time| {
+        let mut time = time.split(':');
+        Some((time.next()?.parse().ok()?, time.next()?.parse().ok()?))
+    });
+
+    Some(Timestamp::Active {
+        start: datetime(year, month, day, time),
+        repeater: None,
+        delay: None,
+    })
+}
+
+#[test]
+fn convert() {
+    assert_eq!(
+        rss_to_org(
+            r#"<?xml version="1.0"?>
+<rss version="2.0">
+<channel>
+    <title>Feed</title>
+    <link>https://example.com</link>
+    <item>
+        <title>First
+        post</title>
+        <description><![CDATA[Line 1
+* not a headline]]></description>
+        <pubDate>Tue, 09 Apr 2019</pubDate>
+        <category>rust lang</category>
+        <category>org</category>
+    </item>
+    <item>
+        <title>Second post</title>
+    </item>
+</channel>
+</rss>"#
+        )
+        .unwrap(),
+        "#+TITLE: Feed\n\
+         * First post :rust_lang:org:\n\
+         SCHEDULED: <2019-04-09 Tue>\n\
+         Line 1\n \
+         * not a headline\n\n\
+         * Second post\n"
+    );
+}
@@ -207,27 +207,67 @@
 //!
 //! # Features
 //!
-//! By now, orgize provides three features:
+//! By now, orgize provides the following features:
 //!
 //! + `ser`: adds the ability to serialize `Org` and other elements using `serde`, enabled by default.
 //!
-//! + `chrono`: adds the ability to convert `Datetime` into `chrono` structs, disabled by default.
+//! + `chrono`: adds the ability to convert `Datetime` into `chrono` structs, and provides
+//!   [`agenda`] module for querying dates, disabled by default.
 //!
 //! + `syntect`: provides [`SyntectHtmlHandler`] for highlighting code block, disabled by default.
 //!
+//! + `scraper`: provides [`Org::from_html`] for converting html into `Org` struct, disabled by default.
+//!
+//! + `serde_json`: provides [`Org::from_json_emacs`] for importing Emacs's org-element json,
+//!   [`Org::from_github_issues`] and [`Org::to_github_issues_json`] for importing and exporting
+//!   GitHub issues, and [`Org::from_jira_json`] for importing Jira issues, disabled by default.
+//!
+//! + `quick-xml`: provides [`Org::from_rss`] for importing rss feeds, disabled by default.
+//!
+//! + `image`: provides [`DefaultHtmlHandler::with_base_path`] for adding the actual dimensions
+//!   of images to html output, disabled by default.
+//!
+//! + `xlsx`: provides [`Org::to_excel`] for exporting headlines as an Excel workbook, disabled
+//!   by default.
+//!
+//! + `calamine`: provides [`Org::from_excel`] for importing Excel and OpenDocument
+//!   spreadsheets, disabled by default.
+//!
+//! + `arbitrary`: implements `Arbitrary` for [`Title`] and its fields, for fuzzing, disabled
+//!   by default.
+//!
+//! [`agenda`]: agenda/index.html
 //! [`SyntectHtmlHandler`]: export/struct.SyntectHtmlHandler.html
+//! [`Org::from_html`]: struct.Org.html#method.from_html
+//! [`Org::from_json_emacs`]: struct.Org.html#method.from_json_emacs
+//! [`Org::from_github_issues`]: struct.Org.html#method.from_github_issues
+//! [`Org::to_github_issues_json`]: struct.Org.html#method.to_github_issues_json
+//! [`Org::from_jira_json`]: struct.Org.html#method.from_jira_json
+//! [`Org::from_rss`]: struct.Org.html#method.from_rss
+//! [`Org::to_excel`]: struct.Org.html#method.to_excel
+//! [`Org::from_excel`]: struct.Org.html#method.from_excel
+//! [`DefaultHtmlHandler::with_base_path`]: export/struct.DefaultHtmlHandler.html#method.with_base_path
+//! [`Title`]: elements/struct.Title.html
 //!
 //! # License
 //!
 //! MIT
 
+#[cfg(feature = "chrono")]
+pub mod agenda;
+pub mod cite;
 mod config;
 pub mod elements;
 pub mod export;
+mod footnote;
 mod headline;
+pub mod import;
+mod logbook;
+mod normalize;
 mod org;
 mod parse;
 mod parsers;
+mod template;
 mod validate;
 
 // Re-export of the indextree crate.
@@ -237,9 +277,20 @@ pub use syntect;
 
 pub use config::ParseConfig;
 pub use elements::Element;
-pub use headline::{Document, Headline};
-pub use org::{Event, Org};
-pub use validate::ValidationError;
+pub use headline::{Document, Headline, MAX_HEADLINE_LEVEL};
+pub use normalize::LineEnding;
+pub use org::{Event, Org, ParseError};
+pub use template::{OrgTemplate, TemplateError};
+pub use validate::{DrawerError, LevelError, TitleError, ValidationError};
 
 #[cfg(feature = "wasm")]
 mod wasm;
+
+// creates an empty directory for a test, unique across tests and processes
+#[cfg(test)]
+pub(crate) fn test_dir(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("orgize-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
@@ -0,0 +1,288 @@
+use indextree::NodeId;
+use std::time::Duration;
+
+use crate::config::ParseConfig;
+use crate::elements::{Clock, Element};
+use crate::export::write_datetime;
+use crate::parsers::{parse_container, Container, OwnedArena};
+use crate::{Headline, Org};
+
+impl Org<'_> {
+    /// Keeps the `max_entries` most recent closed clocks in each `:LOGBOOK:`
+    /// drawer, and replaces older ones with a summary item, like
+    /// `- [N clock entries totalling H:MM] [timestamp]`, where `timestamp`
+    /// is the start of the oldest removed clock.
+    ///
+    /// Running clocks and other entries are kept. Returns the number of
+    /// removed clocks.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let mut org = Org::parse(
+    ///     "* Task\n:LOGBOOK:\n\
+    ///      CLOCK: [2019-04-10 Wed 10:00]--[2019-04-10 Wed 11:00] =>  1:00\n\
+    ///      CLOCK: [2019-04-08 Mon 10:00]--[2019-04-08 Mon 10:30] =>  0:30\n\
+    ///      CLOCK: [2019-04-09 Tue 10:00]--[2019-04-09 Tue 12:15] =>  2:15\n\
+    ///      :END:\n",
+    /// );
+    ///
+    /// assert_eq!(org.compress_logbook(1), 2);
+    ///
+    /// let mut writer = Vec::new();
+    /// org.write_org(&mut writer).unwrap();
+    /// assert_eq!(
+    ///     String::from_utf8(writer).unwrap(),
+    ///     "* Task\n:LOGBOOK:\n\
+    ///      CLOCK: [2019-04-10 Wed 10:00]--[2019-04-10 Wed 11:00] => 1:00\n\
+    ///      - [2 clock entries totalling 2:45] [2019-04-08 Mon 10:00]\n\
+    ///      :END:\n"
+    /// );
+    /// ```
+    pub fn compress_logbook(&mut self, max_entries: usize) -> usize {
+        let drawers: Vec<_> = self
+            .root
+            .descendants(&self.arena)
+            .filter(|&node| match &self[node] {
+                Element::Drawer(drawer) => drawer.name.eq_ignore_ascii_case("LOGBOOK"),
+                _ => false,
+            })
+            .collect();
+
+        let mut removed = 0;
+        for drawer in drawers {
+            let mut clocks: Vec<_> = drawer
+                .children(&self.arena)
+                .filter(|&node| matches!(self[node], Element::Clock(Clock::Closed { .. })))
+                .collect();
+            if clocks.len() <= max_entries {
+                continue;
+            }
+
+            // most recent first
+            clocks.sort_by_key(|&node| std::cmp::Reverse(start(&self[node])));
+            let old = clocks.split_off(max_entries);
+
+            let mut minutes: usize = 0;
+            for &node in &old {
+                if let Element::Clock(clock) = &self[node] {
+                    let duration = clock.duration().and_then(parse_minutes);
+                    minutes = minutes.saturating_add(duration.unwrap_or(0));
+                }
+            }
+            let mut summary = format!(
+                "- [{} clock entries totalling {}:{:02}] ",
+                old.len(),
+                minutes / 60,
+                minutes % 60
+            );
+            if let Some(&oldest) = old.last() {
+                if let Element::Clock(Clock::Closed { start, .. }) = &self[oldest] {
+                    let mut writer = Vec::new();
+                    let _ = write_datetime(&mut writer, "[", start, "]");
+                    summary.push_str(&String::from_utf8_lossy(&writer));
+                }
+            }
+            summary.push('\n');
+
+            for &node in &old {
+                node.detach(&mut self.arena);
+            }
+            if let Some(list) = self.parse_detached(&summary) {
+                drawer.append(list, &mut self.arena);
+            }
+            removed += old.len();
+        }
+
+        self.debug_validate();
+
+        removed
+    }
+
+    /// Returns all clocks, closed and running, from the `:LOGBOOK:` drawers
+    /// in the document, paired with their headlines and sorted by start
+    /// time.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse(
+    ///     "* A\n:LOGBOOK:\n\
+    ///      CLOCK: [2019-04-10 Wed 10:00]--[2019-04-10 Wed 11:00] =>  1:00\n\
+    ///      :END:\n\
+    ///      ** B\n:LOGBOOK:\n\
+    ///      CLOCK: [2019-04-11 Thu 09:00]\n\
+    ///      CLOCK: [2019-04-08 Mon 10:00]--[2019-04-08 Mon 10:30] =>  0:30\n\
+    ///      :END:\n",
+    /// );
+    ///
+    /// let clocks: Vec<_> = org
+    ///     .all_clocks()
+    ///     .into_iter()
+    ///     .map(|(headline, clock)| (headline.title(&org).raw.as_ref(), clock.is_running()))
+    ///     .collect();
+    /// assert_eq!(clocks, vec![("B", false), ("A", false), ("B", true)]);
+    /// ```
+    pub fn all_clocks(&self) -> Vec<(Headline, &Clock<'_>)> {
+        let mut clocks = Vec::new();
+        for headline in self.headlines() {
+            let section = match headline.section_node() {
+                Some(section) => section,
+                None => continue,
+            };
+            for drawer in section.children(&self.arena) {
+                match &self[drawer] {
+                    Element::Drawer(d) if d.name.eq_ignore_ascii_case("LOGBOOK") => (),
+                    _ => continue,
+                }
+                for node in drawer.children(&self.arena) {
+                    if let Element::Clock(clock) = &self[node] {
+                        clocks.push((headline, clock));
+                    }
+                }
+            }
+        }
+        clocks.sort_by_key(|(_, clock)| clock_start(clock));
+        clocks
+    }
+
+    /// Returns the total time of closed clocks in `headline`'s `:LOGBOOK:`
+    /// drawer, and in those of its descendants if `recursive` is `true`.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    /// use std::time::Duration;
+    ///
+    /// let org = Org::parse(
+    ///     "* A\n:LOGBOOK:\n\
+    ///      CLOCK: [2019-04-10 Wed 10:00]--[2019-04-10 Wed 11:00] =>  1:00\n\
+    ///      :END:\n\
+    ///      ** B\n:LOGBOOK:\n\
+    ///      CLOCK: [2019-04-11 Thu 09:00]\n\
+    ///      CLOCK: [2019-04-08 Mon 10:00]--[2019-04-08 Mon 10:30] =>  0:30\n\
+    ///      :END:\n",
+    /// );
+    /// let a = org.headlines().next().unwrap();
+    ///
+    /// assert_eq!(
+    ///     org.total_clocked_time_for_headline(a, false),
+    ///     Duration::from_secs(60 * 60)
+    /// );
+    /// assert_eq!(
+    ///     org.total_clocked_time_for_headline(a, true),
+    ///     Duration::from_secs(90 * 60)
+    /// );
+    /// ```
+    pub fn total_clocked_time_for_headline(&self, headline: Headline, recursive: bool) -> Duration {
+        let target = headline.headline_node();
+        self.all_clocks()
+            .into_iter()
+            .filter(|(clocked, _)| {
+                if recursive {
+                    clocked
+                        .headline_node()
+                        .ancestors(&self.arena)
+                        .any(|node| node == target)
+                } else {
+                    clocked.headline_node() == target
+                }
+            })
+            .filter_map(|(_, clock)| clock.elapsed())
+            .sum()
+    }
+
+    // parses `content` as section contents, and returns the first detached element
+    fn parse_detached(&mut self, content: &str) -> Option<NodeId> {
+        let sec_n = self.arena.new_node(Element::Section);
+        parse_container(
+            &mut OwnedArena::new(&mut self.arena),
+            Container::Block {
+                node: sec_n,
+                content,
+            },
+            &ParseConfig::default(),
+        );
+        let node = self.arena[sec_n].first_child()?;
+        node.detach(&mut self.arena);
+        Some(node)
+    }
+}
+
+// returns the start of a closed clock as a comparable tuple
+fn start(element: &Element) -> Option<(u16, u8, u8, u8, u8)> {
+    match element {
+        Element::Clock(clock @ Clock::Closed { .. }) => Some(clock_start(clock)),
+        _ => None,
+    }
+}
+
+// returns the start of a clock as a comparable tuple
+fn clock_start(clock: &Clock) -> (u16, u8, u8, u8, u8) {
+    let start = match clock {
+        Clock::Closed { start, .. } | Clock::Running { start, .. } => start,
+    };
+    (
+        start.year,
+        start.month,
+        start.day,
+        start.hour.unwrap_or_default(),
+        start.minute.unwrap_or_default(),
+    )
+}
+
+// parses clock duration `H:MM` in minutes
+fn parse_minutes(duration: &str) -> Option<usize> {
+    let mut parts = duration.trim().splitn(2, ':');
+    let hours: usize = parts.next()?.parse().ok()?;
+    let minutes: usize = parts.next()?.parse().ok()?;
+    hours.checked_mul(60)?.checked_add(minutes)
+}
+
+#[test]
+fn parse_minutes_overflow() {
+    assert_eq!(parse_minutes("1:30"), Some(90));
+    assert_eq!(parse_minutes("307445734561825861:00"), None);
+    assert_eq!(parse_minutes("x:00"), None);
+}
+
+#[test]
+fn all_clocks() {
+    assert!(Org::parse("").all_clocks().is_empty());
+
+    // clocks outside logbook drawers and before the first headline are skipped
+    let org = Org::parse(
+        "CLOCK: [2019-04-01 Mon 10:00]--[2019-04-01 Mon 11:00] =>  1:00\n\
+         * Été\nCLOCK: [2019-04-02 Tue 10:00]--[2019-04-02 Tue 11:00] =>  1:00\n\
+         :logbook:\nCLOCK: [2019-04-10 Wed 10:00]\n:END:\n\
+         :NOTES:\nCLOCK: [2019-04-03 Wed 10:00]\n:END:\n",
+    );
+    let clocks = org.all_clocks();
+    assert_eq!(clocks.len(), 1);
+    assert_eq!(clocks[0].0.title(&org).raw, "Été");
+    assert!(clocks[0].1.is_running());
+}
+
+#[test]
+fn total_clocked_time_for_headline() {
+    let org = Org::parse(
+        "* A\n:LOGBOOK:\nCLOCK: [2019-04-10 Wed 10:00]\n:END:\n\
+         ** 日本\n:LOGBOOK:\n\
+         CLOCK: [2019-04-10 Wed 10:00]--[2019-04-10 Wed 10:45] =>  0:45\n:END:\n\
+         * B\n",
+    );
+    let headlines: Vec<_> = org.headlines().collect();
+
+    // running clocks are not counted
+    assert_eq!(
+        org.total_clocked_time_for_headline(headlines[0], false),
+        Duration::from_secs(0)
+    );
+    assert_eq!(
+        org.total_clocked_time_for_headline(headlines[0], true),
+        Duration::from_secs(45 * 60)
+    );
+    assert_eq!(
+        org.total_clocked_time_for_headline(headlines[2], true),
+        Duration::from_secs(0)
+    );
+}
@@ -0,0 +1,286 @@
+use crate::config::ParseConfig;
+use crate::elements::{title::parse_properties_drawer, Element, Keyword, Planning, Table};
+use crate::parsers::{parse_container, Container, OwnedArena};
+use crate::Org;
+
+/// Line ending style
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineEnding {
+    /// `\n`, used on Unix-like systems
+    Lf,
+    /// `\r\n`, used on Windows
+    CrLf,
+}
+
+impl Org<'_> {
+    /// Returns a copy of this document with all line endings converted to
+    /// `target`.
+    ///
+    /// Line endings are kept by the parser inside multi-line contents, like
+    /// paragraphs or blocks, so a document parsed from a Windows-created file
+    /// may contain `\r\n` in its elements. Line endings between elements
+    /// aren't stored, they are written by the exporter.
+    ///
+    /// The document isn't re-parsed, so headlines keep the todo keywords they
+    /// were parsed with.
+    ///
+    /// ```rust
+    /// use orgize::{LineEnding, Org};
+    ///
+    /// let org = Org::parse("* TODO Title\r\nsome\r\ntext\r\n#+BEGIN_SRC rust\r\ncode\r\n#+END_SRC\r\n");
+    ///
+    /// let mut writer = Vec::new();
+    /// org.convert_line_endings(LineEnding::Lf)
+    ///     .write_org(&mut writer)
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     String::from_utf8(writer).unwrap(),
+    ///     "* TODO Title\nsome\ntext\n#+BEGIN_SRC rust\ncode\n#+END_SRC\n"
+    /// );
+    /// ```
+    pub fn convert_line_endings(&self, target: LineEnding) -> Org<'static> {
+        let mut org = self.copy_without(|_| false);
+
+        let nodes: Vec<_> = org.root.descendants(&org.arena).collect();
+        for node in nodes {
+            let value = match &mut org[node] {
+                Element::Text { value }
+                | Element::Code { value }
+                | Element::Verbatim { value }
+                | Element::Table(Table::TableEl { value, .. }) => value,
+                Element::Comment(comment) => &mut comment.value,
+                Element::FixedWidth(fixed_width) => &mut fixed_width.value,
+                Element::CommentBlock(block) => &mut block.contents,
+                Element::ExampleBlock(block) => &mut block.contents,
+                Element::ExportBlock(block) => &mut block.contents,
+                Element::SourceBlock(block) => &mut block.contents,
+                _ => continue,
+            };
+            if let Some(converted) = convert_line_ending(value, target) {
+                *value = converted.into();
+            }
+        }
+
+        org
+    }
+
+    /// Returns a copy of this document with consistent whitespace.
+    ///
+    /// Elements in sections are separated by exactly one blank line, except
+    /// consecutive keywords and affiliated keywords, like `#+CAPTION`, which
+    /// stay attached to the following element. Headline stars are followed by
+    /// exactly one space, and planning lines and property drawers directly
+    /// follow their headline. Blank lines at the beginning and the end of the
+    /// document are removed.
+    ///
+    /// The document isn't re-parsed, so headlines keep the todo keywords they
+    /// were parsed with.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ParseConfig};
+    ///
+    /// let org = Org::parse(
+    ///     "\n#+TITLE: T\n#+AUTHOR: A\n\n\n*   H1\n\nSCHEDULED: <2019-04-08 Mon>\n\n\
+    ///      :PROPERTIES:\n:ID: 1\n:END:\n\ntext\n\n\n\nmore\n- a\n- b\n\n\n** H2\n\n\n",
+    /// );
+    ///
+    /// let mut writer = Vec::new();
+    /// org.normalize_whitespace().write_org(&mut writer).unwrap();
+    /// assert_eq!(
+    ///     String::from_utf8(writer).unwrap(),
+    ///     "#+TITLE: T\n#+AUTHOR: A\n\n* H1\nSCHEDULED: <2019-04-08 Mon>\n\
+    ///      :PROPERTIES:\n:ID: 1\n:END:\ntext\n\nmore\n\n- a\n- b\n\n** H2\n"
+    /// );
+    ///
+    /// let config = ParseConfig {
+    ///     todo_keywords: (vec!["OPEN".into()], vec![]),
+    ///     ..Default::default()
+    /// };
+    /// let org = Org::parse_custom("* OPEN H1\n\nDEADLINE: <2019-04-08 Mon>\ntext\n", &config);
+    ///
+    /// let org = org.normalize_whitespace();
+    /// let title = org.headlines().next().unwrap().title(&org);
+    /// assert_eq!(title.keyword.as_deref(), Some("OPEN"));
+    /// assert!(title.deadline().is_some());
+    ///
+    /// let mut writer = Vec::new();
+    /// org.write_org(&mut writer).unwrap();
+    /// assert_eq!(
+    ///     String::from_utf8(writer).unwrap(),
+    ///     "* OPEN H1\nDEADLINE: <2019-04-08 Mon>\ntext\n"
+    /// );
+    /// ```
+    pub fn normalize_whitespace(&self) -> Org<'static> {
+        let mut org = self.copy_without(|_| false);
+        attach_planning(&mut org);
+
+        let sections: Vec<_> = org
+            .root
+            .descendants(&org.arena)
+            .filter(|&node| matches!(org[node], Element::Section))
+            .collect();
+        for sec_n in sections {
+            let children: Vec<_> = sec_n.children(&org.arena).collect();
+            for (index, &node) in children.iter().enumerate() {
+                let attached = match &org[node] {
+                    Element::Keyword(keyword) => {
+                        is_affiliated(keyword)
+                            || children
+                                .get(index + 1)
+                                .map(|&next| matches!(org[next], Element::Keyword(_)))
+                                .unwrap_or_default()
+                    }
+                    _ => false,
+                };
+                if let Some(post_blank) = org[node].post_blank_mut() {
+                    *post_blank = if attached { 0 } else { 1 };
+                }
+                // blank lines at the end of lists are counted by their last item
+                let mut parent = node;
+                while let Some(last) = parent.last_child(&org.arena) {
+                    if let Some(post_blank) = org[last].post_blank_mut() {
+                        *post_blank = 0;
+                    }
+                    parent = last;
+                }
+            }
+        }
+
+        let nodes: Vec<_> = org.root.descendants(&org.arena).collect();
+        for node in nodes {
+            match &mut org[node] {
+                Element::Document { pre_blank } => *pre_blank = 0,
+                Element::Title(title) => title.post_blank = 0,
+                _ => (),
+            }
+        }
+
+        // removes blank lines at the end of document
+        let mut parent = org.root;
+        while let Some(last) = parent.last_child(&org.arena) {
+            if let Some(post_blank) = org[last].post_blank_mut() {
+                *post_blank = 0;
+            }
+            if !matches!(org[last], Element::Headline { .. } | Element::Section) {
+                break;
+            }
+            parent = last;
+        }
+
+        org
+    }
+}
+
+// moves planning lines and property drawers, which are separated from their
+// headline by blank lines and so parsed into its section, into the title
+fn attach_planning(org: &mut Org) {
+    let headlines: Vec<_> = org.headlines().collect();
+    for headline in headlines {
+        let sec_n = match headline.section_node() {
+            Some(sec_n) => sec_n,
+            None => continue,
+        };
+
+        while let Some(first) = org.arena[sec_n].first_child() {
+            let title = headline.title(org);
+            let (planned, has_properties) =
+                (title.planning.is_some(), !title.properties.is_empty());
+            match &org[first] {
+                Element::Paragraph { post_blank } if !planned && !has_properties => {
+                    let post_blank = *post_blank;
+                    let text = org.node_to_org(first);
+                    let (rest, planning) = match Planning::parse(&text) {
+                        Some((rest, planning)) => (rest.trim_end(), planning.into_owned()),
+                        None => break,
+                    };
+                    // lines following the planning line stay in the paragraph
+                    if !rest.is_empty() {
+                        let paragraph = org.arena.new_node(Element::Paragraph { post_blank });
+                        parse_container(
+                            &mut OwnedArena::new(&mut org.arena),
+                            Container::Inline {
+                                content: rest,
+                                node: paragraph,
+                            },
+                            &ParseConfig::default(),
+                        );
+                        first.insert_after(paragraph, &mut org.arena);
+                    }
+                    first.detach(&mut org.arena);
+                    headline.title_mut(org).planning = Some(Box::new(planning));
+                }
+                Element::Drawer(drawer) if drawer.name == "PROPERTIES" && !has_properties => {
+                    let text = org.node_to_org(first);
+                    let properties = match parse_properties_drawer(&text) {
+                        Ok((_, properties)) => properties.into_owned(),
+                        Err(_) => break,
+                    };
+                    first.detach(&mut org.arena);
+                    headline.title_mut(org).properties = properties;
+                }
+                _ => break,
+            }
+        }
+
+        if org.arena[sec_n].first_child().is_none() {
+            sec_n.detach(&mut org.arena);
+        }
+    }
+}
+
+// returns `None` if `text` already uses `target` line endings
+fn convert_line_ending(text: &str, target: LineEnding) -> Option<String> {
+    let has_crlf = text.contains("\r\n");
+    match target {
+        LineEnding::Lf if has_crlf => Some(text.replace("\r\n", "\n")),
+        LineEnding::CrLf if text.contains('\n') => {
+            let text = if has_crlf {
+                text.replace("\r\n", "\n")
+            } else {
+                text.to_string()
+            };
+            Some(text.replace('\n', "\r\n"))
+        }
+        _ => None,
+    }
+}
+
+// affiliated keywords are attached to the following element
+fn is_affiliated(keyword: &Keyword) -> bool {
+    let key = keyword.key.to_ascii_uppercase();
+    key.starts_with("ATTR_")
+        || matches!(
+            key.as_str(),
+            "CAPTION" | "HEADER" | "NAME" | "PLOT" | "RESULTS"
+        )
+}
+
+#[test]
+fn convert_line_endings() {
+    let org = Org::parse(
+        "* TODO A\r\ntext\r\nmore\r\n| a | b |\r\n|---+---|\r\n| c | d |\r\n\
+         : fixed\r\n: width\r\n+---+\r\n| e |\r\n+---+\r\n",
+    );
+
+    let org = org.convert_line_endings(LineEnding::Lf);
+    assert_eq!(
+        org.to_pretty_org(2),
+        "* TODO A\ntext\nmore\n| a | b |\n|---+---|\n| c | d |\n\
+         : fixed\n: width\n+---+\n| e |\n+---+\n"
+    );
+    assert!(org
+        .iter()
+        .all(|event| !format!("{:?}", event).contains("\\r")));
+
+    let org = org.convert_line_endings(LineEnding::CrLf);
+    let texts: Vec<_> = org
+        .iter()
+        .filter_map(|event| match event {
+            crate::Event::Start(Element::Text { value }) if value.contains('\n') => {
+                Some(value.to_string())
+            }
+            _ => None,
+        })
+        .collect();
+    assert_eq!(texts, ["text\r\nmore"]);
+}
@@ -1,19 +1,48 @@
 use indextree::{Arena, NodeEdge, NodeId};
+use memchr::memchr_iter;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::error;
+use std::fmt;
 use std::io::{Error, Write};
 use std::ops::{Index, IndexMut};
+use std::path::{Path, PathBuf};
 
 use crate::{
     config::{ParseConfig, DEFAULT_CONFIG},
-    elements::{Element, Keyword},
-    export::{DefaultHtmlHandler, DefaultOrgHandler, HtmlHandler, OrgHandler},
+    elements::{
+        Comment, CommentBlock, Element, ExampleBlock, ExportBlock, FixedWidth, Keyword, Link, List,
+        ListItem, Planning, SourceBlock, Table, TableCell, TableRow,
+    },
+    export::{write_plain_text, DefaultHtmlHandler, DefaultOrgHandler, HtmlHandler, OrgHandler},
     parsers::{blank_lines_count, parse_container, Container, OwnedArena},
 };
 
 pub struct Org<'a> {
     pub(crate) arena: Arena<Element<'a>>,
     pub(crate) root: NodeId,
+    pub(crate) source_path: Option<PathBuf>,
 }
 
+/// Parse Error
+#[derive(Debug)]
+pub enum ParseError {
+    /// Planning line has a malformed timestamp, `line` starts from 1
+    MalformedPlanning { line: usize },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::MalformedPlanning { line } => {
+                write!(f, "malformed planning at line {}", line)
+            }
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
 #[derive(Debug)]
 pub enum Event<'a, 'b> {
     Start(&'b Element<'a>),
@@ -25,7 +54,11 @@ impl<'a> Org<'a> {
     pub fn new() -> Org<'static> {
         let mut arena = Arena::new();
         let root = arena.new_node(Element::Document { pre_blank: 0 });
-        Org { arena, root }
+        Org {
+            arena,
+            root,
+            source_path: None,
+        }
     }
 
     /// Parses string `text` into `Org` struct.
@@ -39,11 +72,18 @@ impl<'a> Org<'a> {
     }
 
     /// Parses string `text` into `Org` struct with custom `ParseConfig`.
+    ///
+    /// Parsing never fails, so `ParseConfig::strict_planning` is ignored, use
+    /// `try_parse_custom` to validate planning lines.
     pub fn parse_custom(text: &'a str, config: &ParseConfig) -> Org<'a> {
         let mut arena = Arena::new();
         let (text, pre_blank) = blank_lines_count(text);
         let root = arena.new_node(Element::Document { pre_blank });
-        let mut org = Org { arena, root };
+        let mut org = Org {
+            arena,
+            root,
+            source_path: None,
+        };
 
         parse_container(
             &mut org.arena,
@@ -54,17 +94,63 @@ impl<'a> Org<'a> {
             config,
         );
 
+        if config.org_indent_mode || org.startup_indent() {
+            org.remove_virtual_indentation();
+        }
+
         org.debug_validate();
 
         org
     }
 
+    /// Likes `parse_custom`, but returns an error if `text` isn't valid
+    /// under the given `ParseConfig`.
+    ///
+    /// With `strict_planning` enabled, every line starting with `SCHEDULED:`,
+    /// `DEADLINE:` or `CLOSED:` outside of blocks must be followed by valid
+    /// timestamps, whether it's right after a headline or further down its
+    /// section.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ParseConfig};
+    ///
+    /// let config = ParseConfig {
+    ///     strict_planning: true,
+    ///     ..Default::default()
+    /// };
+    ///
+    /// assert!(Org::try_parse_custom("* TODO\nSCHEDULED: <2019-04-08 Mon>\n", &config).is_ok());
+    /// assert!(Org::try_parse_custom("* TODO\nSCHEDULED: tomorrow\n", &config).is_err());
+    /// assert!(Org::try_parse_custom("* TODO\nSCHEDULED: tomorrow\n", &Default::default()).is_ok());
+    /// ```
+    pub fn try_parse_custom(text: &'a str, config: &ParseConfig) -> Result<Org<'a>, ParseError> {
+        if config.strict_planning {
+            let mut in_block = false;
+            for (index, line) in text.lines().enumerate() {
+                let trimmed = line.trim_start().to_ascii_uppercase();
+                if trimmed.starts_with("#+BEGIN_") {
+                    in_block = true;
+                } else if trimmed.starts_with("#+END_") {
+                    in_block = false;
+                } else if !in_block && Planning::is_malformed(line) {
+                    return Err(ParseError::MalformedPlanning { line: index + 1 });
+                }
+            }
+        }
+
+        Ok(Org::parse_custom(text, config))
+    }
+
     /// Likes `parse_custom`, but accepts `String`.
     pub fn parse_string_custom(text: String, config: &ParseConfig) -> Org<'static> {
         let mut arena = Arena::new();
         let (text, pre_blank) = blank_lines_count(&text);
         let root = arena.new_node(Element::Document { pre_blank });
-        let mut org = Org { arena, root };
+        let mut org = Org {
+            arena,
+            root,
+            source_path: None,
+        };
 
         parse_container(
             &mut OwnedArena::new(&mut org.arena),
@@ -75,11 +161,79 @@ impl<'a> Org<'a> {
             config,
         );
 
+        if config.org_indent_mode || org.startup_indent() {
+            org.remove_virtual_indentation();
+        }
+
         org.debug_validate();
 
         org
     }
 
+    // returns `true` if `indent` is set by the last `#+STARTUP` keyword
+    // which sets `indent` or `noindent`
+    fn startup_indent(&self) -> bool {
+        self.keywords()
+            .filter(|keyword| keyword.key.eq_ignore_ascii_case("STARTUP"))
+            .flat_map(|keyword| keyword.value.split_whitespace())
+            .filter(|option| *option == "indent" || *option == "noindent")
+            .last()
+            == Some("indent")
+    }
+
+    // removes leading spaces added by `org-indent-mode` from sections
+    fn remove_virtual_indentation(&mut self) {
+        let sections: Vec<_> = self
+            .root
+            .descendants(&self.arena)
+            .filter(|&node| matches!(self[node], Element::Section))
+            .collect();
+
+        for sec_n in sections {
+            let width = match sec_n.ancestors(&self.arena).nth(1).map(|n| &self[n]) {
+                Some(Element::Headline { level }) => level + 1,
+                _ => continue,
+            };
+            let nodes: Vec<_> = sec_n.descendants(&self.arena).collect();
+            for node in nodes {
+                let at_line_start = node
+                    .parent(&self.arena)
+                    .map(|parent| {
+                        matches!(self[parent], Element::Paragraph { .. })
+                            && parent.first_child(&self.arena) == Some(node)
+                    })
+                    .unwrap_or_default();
+                let value = match &mut self[node] {
+                    Element::List(List { indent, .. })
+                    | Element::ListItem(ListItem { indent, .. }) => {
+                        *indent = indent.saturating_sub(width);
+                        continue;
+                    }
+                    Element::Text { value } if !at_line_start => {
+                        let stripped = strip_indentation(value, width, true);
+                        if stripped != *value {
+                            *value = stripped.into();
+                        }
+                        continue;
+                    }
+                    Element::Text { value } => value,
+                    Element::CommentBlock(CommentBlock { contents, .. })
+                    | Element::ExampleBlock(ExampleBlock { contents, .. })
+                    | Element::ExportBlock(ExportBlock { contents, .. })
+                    | Element::SourceBlock(SourceBlock { contents, .. }) => contents,
+                    Element::FixedWidth(FixedWidth { value, .. })
+                    | Element::Comment(Comment { value, .. })
+                    | Element::Table(Table::TableEl { value, .. }) => value,
+                    _ => continue,
+                };
+                let stripped = strip_indentation(value, width, false);
+                if stripped != *value {
+                    *value = stripped.into();
+                }
+            }
+        }
+    }
+
     /// Returns a reference to the underlay arena.
     pub fn arena(&self) -> &Arena<Element<'a>> {
         &self.arena
@@ -90,6 +244,27 @@ impl<'a> Org<'a> {
         &mut self.arena
     }
 
+    /// Returns the path of the file this document was read from, if set.
+    pub fn source_path(&self) -> Option<&Path> {
+        self.source_path.as_deref()
+    }
+
+    /// Sets the path of the file this document was read from.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    /// use std::path::Path;
+    ///
+    /// let mut org = Org::parse("* a");
+    /// assert_eq!(org.source_path(), None);
+    ///
+    /// org.set_source_path("/home/user/notes.org");
+    /// assert_eq!(org.source_path(), Some(Path::new("/home/user/notes.org")));
+    /// ```
+    pub fn set_source_path<P: Into<PathBuf>>(&mut self, path: P) {
+        self.source_path = Some(path.into());
+    }
+
     /// Returns an iterator of `Event`s.
     pub fn iter<'b>(&'b self) -> impl Iterator<Item = Event<'a, 'b>> + 'b {
         self.root.traverse(&self.arena).map(move |edge| match edge {
@@ -109,12 +284,600 @@ impl<'a> Org<'a> {
             })
     }
 
+    /// Returns the value of the first keyword named `key`, compared
+    /// case-insensitively.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse("#+title: Notes\n#+Author: me\n");
+    ///
+    /// assert_eq!(org.keyword_value("TITLE"), Some("Notes"));
+    /// assert_eq!(org.keyword_value("author"), Some("me"));
+    /// assert_eq!(org.keyword_value("DATE"), None);
+    /// ```
+    pub fn keyword_value(&self, key: &str) -> Option<&str> {
+        self.keywords()
+            .find(|keyword| keyword.key.eq_ignore_ascii_case(key))
+            .map(|keyword| &*keyword.value)
+    }
+
+    /// Returns the line numbers, starting from 1, of all keywords grouped by
+    /// their uppercased keys.
+    ///
+    /// Lines are counted in the document as written by `write_org`, with one
+    /// line per table row, so they match the parsed text only when it was
+    /// already in that form, e.g. indentation and `CRLF` line endings are
+    /// not taken into account.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse("#+TITLE: Notes\n\n* Intro\n#+title: Other\n#+CAPTION: Table\n");
+    /// let positions = org.keyword_positions();
+    ///
+    /// assert_eq!(positions["TITLE"], vec![1, 4]);
+    /// assert_eq!(positions["CAPTION"], vec![5]);
+    /// ```
+    pub fn keyword_positions(&self) -> HashMap<Cow<'_, str>, Vec<usize>> {
+        let mut positions: HashMap<Cow<'_, str>, Vec<usize>> = HashMap::new();
+        let (mut line, mut buffer) = (1, Vec::new());
+        for event in self.iter() {
+            buffer.clear();
+            match event {
+                Event::Start(element) => DefaultOrgHandler.start(&mut buffer, element).unwrap(),
+                Event::End(element) => DefaultOrgHandler.end(&mut buffer, element).unwrap(),
+            }
+            if let Event::Start(Element::Keyword(keyword)) = event {
+                let key = if keyword.key.chars().any(char::is_lowercase) {
+                    Cow::Owned(keyword.key.to_uppercase())
+                } else {
+                    Cow::Borrowed(&*keyword.key)
+                };
+                positions.entry(key).or_default().push(line);
+            }
+            line += memchr_iter(b'\n', &buffer).count();
+            if let Event::End(Element::TableRow(_)) = event {
+                line += 1;
+            }
+        }
+        positions
+    }
+
+    /// Returns the values of all `#+HTML_HEAD` and `#+HTML_HEAD_EXTRA`
+    /// keywords, in document order, to be included verbatim in the `<head>`
+    /// of the html page.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse(
+    ///     "#+HTML_HEAD: <link rel=\"stylesheet\" href=\"a.css\">\n\
+    ///      #+TITLE: Notes\n\
+    ///      #+HTML_HEAD_EXTRA: <style>p { color: red; }</style>\n",
+    /// );
+    ///
+    /// assert_eq!(
+    ///     org.html_head_entries().collect::<Vec<_>>(),
+    ///     vec![
+    ///         "<link rel=\"stylesheet\" href=\"a.css\">",
+    ///         "<style>p { color: red; }</style>",
+    ///     ]
+    /// );
+    /// ```
+    pub fn html_head_entries(&self) -> impl Iterator<Item = &str> {
+        self.keywords()
+            .filter(|keyword| {
+                keyword.key.eq_ignore_ascii_case("HTML_HEAD")
+                    || keyword.key.eq_ignore_ascii_case("HTML_HEAD_EXTRA")
+            })
+            .map(|keyword| &*keyword.value)
+    }
+
+    /// Returns the contents of the first `<style>` element in
+    /// [`html_head_entries`].
+    ///
+    /// [`html_head_entries`]: Org::html_head_entries
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse("#+HTML_HEAD: <style type=\"text/css\">p { color: red; }</style>\n");
+    /// assert_eq!(org.inline_css(), Some("p { color: red; }"));
+    ///
+    /// let org = Org::parse("#+HTML_HEAD: <link rel=\"stylesheet\" href=\"a.css\">\n");
+    /// assert_eq!(org.inline_css(), None);
+    /// ```
+    pub fn inline_css(&self) -> Option<&str> {
+        self.html_head_entries().find_map(|entry| {
+            let start = entry.find("<style")?;
+            let entry = &entry[start..];
+            let contents = &entry[entry.find('>')? + 1..];
+            Some(&contents[..contents.find("</style>")?])
+        })
+    }
+
+    /// Returns the number of elements of each type in this document, most
+    /// frequent first, excluding the document itself.
+    ///
+    /// Types are named by [`Element::type_name`].
+    ///
+    /// [`Element::type_name`]: elements/enum.Element.html#method.type_name
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse("* a\n* b\n");
+    /// assert_eq!(
+    ///     org.element_counts(),
+    ///     vec![("headline", 2), ("text", 2), ("title", 2)]
+    /// );
+    /// ```
+    pub fn element_counts(&self) -> Vec<(&'static str, usize)> {
+        let mut counts: Vec<(&'static str, usize)> = Vec::new();
+        for node in self.root.descendants(&self.arena).skip(1) {
+            let name = self[node].type_name();
+            match counts.iter_mut().find(|(n, _)| *n == name) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((name, 1)),
+            }
+        }
+        counts.sort_by(|(a, x), (b, y)| y.cmp(x).then(a.cmp(b)));
+        counts
+    }
+
+    /// Returns the number of words in the prose of this document.
+    ///
+    /// A word is a run of non-whitespace characters containing at least one
+    /// alphanumeric character, so `it's` and `e-mail` are single words and
+    /// a lone `-` isn't one. Paragraphs, headline titles, list items,
+    /// footnote definitions and link descriptions are counted, while
+    /// blocks, tables, comments, drawers, keywords and timestamps are
+    /// skipped. Links without description count as one word.
+    ///
+    /// The document is traversed only once.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse(
+    ///     "#+TITLE: Ignored\n* Hello, /wor/ld!\nIt's a [[https://example.com][good day]] - \
+    ///      see [[https://example.com]].\n\
+    ///      #+BEGIN_SRC rust\nfn main() {}\n#+END_SRC\n| a | b |\n",
+    /// );
+    /// assert_eq!(org.total_word_count(), 8);
+    /// ```
+    pub fn total_word_count(&self) -> usize {
+        let mut count = 0;
+        // a word is being read, and it contains an alphanumeric character
+        let mut word = None;
+        // number of skipped elements being traversed
+        let mut skipped = 0;
+
+        for edge in self.root.traverse(&self.arena) {
+            match edge {
+                NodeEdge::Start(node) => match &self[node] {
+                    Element::Table(_) | Element::Drawer(_) | Element::DynBlock(_) => skipped += 1,
+                    _ if skipped > 0 => (),
+                    Element::Text { value }
+                    | Element::Code { value }
+                    | Element::Verbatim { value } => {
+                        for c in value.chars() {
+                            if c.is_whitespace() {
+                                end_word(&mut word, &mut count);
+                            } else {
+                                let alphanumeric = word.unwrap_or_default();
+                                word = Some(alphanumeric || c.is_alphanumeric());
+                            }
+                        }
+                    }
+                    Element::Link(link) => {
+                        end_word(&mut word, &mut count);
+                        match &link.desc {
+                            Some(desc) => {
+                                count += desc
+                                    .split_whitespace()
+                                    .filter(|w| w.chars().any(char::is_alphanumeric))
+                                    .count()
+                            }
+                            None => count += 1,
+                        }
+                    }
+                    element if element.is_container() => end_word(&mut word, &mut count),
+                    _ => (),
+                },
+                NodeEdge::End(node) => match &self[node] {
+                    Element::Table(_) | Element::Drawer(_) | Element::DynBlock(_) => skipped -= 1,
+                    element if element.is_container() => end_word(&mut word, &mut count),
+                    _ => (),
+                },
+            }
+        }
+        end_word(&mut word, &mut count);
+
+        count
+    }
+
+    /// Returns a document containing a table of [`Org::element_counts`],
+    /// with `Element Type` and `Count` columns.
+    ///
+    /// [`Org::element_counts`]: #method.element_counts
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse("* a\n*b*\n");
+    ///
+    /// let mut writer = Vec::new();
+    /// org.statistics_table().write_html(&mut writer).unwrap();
+    /// assert_eq!(
+    ///     String::from_utf8(writer).unwrap(),
+    ///     "<main><section><table>\
+    ///      <thead><tr><th>Element Type</th><th>Count</th></tr></thead>\
+    ///      <tbody><tr><td>text</td><td>2</td></tr><tr><td>bold</td><td>1</td></tr>\
+    ///      <tr><td>headline</td><td>1</td></tr><tr><td>paragraph</td><td>1</td></tr>\
+    ///      <tr><td>section</td><td>1</td></tr><tr><td>title</td><td>1</td></tr>\
+    ///      </tbody></table></section></main>"
+    /// );
+    /// ```
+    pub fn statistics_table(&self) -> Org<'static> {
+        let mut arena = Arena::new();
+        let root = arena.new_node(Element::Document { pre_blank: 0 });
+        let section = arena.new_node(Element::Section);
+        root.append(section, &mut arena);
+        let table = arena.new_node(Element::Table(Table::Org {
+            tblfm: None,
+            post_blank: 0,
+            has_header: true,
+        }));
+        section.append(table, &mut arena);
+
+        fn append_row(
+            arena: &mut Arena<Element<'static>>,
+            table: NodeId,
+            row: TableRow,
+            cell: TableCell,
+            values: [String; 2],
+        ) {
+            let row = arena.new_node(Element::TableRow(row));
+            table.append(row, arena);
+            for value in values {
+                let cell = arena.new_node(Element::TableCell(cell.clone()));
+                row.append(cell, arena);
+                let text = arena.new_node(Element::Text {
+                    value: value.into(),
+                });
+                cell.append(text, arena);
+            }
+        }
+
+        append_row(
+            &mut arena,
+            table,
+            TableRow::Header,
+            TableCell::Header,
+            ["Element Type".into(), "Count".into()],
+        );
+        let rule = arena.new_node(Element::TableRow(TableRow::HeaderRule));
+        table.append(rule, &mut arena);
+        for (name, count) in self.element_counts() {
+            append_row(
+                &mut arena,
+                table,
+                TableRow::Body,
+                TableCell::Body,
+                [name.into(), count.to_string()],
+            );
+        }
+
+        Org {
+            arena,
+            root,
+            source_path: None,
+        }
+    }
+
+    /// Returns the plain text of the first paragraph before the first
+    /// headline, or the first paragraph under the first headline if there
+    /// is none, with whitespaces collapsed.
+    ///
+    /// With `max_chars`, the text is truncated to at most `max_chars`
+    /// characters, cutting at the last whitespace if possible.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse("#+TITLE: Notes\n* Intro\n*Orgize* parses\norg-mode files.\n");
+    /// assert_eq!(
+    ///     org.first_paragraph_text(None).as_deref(),
+    ///     Some("Orgize parses org-mode files.")
+    /// );
+    /// assert_eq!(
+    ///     org.first_paragraph_text(Some(20)).as_deref(),
+    ///     Some("Orgize parses")
+    /// );
+    ///
+    /// assert_eq!(Org::parse("* Intro\n* Usage\ntext\n").first_paragraph_text(None), None);
+    ///
+    /// let org = Org::parse("xé café");
+    /// assert_eq!(org.first_paragraph_text(Some(1)).as_deref(), Some("x"));
+    /// assert_eq!(org.first_paragraph_text(Some(5)).as_deref(), Some("xé"));
+    /// ```
+    pub fn first_paragraph_text(&self, max_chars: Option<usize>) -> Option<Cow<'_, str>> {
+        let first_headline = self
+            .root
+            .children(&self.arena)
+            .find(|&node| matches!(self[node], Element::Headline { .. }));
+        let paragraph = self
+            .root
+            .children(&self.arena)
+            .filter(|&node| matches!(self[node], Element::Section))
+            .chain(first_headline)
+            .flat_map(|node| node.descendants(&self.arena))
+            .find(|&node| matches!(self[node], Element::Paragraph { .. }))?;
+
+        let mut children = paragraph.children(&self.arena);
+        let mut text = match (&children.next().map(|n| &self[n]), children.next()) {
+            // borrows the text if it's already on one line
+            (Some(Element::Text { value }), None)
+                if value.split_whitespace().collect::<Vec<_>>().join(" ") == value.trim() =>
+            {
+                Cow::Borrowed(value.trim())
+            }
+            _ => {
+                let mut text = String::new();
+                write_plain_text(self, paragraph, &mut text);
+                Cow::Owned(text.split_whitespace().collect::<Vec<_>>().join(" "))
+            }
+        };
+
+        if let Some(max_chars) = max_chars {
+            text = match text {
+                Cow::Borrowed(text) => Cow::Borrowed(truncate(text, max_chars)),
+                Cow::Owned(text) => Cow::Owned(truncate(&text, max_chars).to_string()),
+            };
+        }
+
+        Some(text)
+    }
+
+    /// Returns a preview of this document, in at most `max_chars`
+    /// characters, like for `<meta name="description">`.
+    ///
+    /// It's the text of [`Org::first_paragraph_text`], or the titles of the
+    /// first three level-1 headlines if there is no such paragraph. Text
+    /// which doesn't fit is cut at a whitespace and ends with `…`.
+    ///
+    /// [`Org::first_paragraph_text`]: #method.first_paragraph_text
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse("* Intro\nOrgize parses org-mode files.\n");
+    /// assert_eq!(org.summary(100), "Orgize parses org-mode files.");
+    /// assert_eq!(org.summary(20), "Orgize parses…");
+    ///
+    /// let org = Org::parse("* Intro\n* Usage\n* /API/\n* License\n");
+    /// assert_eq!(org.summary(100), "Intro, Usage, API");
+    ///
+    /// let org = Org::parse("aé bé");
+    /// assert_eq!(org.summary(2), "a…");
+    /// assert_eq!(org.summary(4), "aé…");
+    /// ```
+    pub fn summary(&self, max_chars: usize) -> String {
+        let text = match self.first_paragraph_text(None) {
+            Some(text) => text.into_owned(),
+            None => {
+                let titles: Vec<_> = self
+                    .document()
+                    .children(self)
+                    .take(3)
+                    .map(|headline| {
+                        let mut text = String::new();
+                        write_plain_text(self, headline.title_node(), &mut text);
+                        text.split_whitespace().collect::<Vec<_>>().join(" ")
+                    })
+                    .collect();
+                titles.join(", ")
+            }
+        };
+
+        if text.chars().count() <= max_chars {
+            return text;
+        }
+        let mut summary = truncate(&text, max_chars.saturating_sub(1)).to_string();
+        summary.push('…');
+        summary
+    }
+
+    /// Returns `true` if element `node` or any of its descendants is a link.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse("* Title\n*bold [[https://example.com][link]]*\n");
+    /// let headline = org.headlines().next().unwrap();
+    ///
+    /// assert!(!org.contains_link(headline.title_node()));
+    /// assert!(org.contains_link(headline.section_node().unwrap()));
+    /// ```
+    pub fn contains_link(&self, node: NodeId) -> bool {
+        node.descendants(&self.arena)
+            .any(|node| matches!(self[node], Element::Link(_)))
+    }
+
+    /// Returns all links inside element `node`, in document order.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse("* [[file:a.org][A]]\n/see [[https://example.com]]/\n");
+    /// let headline = org.headlines().next().unwrap();
+    /// let links = org.extract_links(headline.headline_node());
+    ///
+    /// assert_eq!(links.len(), 2);
+    /// assert_eq!(links[0].path, "file:a.org");
+    /// assert_eq!(links[1].path, "https://example.com");
+    /// ```
+    pub fn extract_links(&self, node: NodeId) -> Vec<&Link<'_>> {
+        node.descendants(&self.arena)
+            .filter_map(|node| match &self[node] {
+                Element::Link(link) => Some(link),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Flattens a nested list into its items, paired with their depth.
+    ///
+    /// Items of list `node` have depth 0, items of lists nested directly in
+    /// them have depth 1, and so on. Returns an empty vector if `node` is not
+    /// a list.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse("- a\n  1. b\n     + c\n- d\n");
+    /// let section = org.document().section_node().unwrap();
+    /// let list = org.arena()[section].first_child().unwrap();
+    ///
+    /// let items = org.flatten_list_items(list);
+    /// let bullets: Vec<_> = items
+    ///     .iter()
+    ///     .map(|(depth, item)| (*depth, item.bullet.trim()))
+    ///     .collect();
+    /// assert_eq!(bullets, vec![(0, "-"), (1, "1."), (2, "+"), (0, "-")]);
+    /// ```
+    pub fn flatten_list_items(&self, node: NodeId) -> Vec<(usize, &ListItem<'_>)> {
+        fn flatten<'a, 'b>(
+            org: &'b Org<'a>,
+            node: NodeId,
+            depth: usize,
+            items: &mut Vec<(usize, &'b ListItem<'a>)>,
+        ) {
+            if let Element::List(_) = &org[node] {
+                for child in node.children(&org.arena) {
+                    if let Element::ListItem(item) = &org[child] {
+                        items.push((depth, item));
+                        for grandchild in child.children(&org.arena) {
+                            flatten(org, grandchild, depth + 1, items);
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut items = Vec::new();
+        flatten(self, node, 0, &mut items);
+        items
+    }
+
+    /// Renders element `node` and its descendants as html, without the
+    /// surrounding document.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse("* *Hello* world\n| a | /b/ |\n");
+    /// let headline = org.headlines().next().unwrap();
+    /// assert_eq!(
+    ///     org.node_to_html(headline.title_node()),
+    ///     "<h1><b>Hello</b> world</h1>"
+    /// );
+    ///
+    /// let table = org.arena()[headline.section_node().unwrap()].first_child().unwrap();
+    /// let cell = org.arena()[org.arena()[table].first_child().unwrap()]
+    ///     .last_child()
+    ///     .unwrap();
+    /// assert_eq!(org.node_to_html(cell), "<td><i>b</i></td>");
+    /// ```
+    pub fn node_to_html(&self, node: NodeId) -> String {
+        let mut handler = DefaultHtmlHandler::default();
+        let mut writer = Vec::new();
+        for edge in node.traverse(&self.arena) {
+            let _ = match edge {
+                NodeEdge::Start(node) => handler.start(&mut writer, &self[node]),
+                NodeEdge::End(node) => handler.end(&mut writer, &self[node]),
+            };
+        }
+        String::from_utf8_lossy(&writer).into_owned()
+    }
+
+    /// Returns an owned copy of this `Org` struct, without nodes for which
+    /// `excluded` returns `true` and their descendants. The root node is
+    /// always copied.
+    pub(crate) fn copy_without<F>(&self, excluded: F) -> Org<'static>
+    where
+        F: Fn(NodeId) -> bool,
+    {
+        let mut arena = Arena::new();
+        let mut parents: Vec<NodeId> = Vec::new();
+        let mut root = None;
+        let mut skipped = None;
+        for edge in self.root.traverse(&self.arena) {
+            match edge {
+                NodeEdge::Start(node) if skipped.is_none() => {
+                    if node != self.root && excluded(node) {
+                        skipped = Some(node);
+                        continue;
+                    }
+                    let copy = arena.new_node(self[node].clone().into_owned());
+                    match parents.last() {
+                        Some(parent) => parent.append(copy, &mut arena),
+                        None => root = Some(copy),
+                    }
+                    parents.push(copy);
+                }
+                NodeEdge::End(node) if skipped == Some(node) => skipped = None,
+                NodeEdge::End(_) if skipped.is_none() => {
+                    parents.pop();
+                }
+                _ => (),
+            }
+        }
+
+        Org {
+            arena,
+            root: root.expect("root is always copied"),
+            source_path: self.source_path.clone(),
+        }
+    }
+
+    /// Renders element `node` and its descendants back to org syntax.
+    ///
+    /// List item contents, including checkboxes and `::` description
+    /// separators, are kept as written and nested lists keep their
+    /// indentation, so a list parsed from a document serialises back to
+    /// the same text.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let text = "- [X] one\n  1. nested\n  2. [ ] two\n- term :: description\n";
+    /// let org = Org::parse(text);
+    /// let section = org.document().section_node().unwrap();
+    /// let list = org.arena()[section].first_child().unwrap();
+    ///
+    /// assert_eq!(org.node_to_org(list), text);
+    /// ```
+    pub fn node_to_org(&self, node: NodeId) -> String {
+        let mut handler = DefaultOrgHandler;
+        let mut writer = Vec::new();
+        for edge in node.traverse(&self.arena) {
+            let _ = match edge {
+                NodeEdge::Start(node) => handler.start(&mut writer, &self[node]),
+                NodeEdge::End(node) => handler.end(&mut writer, &self[node]),
+            };
+        }
+        String::from_utf8_lossy(&writer).into_owned()
+    }
+
     /// Writes an `Org` struct as html format.
     pub fn write_html<W>(&self, writer: W) -> Result<(), Error>
     where
         W: Write,
     {
-        self.write_html_custom(writer, &mut DefaultHtmlHandler)
+        self.write_html_custom(writer, &mut DefaultHtmlHandler::default())
     }
 
     /// Writes an `Org` struct as html format with custom `HtmlHandler`.
@@ -180,6 +943,55 @@ impl<'a> IndexMut<NodeId> for Org<'a> {
     }
 }
 
+// returns at most `max_chars` characters of `text`, cut at a whitespace if possible
+fn truncate(text: &str, max_chars: usize) -> &str {
+    let (end, next) = match text.char_indices().nth(max_chars) {
+        Some(next) => next,
+        None => return text,
+    };
+    // the next char is included, so text can be cut right before it
+    let end = match text[..end + next.len_utf8()].rfind(char::is_whitespace) {
+        Some(space) if space > 0 => space,
+        _ => end,
+    };
+    text[..end].trim_end()
+}
+
+// counts the word being read, if it contains an alphanumeric character
+fn end_word(word: &mut Option<bool>, count: &mut usize) {
+    if word.take() == Some(true) {
+        *count += 1;
+    }
+}
+
+// removes up to `width` leading spaces from each line of `text`, except the
+// first one if `skip_first` is `true`
+fn strip_indentation(text: &str, width: usize, skip_first: bool) -> String {
+    let lines: Vec<_> = text
+        .split('\n')
+        .enumerate()
+        .map(|(index, line)| {
+            if index == 0 && skip_first {
+                line
+            } else {
+                let spaces = line.len() - line.trim_start_matches(' ').len();
+                &line[spaces.min(width)..]
+            }
+        })
+        .collect();
+    lines.join("\n")
+}
+
+// returns the byte offset of `slice` in `source`, if it's borrowed from it
+pub(crate) fn offset_in(source: &str, slice: &str) -> Option<usize> {
+    let offset = (slice.as_ptr() as usize).checked_sub(source.as_ptr() as usize)?;
+    if offset + slice.len() <= source.len() {
+        Some(offset)
+    } else {
+        None
+    }
+}
+
 #[cfg(feature = "ser")]
 use serde::{ser::Serializer, Serialize};
 
@@ -191,3 +1003,170 @@ impl Serialize for Org<'_> {
         serializer.serialize_newtype_struct("Org", &Node::new(self.root, &self.arena))
     }
 }
+
+#[test]
+fn keyword_positions() {
+    let org = Org::parse(
+        "\n#+TITLE: Notes\n\n\n* Intro\n:PROPERTIES:\n:ID: 1\n:END:\n| a |\n#+caption: Été\n",
+    );
+    let positions = org.keyword_positions();
+    assert_eq!(positions["TITLE"], [2]);
+    assert_eq!(positions["CAPTION"], [10]);
+
+    let org = Org::parse_string("* Intro\n#+TITLE: Notes\n".to_string());
+    assert_eq!(org.keyword_positions()["TITLE"], [2]);
+
+    assert!(Org::parse("").keyword_positions().is_empty());
+}
+
+#[test]
+fn try_parse_custom() {
+    let config = ParseConfig {
+        strict_planning: true,
+        ..Default::default()
+    };
+    let line = |text| match Org::try_parse_custom(text, &config) {
+        Err(ParseError::MalformedPlanning { line }) => Some(line),
+        Ok(_) => None,
+    };
+
+    assert_eq!(line("* a\ntext\n\n  DEADLINE: soon\n"), Some(4));
+    assert_eq!(
+        line("* a\nSCHEDULED: <2019-04-08 Mon>\ntext\nCLOSED:\n"),
+        Some(4)
+    );
+    assert_eq!(line("SCHEDULED: x\n* a\n"), Some(1));
+    assert_eq!(line("* a\n#+begin_src\nSCHEDULED: x\n#+end_src\n"), None);
+    assert_eq!(line(""), None);
+
+    // `parse_custom` never fails
+    let org = Org::parse_custom("* a\ntext\nDEADLINE: soon\n", &config);
+    assert_eq!(org.headlines().count(), 1);
+}
+
+#[test]
+fn statistics_table() {
+    let org = Org::parse("").statistics_table();
+    assert_eq!(
+        org.to_pretty_org(2),
+        "| Element Type | Count |\n|--------------+-------|\n"
+    );
+
+    let org = Org::parse("* Été\n| a |\n").statistics_table();
+    let table = org.root.descendants(&org.arena).nth(2).unwrap();
+    assert!(matches!(
+        org[table],
+        Element::Table(Table::Org {
+            has_header: true,
+            ..
+        })
+    ));
+    assert_eq!(table.children(&org.arena).count(), 9);
+    assert_eq!(
+        org.to_pretty_org(2),
+        "| Element Type | Count |\n\
+         |--------------+-------|\n\
+         | text         | 2     |\n\
+         | headline     | 1     |\n\
+         | section      | 1     |\n\
+         | table        | 1     |\n\
+         | table-cell   | 1     |\n\
+         | table-row    | 1     |\n\
+         | title        | 1     |\n"
+    );
+}
+
+#[test]
+fn extract_links() {
+    let org = Org::parse("");
+    assert!(!org.contains_link(org.root));
+    assert!(org.extract_links(org.root).is_empty());
+
+    let org = Org::parse("[[https://例え.jp][*日本*]] and [[file:été.org]]\n");
+    let links = org.extract_links(org.root);
+    assert_eq!(links.len(), 2);
+    assert_eq!(links[0].path, "https://例え.jp");
+    assert_eq!(links[0].desc.as_deref(), Some("*日本*"));
+    assert_eq!(links[1].path, "file:été.org");
+
+    // the node itself can be a link
+    let link = org
+        .root
+        .descendants(&org.arena)
+        .find(|&node| matches!(org[node], Element::Link(_)))
+        .unwrap();
+    assert!(org.contains_link(link));
+    assert_eq!(org.extract_links(link).len(), 1);
+}
+
+#[test]
+fn flatten_list_items() {
+    let org = Org::parse("");
+    assert!(org.flatten_list_items(org.root).is_empty());
+
+    let org = Org::parse("- été\n  text\n  - 日本\n\n    + deep\n- last\n");
+    let section = org.document().section_node().unwrap();
+    let list = org.arena[section].first_child().unwrap();
+
+    let items: Vec<_> = org
+        .flatten_list_items(list)
+        .into_iter()
+        .map(|(depth, item)| (depth, item.bullet.trim(), item.indent))
+        .collect();
+    assert_eq!(items, [(0, "-", 0), (1, "-", 2), (2, "+", 4), (0, "-", 0)]);
+
+    // not a list
+    let item = org.arena[list].first_child().unwrap();
+    assert!(org.flatten_list_items(item).is_empty());
+}
+
+#[test]
+fn org_indent_mode() {
+    let parse = |source: &str, org_indent_mode: bool| {
+        let config = ParseConfig {
+            org_indent_mode,
+            ..Default::default()
+        };
+        let mut writer = Vec::new();
+        Org::parse_custom(source, &config)
+            .write_org(&mut writer)
+            .unwrap();
+        String::from_utf8(writer).unwrap()
+    };
+
+    assert_eq!(parse("", true), "");
+
+    let source = "  intro\n* T\n  été\n    日本\n x\n";
+    // text before the first headline is not indented
+    assert_eq!(parse(source, true), "  intro\n* T\nété\n  日本\nx\n");
+    assert_eq!(parse(source, false), source);
+
+    let source = "** T\n   #+BEGIN_SRC rust\n     code\n   #+END_SRC\n   : fixed\n   :   more\n   \
+                  #+KEY: v\n   # comment\n   +-+\n   | b |\n   +-+\n";
+    assert_eq!(
+        parse(source, true),
+        "** T\n#+BEGIN_SRC rust\n  code\n#+END_SRC\n: fixed\n:   more\n\
+         #+KEY: v\n# comment\n+-+\n| b |\n+-+\n"
+    );
+
+    // `#+STARTUP: indent` enables it, the last option wins
+    let source = "#+STARTUP: overview indent\n* T\n  text\n";
+    assert_eq!(
+        parse(source, false),
+        "#+STARTUP: overview indent\n* T\ntext\n"
+    );
+    let source = "#+STARTUP: indent\n#+startup: noindent\n* T\n  text\n";
+    assert_eq!(parse(source, false), source);
+}
+
+#[test]
+fn keyword_value() {
+    assert_eq!(Org::parse("").keyword_value("TITLE"), None);
+
+    let org = Org::parse("#+TITLE:\n#+title: second\n* h\n#+DATE: été\n");
+    // the first keyword is used, even if empty
+    assert_eq!(org.keyword_value("Title"), Some(""));
+    // keywords in sections of headlines are found too
+    assert_eq!(org.keyword_value("date"), Some("été"));
+    assert_eq!(org.keyword_value(""), None);
+}
@@ -75,6 +75,14 @@ where
     }
 }
 
+#[test]
+fn test_line() {
+    assert_eq!(line("foo"), Ok(("", "foo")));
+    assert_eq!(line("foo\nbar"), Ok(("bar", "foo")));
+    assert_eq!(line("foo\r\nbar"), Ok(("bar", "foo")));
+    assert_eq!(line("\r\n"), Ok(("", "")));
+}
+
 #[test]
 fn test_lines_while() {
     assert_eq!(lines_while(|line| line == "foo")("foo"), Ok(("", "foo")));
@@ -120,7 +120,7 @@ pub fn parse_container<'a, T: ElementArena<'a>>(
     while let Some(container) = containers.pop() {
         match container {
             Container::Document { content, node } => {
-                parse_section_and_headlines(arena, content, node, containers);
+                parse_section_and_headlines(arena, content, node, containers, config);
             }
             Container::Headline { content, node } => {
                 parse_headline_content(arena, content, node, containers, config);
@@ -145,7 +145,7 @@ pub fn parse_headline_content<'a, T: ElementArena<'a>>(
     let (tail, (title, content)) = Title::parse(content, config).unwrap();
     let node = arena.append(title, parent);
     containers.push(Container::Inline { content, node });
-    parse_section_and_headlines(arena, tail, parent, containers);
+    parse_section_and_headlines(arena, tail, parent, containers, config);
 }
 
 pub fn parse_section_and_headlines<'a, T: ElementArena<'a>>(
@@ -153,6 +153,7 @@ pub fn parse_section_and_headlines<'a, T: ElementArena<'a>>(
     content: &'a str,
     parent: NodeId,
     containers: &mut Vec<Container<'a>>,
+    config: &ParseConfig,
 ) {
     let content = blank_lines_count(content).0;
 
@@ -162,7 +163,9 @@ pub fn parse_section_and_headlines<'a, T: ElementArena<'a>>(
 
     let mut last_end = 0;
     for i in memchr_iter(b'\n', content.as_bytes()).chain(once(content.len())) {
-        if let Some((mut tail, (headline_content, level))) = parse_headline(&content[last_end..]) {
+        if let Some((mut tail, (headline_content, level))) =
+            parse_headline(&content[last_end..], config)
+        {
             if last_end != 0 {
                 let node = arena.append(Element::Section, parent);
                 let content = &content[0..last_end];
@@ -175,7 +178,7 @@ pub fn parse_section_and_headlines<'a, T: ElementArena<'a>>(
                 node,
             });
 
-            while let Some((new_tail, (content, level))) = parse_headline(tail) {
+            while let Some((new_tail, (content, level))) = parse_headline(tail, config) {
                 debug_assert_ne!(tail, new_tail);
                 let node = arena.append(Element::Headline { level }, parent);
                 containers.push(Container::Headline { content, node });
@@ -635,10 +638,13 @@ pub fn blank_lines_count(input: &str) -> (&str, usize) {
     crate::parse::combinators::blank_lines_count(input).unwrap_or((input, 0))
 }
 
-pub fn parse_headline(input: &str) -> Option<(&str, (&str, usize))> {
-    let (input_, level) = parse_headline_level(input)?;
+pub fn parse_headline<'a>(
+    input: &'a str,
+    config: &ParseConfig,
+) -> Option<(&'a str, (&'a str, usize))> {
+    let (input_, level) = parse_headline_level(input, config)?;
     let (input_, content) = lines_while(move |line| {
-        parse_headline_level(line)
+        parse_headline_level(line, config)
             .map(|(_, l)| l > level)
             .unwrap_or(true)
     })(input_)
@@ -646,12 +652,43 @@ pub fn parse_headline(input: &str) -> Option<(&str, (&str, usize))> {
     Some((input_, (&input[0..level + content.len()], level)))
 }
 
-pub fn parse_headline_level(input: &str) -> Option<(&str, usize)> {
+pub fn parse_headline_level<'a>(input: &'a str, config: &ParseConfig) -> Option<(&'a str, usize)> {
     let (input, stars) = take_while1::<_, _, ()>(|c: char| c == '*')(input).ok()?;
 
-    if input.starts_with(' ') || input.starts_with('\n') || input.is_empty() {
+    if input.starts_with(' ')
+        || (config.allow_empty_headline_stars
+            && (input.starts_with('\n') || input.starts_with("\r\n") || input.is_empty()))
+    {
         Some((input, stars.len()))
     } else {
         None
     }
 }
+
+#[test]
+fn parse_empty_headline_stars() {
+    let config = ParseConfig {
+        allow_empty_headline_stars: true,
+        ..Default::default()
+    };
+
+    assert_eq!(parse_headline_level("*\n", &ParseConfig::default()), None);
+    assert_eq!(parse_headline_level("**", &ParseConfig::default()), None);
+    assert_eq!(
+        parse_headline_level("* a", &ParseConfig::default()),
+        Some((" a", 1))
+    );
+    assert_eq!(parse_headline_level("*\n", &config), Some(("\n", 1)));
+    assert_eq!(parse_headline_level("**\r\n", &config), Some(("\r\n", 2)));
+    assert_eq!(parse_headline_level("**", &config), Some(("", 2)));
+    assert_eq!(parse_headline_level("*a", &config), None);
+
+    assert_eq!(
+        parse_headline("* a\n*\ntext\n* b\n", &ParseConfig::default()),
+        Some(("* b\n", ("* a\n*\ntext\n", 1)))
+    );
+    assert_eq!(
+        parse_headline("* a\n*\ntext\n* b\n", &config),
+        Some(("*\ntext\n* b\n", ("* a\n", 1)))
+    );
+}
@@ -0,0 +1,210 @@
+use std::collections::HashMap;
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::Org;
+
+/// Template Error
+#[derive(Debug, PartialEq)]
+pub enum TemplateError {
+    /// `{{{` isn't closed by `}}}` on the same line, `line` starts from 1
+    UnclosedPlaceholder { line: usize },
+    /// Placeholder name contains other characters than letters, digits,
+    /// `_` and `-`, `line` starts from 1
+    InvalidVariableName { name: String, line: usize },
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TemplateError::UnclosedPlaceholder { line } => {
+                write!(f, "unclosed placeholder at line {}", line)
+            }
+            TemplateError::InvalidVariableName { name, line } => {
+                write!(f, "invalid variable name {:?} at line {}", name, line)
+            }
+        }
+    }
+}
+
+impl error::Error for TemplateError {}
+
+/// An Org document containing `{{{name}}}` variable placeholders.
+///
+/// Placeholders with arguments, like `{{{name(arg)}}}`, are regular Org
+/// macros and are left untouched.
+#[derive(Clone, Debug)]
+pub struct OrgTemplate {
+    source: String,
+}
+
+impl OrgTemplate {
+    /// Creates a template from Org source text, without checking its
+    /// placeholders. Use [`from_str`] to check them.
+    ///
+    /// [`from_str`]: #method.from_str
+    pub fn new<S: Into<String>>(source: S) -> OrgTemplate {
+        OrgTemplate {
+            source: source.into(),
+        }
+    }
+
+    /// Returns the Org source text of this template.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Returns the names of variables referenced by this template, in order of
+    /// first appearance and without duplicates.
+    ///
+    /// ```rust
+    /// use orgize::OrgTemplate;
+    ///
+    /// let template = OrgTemplate::new("* {{{title}}}\n{{{date}}} {{{title}}} {{{kbd(C-c)}}}\n");
+    /// assert_eq!(template.variable_names(), vec!["title", "date"]);
+    /// ```
+    pub fn variable_names(&self) -> Vec<&str> {
+        let mut names = Vec::new();
+        for (start, _) in self.source.match_indices("{{{") {
+            if let Some((name, _)) = placeholder(&self.source[start..]) {
+                if !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+        }
+        names
+    }
+
+    /// Substitutes the placeholders with values from `vars`. Placeholders
+    /// without a value are kept as is.
+    fn substitute(&self, vars: &HashMap<String, String>) -> String {
+        let mut output = String::with_capacity(self.source.len());
+        let mut rest = &*self.source;
+        while let Some(start) = rest.find("{{{") {
+            output.push_str(&rest[..start]);
+            rest = &rest[start..];
+            let value = placeholder(rest).and_then(|(name, len)| Some((vars.get(name)?, len)));
+            match value {
+                Some((value, len)) => {
+                    output.push_str(value);
+                    rest = &rest[len..];
+                }
+                None => {
+                    output.push_str("{{{");
+                    rest = &rest[3..];
+                }
+            }
+        }
+        output.push_str(rest);
+        output
+    }
+}
+
+impl FromStr for OrgTemplate {
+    type Err = TemplateError;
+
+    /// Parses Org source text as a template, checking that all `{{{`
+    /// are closed on the same line, and that placeholders without arguments
+    /// have valid variable names.
+    ///
+    /// ```rust
+    /// use orgize::{OrgTemplate, TemplateError};
+    /// use std::str::FromStr;
+    ///
+    /// let template = OrgTemplate::from_str("* {{{title}}}\n{{{kbd(C-c)}}}\n").unwrap();
+    /// assert_eq!(template.variable_names(), vec!["title"]);
+    ///
+    /// assert_eq!(
+    ///     OrgTemplate::from_str("* ok\n{{{a b}}}\n").unwrap_err(),
+    ///     TemplateError::InvalidVariableName {
+    ///         name: "a b".into(),
+    ///         line: 2
+    ///     }
+    /// );
+    /// assert_eq!(
+    ///     OrgTemplate::from_str("{{{title}}\n").unwrap_err(),
+    ///     TemplateError::UnclosedPlaceholder { line: 1 }
+    /// );
+    /// ```
+    fn from_str(s: &str) -> Result<OrgTemplate, TemplateError> {
+        for (index, line) in s.lines().enumerate() {
+            for (start, _) in line.match_indices("{{{") {
+                let contents = &line[start + 3..];
+                let end = contents
+                    .find("}}}")
+                    .ok_or(TemplateError::UnclosedPlaceholder { line: index + 1 })?;
+                let contents = &contents[..end];
+                // macros with arguments aren't variables
+                if !contents.contains('(') && placeholder(&line[start..]).is_none() {
+                    return Err(TemplateError::InvalidVariableName {
+                        name: contents.trim().to_string(),
+                        line: index + 1,
+                    });
+                }
+            }
+        }
+
+        Ok(OrgTemplate::new(s))
+    }
+}
+
+/// Parses a `{{{name}}}` placeholder at the start of `input`, returning its
+/// name and length.
+fn placeholder(input: &str) -> Option<(&str, usize)> {
+    let contents = input.strip_prefix("{{{")?;
+    let end = contents.find("}}}")?;
+    let name = contents[..end].trim();
+    if !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+    {
+        Some((name, end + 6))
+    } else {
+        None
+    }
+}
+
+impl Org<'_> {
+    /// Instantiates `template`, substituting its `{{{name}}}` placeholders
+    /// with values from `vars`, and parses the result.
+    ///
+    /// Values are inserted verbatim, so they can contain Org markup.
+    /// Placeholders without a value are kept, and are then parsed as macros.
+    ///
+    /// ```rust
+    /// use orgize::{Org, OrgTemplate};
+    /// use std::collections::HashMap;
+    ///
+    /// let template = OrgTemplate::new("* Meeting {{{date}}}\nWith {{{people}}}.\n");
+    ///
+    /// let mut vars = HashMap::new();
+    /// vars.insert("date".to_string(), "<2021-03-04 Thu>".to_string());
+    /// vars.insert("people".to_string(), "*Jane*".to_string());
+    /// let org = Org::apply_template(&template, &vars);
+    ///
+    /// let mut writer = Vec::new();
+    /// org.write_org(&mut writer).unwrap();
+    /// assert_eq!(
+    ///     String::from_utf8(writer).unwrap(),
+    ///     "* Meeting <2021-03-04 Thu>\nWith *Jane*.\n"
+    /// );
+    /// ```
+    pub fn apply_template(template: &OrgTemplate, vars: &HashMap<String, String>) -> Org<'static> {
+        Org::parse_string(template.substitute(vars))
+    }
+}
+
+#[test]
+fn substitute() {
+    let template = OrgTemplate::new("{{{a}}} {{{ b }}} {{{c}}} {{{a(1)}}} {{{a}}");
+    let mut vars = HashMap::new();
+    vars.insert("a".to_string(), "1".to_string());
+    vars.insert("b".to_string(), "{{{a}}}".to_string());
+
+    assert_eq!(
+        template.substitute(&vars),
+        "1 {{{a}}} {{{c}}} {{{a(1)}}} {{{a}}"
+    );
+}
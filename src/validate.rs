@@ -1,8 +1,8 @@
 use indextree::NodeId;
 use std::ops::RangeInclusive;
 
-use crate::elements::{Element, Table, TableCell, TableRow};
-use crate::Org;
+use crate::elements::{Element, FnDef, FnRef, Table, TableCell, TableRow};
+use crate::{Headline, Org};
 
 /// Validation Error
 #[derive(Debug)]
@@ -44,6 +44,66 @@ impl ValidationError {
 
 pub type ValidationResult<T> = Result<T, ValidationError>;
 
+/// Headline Level Error
+///
+/// Headline's level is more than one level deeper than the previous headline.
+#[derive(Debug)]
+pub struct LevelError {
+    /// Expected maximum level
+    pub expected: usize,
+    /// Actual level of the headline
+    pub found: usize,
+    /// Node of the headline
+    pub at: NodeId,
+}
+
+/// Property Drawer Error
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DrawerError {
+    /// Drawer name is not valid
+    InvalidName { name: String },
+    /// Drawer is not closed by `:END:`, so it's parsed as a paragraph
+    MissingEnd { at: NodeId },
+    /// Properties drawer is not placed right after the headline
+    Misplaced { at: NodeId },
+    /// Property key appears more than once
+    ///
+    /// `at` is the headline's title node, and `index` is the position of the
+    /// property in its properties drawer.
+    DuplicateKey {
+        key: String,
+        at: NodeId,
+        index: usize,
+    },
+    /// Property key is empty or contains whitespace
+    InvalidKey {
+        key: String,
+        at: NodeId,
+        index: usize,
+    },
+    /// Property value contains control characters
+    InvalidValue {
+        key: String,
+        at: NodeId,
+        index: usize,
+    },
+}
+
+/// Headline Title Error
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TitleError {
+    /// Todo keyword is not defined in parse config
+    InvalidKeyword(String),
+    /// Priority is not an uppercase letter
+    InvalidPriority(char),
+    /// Tag is empty or contains invalid characters
+    InvalidTag(String),
+    /// Property key is empty
+    EmptyPropertyKey,
+    /// Property key appears more than once
+    DuplicatePropertyKey(String),
+}
+
 impl Org<'_> {
     /// Validates an `Org` struct.
     pub fn validate(&self) -> Vec<ValidationError> {
@@ -157,7 +217,6 @@ impl Org<'_> {
                 | Element::Text { .. }
                 | Element::Timestamp(_)
                 | Element::Verbatim { .. }
-                | Element::FnDef(_)
                 | Element::Clock(_)
                 | Element::Comment { .. }
                 | Element::FixedWidth { .. }
@@ -184,6 +243,7 @@ impl Org<'_> {
                     expect_children!(node_id);
                 }
                 Element::ListItem(_)
+                | Element::FnDef(_)
                 | Element::Drawer(_)
                 | Element::TableCell(_)
                 | Element::Table(_) => (),
@@ -192,6 +252,214 @@ impl Org<'_> {
         errors
     }
 
+    /// Checks for headlines jumping more than one level, e.g. a level 3
+    /// headline right after a level 1 headline.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse("* a\n*** b\n** c\n*** d\n* e\n** f\n");
+    /// let errors = org.validate_headline_levels();
+    ///
+    /// assert_eq!(errors.len(), 1);
+    /// assert_eq!(errors[0].expected, 2);
+    /// assert_eq!(errors[0].found, 3);
+    /// ```
+    pub fn validate_headline_levels(&self) -> Vec<LevelError> {
+        let mut errors = Vec::new();
+        let mut previous = 0;
+        for headline in self.headlines() {
+            let level = headline.level();
+            if level > previous + 1 {
+                errors.push(LevelError {
+                    expected: previous + 1,
+                    found: level,
+                    at: headline.headline_node(),
+                });
+            }
+            previous = level;
+        }
+        errors
+    }
+
+    /// Returns footnote definitions which are not referenced anywhere in
+    /// the document.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse("Text[fn:1].\n\n[fn:1] Used.\n\n[fn:2] Unused.\n");
+    /// let orphaned = org.orphaned_footnotes();
+    ///
+    /// assert_eq!(orphaned.len(), 1);
+    /// assert_eq!(orphaned[0].label, "2");
+    /// ```
+    pub fn orphaned_footnotes(&self) -> Vec<&FnDef<'_>> {
+        let references: Vec<_> = self.footnote_refs().map(|fn_ref| &fn_ref.label).collect();
+        self.root
+            .descendants(&self.arena)
+            .filter_map(|node| match &self[node] {
+                Element::FnDef(fn_def) if !references.contains(&&fn_def.label) => Some(fn_def),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns footnote references whose label is not defined anywhere in
+    /// the document.
+    ///
+    /// Inline footnotes, like `[fn:label:definition]`, define their own
+    /// label, and are never returned.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse("A[fn:1], B[fn:2], C[fn:3:inline] and D[fn:3].\n\n[fn:1] Defined.\n");
+    /// let undefined = org.undefined_footnote_refs();
+    ///
+    /// assert_eq!(undefined.len(), 1);
+    /// assert_eq!(undefined[0].label, "2");
+    /// ```
+    pub fn undefined_footnote_refs(&self) -> Vec<&FnRef<'_>> {
+        let definitions: Vec<_> = self
+            .root
+            .descendants(&self.arena)
+            .filter_map(|node| match &self[node] {
+                Element::FnDef(fn_def) => Some(&fn_def.label),
+                Element::FnRef(FnRef {
+                    label,
+                    definition: Some(_),
+                }) => Some(label),
+                _ => None,
+            })
+            .collect();
+        self.footnote_refs()
+            .filter(|fn_ref| fn_ref.definition.is_none() && !definitions.contains(&&fn_ref.label))
+            .collect()
+    }
+
+    /// Returns structural issues of the properties drawers in headlines.
+    ///
+    /// Duplicate property keys are only reported if `strict` is `true`, and
+    /// are compared case-insensitively.
+    ///
+    /// ```rust
+    /// use orgize::{DrawerError, Org};
+    ///
+    /// let org = Org::parse(
+    ///     "* A\n:PROPERTIES:\n:ID: 1\n:id: 2\n:MY KEY: 3\n:END:\n\
+    ///      * B\n:PROPERTIES:\n:ID: 1\n\
+    ///      * C\ntext\n:PROPERTIES:\n:ID: 1\n:END:\n",
+    /// );
+    ///
+    /// let errors: Vec<_> = org
+    ///     .find_broken_property_drawers(true)
+    ///     .into_iter()
+    ///     .map(|(headline, error)| (headline.title(&org).raw.to_string(), error))
+    ///     .collect();
+    ///
+    /// let a = org.headlines().next().unwrap().title_node();
+    /// assert_eq!(errors.len(), 4);
+    /// assert_eq!(
+    ///     errors[0],
+    ///     ("A".into(), DrawerError::DuplicateKey { key: "id".into(), at: a, index: 1 })
+    /// );
+    /// assert_eq!(
+    ///     errors[1],
+    ///     ("A".into(), DrawerError::InvalidKey { key: "MY KEY".into(), at: a, index: 2 })
+    /// );
+    /// assert!(matches!(errors[2], (ref title, DrawerError::MissingEnd { .. }) if title == "B"));
+    /// assert!(matches!(errors[3], (ref title, DrawerError::Misplaced { .. }) if title == "C"));
+    ///
+    /// assert_eq!(org.find_broken_property_drawers(false).len(), 3);
+    /// ```
+    pub fn find_broken_property_drawers(&self, strict: bool) -> Vec<(Headline, DrawerError)> {
+        let mut errors = Vec::new();
+
+        for headline in self.headlines() {
+            let title = headline.title(self);
+            let at = headline.title_node();
+            for (index, (key, value)) in title.properties.iter().enumerate() {
+                if strict
+                    && title
+                        .properties
+                        .iter()
+                        .take(index)
+                        .any(|(k, _)| k.eq_ignore_ascii_case(key))
+                {
+                    errors.push((
+                        headline,
+                        DrawerError::DuplicateKey {
+                            key: key.to_string(),
+                            at,
+                            index,
+                        },
+                    ));
+                }
+                if key.is_empty() || key.contains(char::is_whitespace) {
+                    errors.push((
+                        headline,
+                        DrawerError::InvalidKey {
+                            key: key.to_string(),
+                            at,
+                            index,
+                        },
+                    ));
+                }
+                if value.contains(char::is_control) {
+                    errors.push((
+                        headline,
+                        DrawerError::InvalidValue {
+                            key: key.to_string(),
+                            at,
+                            index,
+                        },
+                    ));
+                }
+            }
+
+            let sec_n = match headline.section_node() {
+                Some(sec_n) => sec_n,
+                None => continue,
+            };
+            for node in sec_n.descendants(&self.arena) {
+                match &self[node] {
+                    Element::Drawer(drawer) if drawer.name.eq_ignore_ascii_case("PROPERTIES") => {
+                        errors.push((headline, DrawerError::Misplaced { at: node }));
+                    }
+                    Element::Paragraph { .. } => {
+                        let starts_drawer = self.arena[node]
+                            .first_child()
+                            .map(|text| match &self[text] {
+                                Element::Text { value } => value
+                                    .lines()
+                                    .next()
+                                    .map(|line| line.trim().eq_ignore_ascii_case(":PROPERTIES:"))
+                                    .unwrap_or_default(),
+                                _ => false,
+                            })
+                            .unwrap_or_default();
+                        if starts_drawer {
+                            errors.push((headline, DrawerError::MissingEnd { at: node }));
+                        }
+                    }
+                    _ => (),
+                }
+            }
+        }
+
+        errors
+    }
+
+    fn footnote_refs(&self) -> impl Iterator<Item = &FnRef<'_>> {
+        self.root
+            .descendants(&self.arena)
+            .filter_map(move |node| match &self[node] {
+                Element::FnRef(fn_ref) => Some(fn_ref),
+                _ => None,
+            })
+    }
+
     pub(crate) fn debug_validate(&self) {
         if cfg!(debug_assertions) {
             let errors = self.validate();